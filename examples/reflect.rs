@@ -0,0 +1,67 @@
+use std::{env, process};
+
+use jlrs::{data::layout::reflect::Reflector, prelude::*};
+
+const USAGE: &str = "usage: reflect [--using MODULE]... [--output FILE] TYPE...";
+
+// Generates layouts for Julia types, e.g.:
+//
+// cargo run --example reflect -- --using LinearAlgebra LinearAlgebra.QRPivoted
+fn main() {
+    let mut modules = vec![];
+    let mut output = None;
+    let mut types = vec![];
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--using" => modules.push(args.next().expect(USAGE)),
+            "--output" | "-o" => output = Some(args.next().expect(USAGE)),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => types.push(arg),
+        }
+    }
+
+    if types.is_empty() {
+        eprintln!("{}", USAGE);
+        process::exit(1);
+    }
+
+    let julia = Builder::new().start_local().expect("Could not init Julia");
+
+    let result = julia.local_scope::<_, 0>(|frame| {
+        for module in &modules {
+            // Safety: loading a package only runs its initialization code.
+            unsafe { Value::eval_string(&frame, format!("using {}", module)) }.map_err(|e| {
+                // Safety: the exception is used before it can be garbage collected.
+                let msg = unsafe { e.as_value() }.error_string_or("<Cannot display exception>");
+                format!("could not load {}: {}", module, msg)
+            })?;
+        }
+
+        let mut reflector = Reflector::new();
+        for ty in &types {
+            reflector
+                .reflect(&frame, ty)
+                .map_err(|e| format!("could not reflect {}: {}", ty, e))?;
+        }
+
+        match output {
+            Some(ref path) => reflector
+                .write_to(path)
+                .map_err(|e| format!("could not write to {}: {}", path, e)),
+            None => {
+                print!("{}", reflector);
+                Ok(())
+            }
+        }
+    });
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
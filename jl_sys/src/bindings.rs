@@ -159,6 +159,14 @@ extern "C" {
 
     pub static mut jl_expr_type: *mut crate::types::jl_datatype_t;
 
+    pub static mut jl_method_type: *mut crate::types::jl_datatype_t;
+
+    pub static mut jl_method_instance_type: *mut crate::types::jl_datatype_t;
+
+    pub static mut jl_methtable_type: *mut crate::types::jl_datatype_t;
+
+    pub static mut jl_code_instance_type: *mut crate::types::jl_datatype_t;
+
     pub static mut jl_emptysvec: *mut crate::types::jl_svec_t;
 
     pub static mut jl_emptytuple: *mut crate::types::jl_value_t;
@@ -324,6 +332,23 @@ extern "C" {
         f: *mut std::ffi::c_void,
     );
 
+    pub fn jl_gc_live_bytes() -> i64;
+
+    pub fn jl_gc_total_hrtime() -> u64;
+
+    #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8",)))]
+    pub fn jl_gc_set_max_memory(max_mem: u64);
+
+    pub fn jl_gc_counted_malloc(sz: usize) -> *mut std::ffi::c_void;
+
+    pub fn jl_gc_counted_realloc_with_old_size(
+        p: *mut std::ffi::c_void,
+        old: usize,
+        sz: usize,
+    ) -> *mut std::ffi::c_void;
+
+    pub fn jl_gc_counted_free_with_size(p: *mut std::ffi::c_void, sz: usize);
+
     pub fn jl_subtype(
         a: *mut crate::types::jl_value_t,
         b: *mut crate::types::jl_value_t,
@@ -1377,4 +1402,10 @@ extern "C" {
         var: *mut crate::types::jl_sym_t,
         val: *mut crate::types::jl_value_t,
     );
+
+    pub fn jlrs_new_module(
+        name: *mut crate::types::jl_sym_t,
+        parent: *mut crate::types::jl_module_t,
+        std_imports: std::ffi::c_int,
+    ) -> *mut crate::types::jl_module_t;
 }
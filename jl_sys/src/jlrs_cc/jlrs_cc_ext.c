@@ -426,6 +426,27 @@ extern "C"
     jl_set_global(m, var, val);
 #endif
     }
+
+    jl_module_t *jlrs_new_module(jl_sym_t *name, jl_module_t *parent, int std_imports)
+    {
+#if JULIA_VERSION_MINOR >= 10
+        jl_module_t *m = jl_new_module(name, parent);
+#else
+    jl_module_t *m = jl_new_module(name);
+    JL_GC_PUSH1(&m);
+    m->parent = parent;
+    jl_gc_wb(m, parent);
+    JL_GC_POP();
+#endif
+        if (std_imports)
+        {
+            JL_GC_PUSH1(&m);
+            jl_add_standard_imports(m);
+            JL_GC_POP();
+        }
+
+        return m;
+    }
 #ifdef __cplusplus
 }
 #endif
\ No newline at end of file
@@ -121,6 +121,7 @@ extern "C" {
 #endif
 
     void jlrs_set_global(jl_module_t *m JL_ROOTING_ARGUMENT, jl_sym_t *var, jl_value_t *val JL_ROOTED_ARGUMENT);
+    jl_module_t *jlrs_new_module(jl_sym_t *name, jl_module_t *parent, int std_imports);
 #ifdef __cplusplus
 }
 #endif // __cplusplus
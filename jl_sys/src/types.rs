@@ -16,6 +16,12 @@ pub struct jl_array_t {
     _unused: [u8; 0],
 }
 
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct jl_code_instance_t {
+    _unused: [u8; 0],
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct jl_datatype_layout_t {
@@ -86,6 +92,24 @@ pub type jl_markfunc_t =
     unsafe extern "C" fn(ptls: *mut jl_tls_states_t, obj: *mut jl_value_t) -> usize;
 pub type jl_sweepfunc_t = unsafe extern "C" fn(obj: *mut jl_value_t);
 
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct jl_method_instance_t {
+    _unused: [u8; 0],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct jl_method_t {
+    _unused: [u8; 0],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct jl_methtable_t {
+    _unused: [u8; 0],
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct jl_module_t {
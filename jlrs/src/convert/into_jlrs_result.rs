@@ -2,17 +2,33 @@
 
 use crate::{
     data::managed::Managed,
-    error::{JlrsError, JlrsResult, JuliaResult, CANNOT_DISPLAY_VALUE},
+    error::{JlrsError, JlrsResult, JuliaResult},
 };
 
 /// Convert data to a `JlrsResult`.
 ///
 /// By default this trait is only implemented for `JuliaResult`. If an exception is thrown, it's
-/// converted to an error message by calling `Base.showerror`.
+/// converted to an [`Exception`] which contains the error message shown by `Base.showerror` and
+/// the name of the exception's type. Its fields and backtrace are only captured by
+/// [`IntoJlrsResult::into_jlrs_result_with_details`].
+///
+/// [`Exception`]: crate::error::Exception
 pub trait IntoJlrsResult<T> {
     /// Convert `self` to `JlrsResult` by calling `Base.showerror` if an exception has been
     /// thrown.
     fn into_jlrs_result(self) -> JlrsResult<T>;
+
+    /// Convert `self` to `JlrsResult` by calling `Base.showerror` if an exception has been
+    /// thrown, the fields and backtrace of the exception are captured too.
+    ///
+    /// By default this method calls [`IntoJlrsResult::into_jlrs_result`].
+    #[inline]
+    fn into_jlrs_result_with_details(self) -> JlrsResult<T>
+    where
+        Self: Sized,
+    {
+        self.into_jlrs_result()
+    }
 }
 
 impl<T> IntoJlrsResult<T> for JuliaResult<'_, '_, T> {
@@ -20,7 +36,15 @@ impl<T> IntoJlrsResult<T> for JuliaResult<'_, '_, T> {
     fn into_jlrs_result(self) -> JlrsResult<T> {
         match self {
             Ok(v) => Ok(v),
-            Err(e) => JlrsError::exception_error(e.error_string_or(CANNOT_DISPLAY_VALUE))?,
+            Err(e) => Err(JlrsError::from_exception(e.as_value()))?,
+        }
+    }
+
+    #[inline]
+    fn into_jlrs_result_with_details(self) -> JlrsResult<T> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(JlrsError::from_exception_with_details(e.as_value()))?,
         }
     }
 }
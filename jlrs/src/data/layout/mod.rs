@@ -82,7 +82,9 @@ pub mod f16;
 pub mod is_bits;
 pub mod julia_enum;
 pub mod nothing;
+pub mod reflect;
 pub mod tuple;
 pub mod typed_layout;
 pub mod union;
 pub mod valid_layout;
+pub mod verify;
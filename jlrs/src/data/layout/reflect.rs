@@ -0,0 +1,735 @@
+//! Generate layouts for Julia types.
+//!
+//! Layouts for Julia types can be derived, but writing them by hand is error-prone. A
+//! [`Reflector`] inspects Julia types at runtime and generates their layouts, including the
+//! layouts of all types that are stored inline in their fields. The generated code contains all
+//! `#[jlrs(...)]` attributes and derives the same traits as layouts generated by JlrsReflect.jl.
+//!
+//! Because the Julia types are inspected in a running session, the layouts can be generated as
+//! part of a Rust build pipeline, e.g. by a separate binary that writes them to a file. The
+//! `reflect` example is such a binary:
+//!
+//! ```text
+//! cargo run --example reflect -- --using MyPackage MyPackage.MyType MyPackage.MyOtherType
+//! ```
+//!
+//! The same can be done from your own code:
+//!
+//! ```ignore
+//! use jlrs::{data::layout::reflect::Reflector, prelude::*};
+//!
+//! let julia = Builder::new().start_local().unwrap();
+//!
+//! julia.local_scope::<_, 0>(|frame| {
+//!     unsafe { Value::eval_string(&frame, "using MyPackage").unwrap() };
+//!
+//!     Reflector::new()
+//!         .reflect(&frame, "MyPackage.MyType")
+//!         .unwrap()
+//!         .write_to("src/layouts.rs")
+//!         .unwrap();
+//! });
+//! ```
+//!
+//! A type with type parameters is reflected generically. Type parameters that are used as the
+//! type of a field become type parameters of the layout, all other type parameters are elided
+//! and a separate type constructor is generated. The generated code isn't formatted, run
+//! `rustfmt` on the output if necessary.
+//!
+//! Layouts are named after their Julia type without its module. Reflecting two types with the
+//! same name that are defined in different modules returns an error.
+//!
+//! Not all types can be reflected. Atomic fields, `Ptr` fields, custom primitive types and union
+//! fields that depend on a type parameter are not supported, reflecting a type that depends on
+//! such a type returns an error.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter, Result as FmtResult, Write as _},
+    fs, io,
+    path::Path,
+};
+
+use jlrs_macros::julia_version;
+use thiserror::Error;
+
+use crate::{
+    data::managed::{
+        datatype::DataType, module::Module, type_name::TypeName, type_var::TypeVar, union::Union,
+        union_all::UnionAll, value::Value, Managed as _,
+    },
+    error::{JlrsError, JlrsResult, CANNOT_DISPLAY_VALUE},
+    memory::target::{unrooted::Unrooted, Target},
+};
+
+/// Reasons a type can't be reflected.
+#[derive(Debug, Error)]
+pub enum ReflectError {
+    #[error("{name} is not a type")]
+    NotAType { name: String },
+    #[error("{ty} is not a struct type")]
+    NotAStruct { ty: String },
+    #[error("the layouts of {first} and {second} would both be named {name}")]
+    NameCollision {
+        name: String,
+        first: String,
+        second: String,
+    },
+    #[error("field {field} of {ty} can't be reflected: {reason}")]
+    UnsupportedField {
+        ty: String,
+        field: String,
+        reason: &'static str,
+    },
+}
+
+/// A layout generated by a [`Reflector`].
+#[derive(Clone, Debug)]
+pub struct ReflectedLayout {
+    julia_type: String,
+    rust_name: String,
+    source: String,
+    scope_lifetime: bool,
+    data_lifetime: bool,
+    layout_params: Vec<usize>,
+}
+
+impl ReflectedLayout {
+    /// The path of the Julia type, e.g. `Main.MyType`.
+    #[inline]
+    pub fn julia_type(&self) -> &str {
+        &self.julia_type
+    }
+
+    /// The name of the generated layout.
+    #[inline]
+    pub fn rust_name(&self) -> &str {
+        &self.rust_name
+    }
+
+    /// The generated code, including the type constructor if one is necessary.
+    #[inline]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+/// Generates layouts for Julia types.
+///
+/// See the [module-level docs] for more information.
+///
+/// [module-level docs]: self
+#[derive(Clone, Debug, Default)]
+pub struct Reflector {
+    layouts: BTreeMap<String, ReflectedLayout>,
+}
+
+impl Reflector {
+    /// Creates a new reflector without any layouts.
+    #[inline]
+    pub fn new() -> Self {
+        Reflector::default()
+    }
+
+    /// Generates the layout of the type at `path`, e.g. `"Main.MyType"`, and of all types that
+    /// are stored inline in its fields.
+    ///
+    /// The module that defines the type must have been loaded before calling this method.
+    pub fn reflect<'target, Tgt>(&mut self, target: &Tgt, path: &str) -> JlrsResult<&mut Self>
+    where
+        Tgt: Target<'target>,
+    {
+        // Safety: types are globally rooted.
+        let ty = unsafe { Module::typed_global_cached::<Value, _, _>(target, path)? };
+        match base_type(ty) {
+            Some(ty) => self.reflect_type(ty),
+            None => Err(JlrsError::other(ReflectError::NotAType {
+                name: path.into(),
+            }))?,
+        }
+    }
+
+    /// Generates the layout of `ty` and of all types that are stored inline in its fields.
+    ///
+    /// If `ty` has type parameters, the layout of its base type is generated.
+    pub fn reflect_type(&mut self, ty: DataType) -> JlrsResult<&mut Self> {
+        let ty = base_type(ty.as_value()).unwrap_or(ty);
+        // Safety: the type is rooted while this method is called.
+        let unrooted = unsafe { Unrooted::new() };
+        self.reflect_base(unrooted, ty)?;
+        Ok(self)
+    }
+
+    /// Returns all generated layouts, sorted by the path of their Julia type.
+    pub fn layouts(&self) -> impl Iterator<Item = &ReflectedLayout> {
+        self.layouts.values()
+    }
+
+    /// Writes the generated code to `path`.
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    fn reflect_base<'scope>(
+        &mut self,
+        unrooted: Unrooted<'scope>,
+        ty: DataType<'scope>,
+    ) -> JlrsResult<String> {
+        let julia_type = julia_type_path(ty);
+        if self.layouts.contains_key(&julia_type) {
+            return Ok(julia_type);
+        }
+
+        if ty.is_abstract()
+            || ty.is_primitive_type()
+            || ty.type_name().as_value() == TypeName::of_tuple(&unrooted)
+            || ty.type_name().as_value() == TypeName::of_namedtuple(&unrooted)
+        {
+            Err(JlrsError::other(ReflectError::NotAStruct {
+                ty: julia_type.clone(),
+            }))?
+        }
+
+        let mut params = Vec::with_capacity(ty.n_parameters());
+        for i in 0..ty.n_parameters() {
+            // Safety: the index is in bounds.
+            let param = unsafe { ty.parameter_unchecked(i) };
+            match param.cast::<TypeVar>() {
+                Ok(tvar) => params.push(tvar),
+                Err(_) => Err(JlrsError::other(ReflectError::NotAStruct {
+                    ty: julia_type.clone(),
+                }))?,
+            }
+        }
+
+        let mut builder = LayoutBuilder {
+            reflector: self,
+            unrooted,
+            ty,
+            julia_type: &julia_type,
+            layout_params: vec![false; params.len()],
+            params,
+            scope_lifetime: false,
+            data_lifetime: false,
+        };
+
+        let mut fields = String::new();
+        let mut has_bits_union = false;
+        let n_fields = ty.field_names().len();
+        for i in 0..n_fields {
+            let name = match ty.field_name_str(i) {
+                Some(name) if is_valid_ident(name) => name,
+                _ => Err(builder.unsupported(i, "the field name is not a valid identifier"))?,
+            };
+            let name = raw_ident(name);
+
+            if is_atomic_field(ty, i) {
+                Err(builder.unsupported(i, "atomic fields are not supported"))?
+            }
+
+            // Safety: the index is in bounds.
+            let field_ty = unsafe { ty.field_type_unchecked(i) };
+            if let Some((size, align)) = builder.bits_union(i, field_ty)? {
+                has_bits_union = true;
+                let _ = writeln!(fields, "    #[jlrs(bits_union_align)]");
+                let _ = writeln!(
+                    fields,
+                    "    _{}_align: ::jlrs::data::layout::union::Align{},",
+                    name.trim_start_matches("r#"),
+                    align
+                );
+                let _ = writeln!(fields, "    #[jlrs(bits_union)]");
+                let _ = writeln!(
+                    fields,
+                    "    pub {}: ::jlrs::data::layout::union::BitsUnion<{}>,",
+                    name, size
+                );
+                let _ = writeln!(fields, "    #[jlrs(bits_union_flag)]");
+                let _ = writeln!(
+                    fields,
+                    "    pub {}_flag: u8,",
+                    name.trim_start_matches("r#")
+                );
+                continue;
+            }
+
+            let rust_ty = match ty.is_pointer_field(i) {
+                Some(true) => builder.pointer_type(field_ty),
+                Some(false) => builder.inline_type(field_ty, i)?,
+                None => builder.member_type(field_ty, i)?,
+            };
+            let _ = writeln!(fields, "    pub {}: {},", name, rust_ty);
+        }
+
+        let LayoutBuilder {
+            params,
+            layout_params,
+            scope_lifetime,
+            data_lifetime,
+            ..
+        } = builder;
+
+        let param_names: Vec<String> = params
+            .iter()
+            .map(|p| p.name().as_string().unwrap_or_default())
+            .collect();
+        let layout_param_names: Vec<&str> = param_names
+            .iter()
+            .zip(&layout_params)
+            .filter_map(|(name, is_layout)| is_layout.then_some(name.as_str()))
+            .collect();
+        let elided_param_names: Vec<&str> = param_names
+            .iter()
+            .zip(&layout_params)
+            .filter_map(|(name, is_layout)| (!is_layout).then_some(name.as_str()))
+            .collect();
+
+        let rust_name = ty.name().to_string();
+        if let Some(other) = self.layouts.values().find(|l| l.rust_name == rust_name) {
+            Err(JlrsError::other(ReflectError::NameCollision {
+                name: rust_name.clone(),
+                first: other.julia_type.clone(),
+                second: julia_type.clone(),
+            }))?
+        }
+
+        let mutable = ty.mutable();
+        let has_lifetimes = scope_lifetime || data_lifetime;
+        let has_elided_params = !elided_param_names.is_empty();
+
+        let mut derives = vec!["Clone", "Debug", "Unbox", "ValidLayout", "Typecheck"];
+        if !mutable && !has_lifetimes && params.is_empty() && !has_bits_union {
+            derives.push("IntoJulia");
+        }
+        if !mutable {
+            derives.push("ValidField");
+        }
+        if !mutable && !has_lifetimes && !has_bits_union {
+            derives.push("IsBits");
+        }
+        if !has_elided_params {
+            derives.push("ConstructType");
+        }
+        if !mutable && !has_elided_params && n_fields > 0 {
+            derives.push("CCallArg");
+            derives.push("CCallReturn");
+        }
+
+        let mut generics = vec![];
+        if scope_lifetime {
+            generics.push("'scope".to_string());
+        }
+        if data_lifetime {
+            generics.push("'data".to_string());
+        }
+        generics.extend(layout_param_names.iter().map(|s| s.to_string()));
+
+        let zst = !mutable && n_fields == 0 && params.is_empty();
+
+        let mut source = String::new();
+        let _ = writeln!(source, "#[repr(C)]");
+        let _ = writeln!(source, "#[derive({})]", derives.join(", "));
+        if zst {
+            let _ = writeln!(
+                source,
+                "#[jlrs(julia_type = \"{}\", zero_sized_type)]",
+                julia_type
+            );
+        } else {
+            let _ = writeln!(source, "#[jlrs(julia_type = \"{}\")]", julia_type);
+        }
+        let _ = writeln!(
+            source,
+            "pub struct {}{} {{",
+            rust_name,
+            angle_bracketed(&generics)
+        );
+        source.push_str(&fields);
+        source.push_str("}\n");
+
+        if has_elided_params {
+            let quoted = |names: &[&str]| {
+                names
+                    .iter()
+                    .map(|n| format!("\"{}\"", n))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let all_param_names: Vec<&str> = param_names.iter().map(String::as_str).collect();
+
+            let _ = writeln!(source);
+            let _ = writeln!(source, "#[derive(ConstructType, HasLayout)]");
+            let _ = writeln!(
+                source,
+                "#[jlrs(julia_type = \"{}\", constructor_for = \"{}\", scope_lifetime = {}, data_lifetime = {}, layout_params = [{}], elided_params = [{}], all_params = [{}])]",
+                julia_type,
+                rust_name,
+                scope_lifetime,
+                data_lifetime,
+                quoted(&layout_param_names),
+                quoted(&elided_param_names),
+                quoted(&all_param_names),
+            );
+            let _ = writeln!(
+                source,
+                "pub struct {}TypeConstructor{} {{",
+                rust_name,
+                angle_bracketed(&param_names)
+            );
+            for name in &param_names {
+                let _ = writeln!(
+                    source,
+                    "    _{}: ::std::marker::PhantomData<{}>,",
+                    name.to_lowercase(),
+                    name
+                );
+            }
+            source.push_str("}\n");
+        }
+
+        let layout_params = layout_params
+            .iter()
+            .enumerate()
+            .filter_map(|(i, is_layout)| is_layout.then_some(i))
+            .collect();
+
+        self.layouts.insert(
+            julia_type.clone(),
+            ReflectedLayout {
+                julia_type: julia_type.clone(),
+                rust_name,
+                source,
+                scope_lifetime,
+                data_lifetime,
+                layout_params,
+            },
+        );
+
+        Ok(julia_type)
+    }
+}
+
+impl Display for Reflector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "use jlrs::prelude::*;")?;
+        for layout in self.layouts.values() {
+            writeln!(f)?;
+            write!(f, "{}", layout.source)?;
+        }
+
+        Ok(())
+    }
+}
+
+struct LayoutBuilder<'r, 'scope> {
+    reflector: &'r mut Reflector,
+    unrooted: Unrooted<'scope>,
+    ty: DataType<'scope>,
+    julia_type: &'r str,
+    params: Vec<TypeVar<'scope>>,
+    layout_params: Vec<bool>,
+    scope_lifetime: bool,
+    data_lifetime: bool,
+}
+
+impl<'r, 'scope> LayoutBuilder<'r, 'scope> {
+    fn unsupported(&self, field: usize, reason: &'static str) -> JlrsError {
+        let field = self
+            .ty
+            .field_name_str(field)
+            .map(String::from)
+            .unwrap_or_else(|| field.to_string());
+
+        JlrsError::other(ReflectError::UnsupportedField {
+            ty: self.julia_type.into(),
+            field,
+            reason,
+        })
+    }
+
+    // Returns the size and alignment of the field if it's a bits union.
+    fn bits_union(&self, field: usize, ty: Value) -> JlrsResult<Option<(usize, usize)>> {
+        let Ok(u) = ty.cast::<Union>() else {
+            return Ok(None);
+        };
+
+        if u.variants().into_iter().any(has_free_type_vars) {
+            Err(self.unsupported(field, "the union depends on a type parameter"))?
+        }
+
+        if self.ty.is_pointer_field(field) == Some(true) {
+            return Ok(None);
+        }
+
+        let mut size = 0;
+        let mut align = 0;
+        if !u.isbits_size_align(&mut size, &mut align) {
+            return Ok(None);
+        }
+
+        Ok(Some((size, align)))
+    }
+
+    // A field or parameter of a generic type, it's stored inline if the type is stored inline.
+    fn member_type(&mut self, ty: Value, field: usize) -> JlrsResult<String> {
+        if let Ok(tvar) = ty.cast::<TypeVar>() {
+            return self.type_param(tvar, field);
+        }
+
+        if let Ok(dt) = ty.cast::<DataType>() {
+            let inline = if dt.has_free_type_vars() {
+                !dt.mutable() && !dt.is_abstract() && may_inline_alloc(dt)
+            } else {
+                dt.is_inline_alloc()
+            };
+
+            if inline {
+                return self.inline_type(ty, field);
+            }
+        }
+
+        Ok(self.pointer_type(ty))
+    }
+
+    fn type_param(&mut self, tvar: TypeVar, field: usize) -> JlrsResult<String> {
+        let Some(idx) = self.params.iter().position(|p| p.as_value() == tvar) else {
+            Err(self.unsupported(field, "the type depends on an unknown type parameter"))?
+        };
+
+        self.layout_params[idx] = true;
+        Ok(tvar.name().as_string().unwrap_or_default())
+    }
+
+    fn inline_type(&mut self, ty: Value, field: usize) -> JlrsResult<String> {
+        if let Ok(tvar) = ty.cast::<TypeVar>() {
+            return self.type_param(tvar, field);
+        }
+
+        let Ok(dt) = ty.cast::<DataType>() else {
+            Err(self.unsupported(field, "the field type is not a DataType"))?
+        };
+
+        if let Some(primitive) = primitive_type(self.unrooted, dt) {
+            return Ok(primitive.into());
+        }
+
+        if dt.is_primitive_type() {
+            Err(self.unsupported(field, "custom primitive types are not supported"))?
+        }
+
+        if dt.type_name().as_value() == TypeName::of_tuple(&self.unrooted) {
+            let n = dt.n_parameters();
+            let mut elems = Vec::with_capacity(n);
+            for i in 0..n {
+                // Safety: the index is in bounds.
+                let elem = unsafe { dt.parameter_unchecked(i) };
+                elems.push(self.member_type(elem, field)?);
+            }
+
+            return Ok(format!(
+                "::jlrs::data::layout::tuple::Tuple{}{}",
+                n,
+                angle_bracketed(&elems)
+            ));
+        }
+
+        if dt == DataType::nothing_type(&self.unrooted) {
+            return Ok("::jlrs::data::layout::nothing::Nothing".into());
+        }
+
+        let base = base_type(dt.as_value()).unwrap_or(dt);
+        let key = self.reflector.reflect_base(self.unrooted, base)?;
+        let layout = &self.reflector.layouts[&key];
+        let rust_name = layout.rust_name.clone();
+        let scope_lifetime = layout.scope_lifetime;
+        let data_lifetime = layout.data_lifetime;
+        let layout_params = layout.layout_params.clone();
+
+        let mut generics = vec![];
+        if scope_lifetime {
+            self.scope_lifetime = true;
+            generics.push("'scope".to_string());
+        }
+        if data_lifetime {
+            self.data_lifetime = true;
+            generics.push("'data".to_string());
+        }
+        for idx in layout_params {
+            // Safety: the layout parameters of the base type are in bounds.
+            let param = unsafe { dt.parameter_unchecked(idx) };
+            generics.push(self.member_type(param, field)?);
+        }
+
+        Ok(format!("{}{}", rust_name, angle_bracketed(&generics)))
+    }
+
+    fn pointer_type(&mut self, ty: Value) -> String {
+        let unrooted = self.unrooted;
+        self.scope_lifetime = true;
+
+        let managed = if let Ok(dt) = ty.cast::<DataType>() {
+            if dt == DataType::module_type(&unrooted) {
+                Some("::jlrs::data::managed::module::ModuleRef<'scope>")
+            } else if dt == DataType::string_type(&unrooted) {
+                Some("::jlrs::data::managed::string::StringRef<'scope>")
+            } else if dt == DataType::symbol_type(&unrooted) {
+                Some("::jlrs::data::managed::symbol::SymbolRef<'scope>")
+            } else if dt == DataType::datatype_type(&unrooted) {
+                Some("::jlrs::data::managed::datatype::DataTypeRef<'scope>")
+            } else if dt == DataType::simplevector_type(&unrooted) {
+                Some("::jlrs::data::managed::simple_vector::SimpleVectorRef<'scope>")
+            } else if dt.type_name().as_value() == TypeName::of_array(&unrooted) {
+                self.data_lifetime = true;
+                Some("::jlrs::data::managed::array::ArrayRef<'scope, 'data>")
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        let managed = managed.unwrap_or_else(|| {
+            self.data_lifetime = true;
+            "::jlrs::data::managed::value::ValueRef<'scope, 'data>"
+        });
+
+        format!("::std::option::Option<{}>", managed)
+    }
+}
+
+fn primitive_type(unrooted: Unrooted, ty: DataType) -> Option<&'static str> {
+    let primitives = [
+        (
+            DataType::bool_type(&unrooted),
+            "::jlrs::data::layout::bool::Bool",
+        ),
+        (
+            DataType::char_type(&unrooted),
+            "::jlrs::data::layout::char::Char",
+        ),
+        (DataType::int8_type(&unrooted), "i8"),
+        (DataType::int16_type(&unrooted), "i16"),
+        (DataType::int32_type(&unrooted), "i32"),
+        (DataType::int64_type(&unrooted), "i64"),
+        (DataType::uint8_type(&unrooted), "u8"),
+        (DataType::uint16_type(&unrooted), "u16"),
+        (DataType::uint32_type(&unrooted), "u32"),
+        (DataType::uint64_type(&unrooted), "u64"),
+        (DataType::float16_type(&unrooted), "::half::f16"),
+        (DataType::float32_type(&unrooted), "f32"),
+        (DataType::float64_type(&unrooted), "f64"),
+    ];
+
+    primitives
+        .into_iter()
+        .find_map(|(dt, name)| (dt == ty).then_some(name))
+}
+
+fn base_type<'scope>(ty: Value<'scope, 'static>) -> Option<DataType<'scope>> {
+    let ty = match ty.cast::<DataType>() {
+        Ok(dt) => dt.type_name().wrapper(),
+        Err(_) => ty,
+    };
+
+    match ty.cast::<UnionAll>() {
+        Ok(ua) => Some(ua.base_type()),
+        Err(_) => ty.cast::<DataType>().ok(),
+    }
+}
+
+fn has_free_type_vars(ty: Value) -> bool {
+    if ty.is::<TypeVar>() {
+        return true;
+    }
+
+    match ty.cast::<DataType>() {
+        Ok(dt) => dt.has_free_type_vars(),
+        Err(_) => false,
+    }
+}
+
+#[julia_version(since = "1.7")]
+fn may_inline_alloc(ty: DataType) -> bool {
+    ty.type_name().mayinlinealloc()
+}
+
+// Julia 1.6 doesn't track whether instances of a generic type may be stored inline, immutable
+// concrete types are stored inline so this is assumed to be true for every immutable type.
+#[julia_version(until = "1.6")]
+fn may_inline_alloc(ty: DataType) -> bool {
+    !ty.mutable()
+}
+
+// Julia 1.6 doesn't support atomic fields.
+#[julia_version(until = "1.6")]
+fn is_atomic_field(_ty: DataType, _idx: usize) -> bool {
+    false
+}
+
+#[julia_version(since = "1.7")]
+fn is_atomic_field(ty: DataType, idx: usize) -> bool {
+    let atomic_fields = ty.type_name().atomicfields();
+    if atomic_fields.is_null() {
+        return false;
+    }
+
+    // Safety: the bitset contains a bit for each field.
+    unsafe { *atomic_fields.add(idx / 32) & (1 << (idx % 32)) != 0 }
+}
+
+fn julia_type_path(ty: DataType) -> String {
+    let mut path = ty.name().to_string();
+    let mut module = ty.type_name().module();
+
+    loop {
+        let name = module
+            .name()
+            .as_string()
+            .unwrap_or_else(|_| CANNOT_DISPLAY_VALUE.into());
+        path = format!("{}.{}", name, path);
+
+        let parent = module.parent();
+        if parent.as_value() == module {
+            break;
+        }
+        module = parent;
+    }
+
+    path
+}
+
+fn angle_bracketed<S: AsRef<str>>(items: &[S]) -> String {
+    if items.is_empty() {
+        return String::new();
+    }
+
+    let items: Vec<&str> = items.iter().map(AsRef::as_ref).collect();
+    format!("<{}>", items.join(", "))
+}
+
+fn is_valid_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c == '_' || c.is_ascii_alphabetic() => {}
+        _ => return false,
+    }
+
+    !matches!(name, "_" | "self" | "Self" | "super" | "crate")
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+fn raw_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let",
+        "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+        "virtual", "where", "while", "yield",
+    ];
+
+    if KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.into()
+    }
+}
@@ -0,0 +1,463 @@
+//! Verify layouts against the Julia types they represent.
+//!
+//! Whether a layout is valid for some Julia type is normally only checked at runtime, e.g. when
+//! data is unboxed or tracked. If a Julia package changes the definition of a type, e.g. by
+//! reordering its fields, this is only discovered when the layout is used.
+//!
+//! A [`LayoutVerifier`] checks a set of layouts at once. For each layout it compares the size,
+//! alignment, and the position, offset, size and type of every field against those of the Julia
+//! type, and collects every mismatch in a [`VerificationReport`]. Fields are matched by name, so
+//! a field that has been moved is reported once under its own name. The `ValidLayout` derive
+//! macro implements [`DescribedLayout`] for all structs without type parameters, so layouts
+//! generated with JlrsReflect can be verified in an integration test:
+//!
+//! ```ignore
+//! use jlrs::{data::layout::verify::LayoutVerifier, prelude::*};
+//!
+//! #[test]
+//! fn layouts_match_julia_types() {
+//!     let mut julia = unsafe { Builder::new().start().unwrap() };
+//!     let mut frame = StackFrame::new();
+//!     let mut julia = julia.instance(&mut frame);
+//!
+//!     julia.local_scope::<_, 0>(|frame| {
+//!         // Load the package that defines the types before verifying them.
+//!         unsafe { Value::eval_string(&frame, "using MyPackage").unwrap() };
+//!
+//!         LayoutVerifier::new()
+//!             .layout::<MyType>()
+//!             .layout::<MyOtherType>()
+//!             .verify(&frame)
+//!             .assert_ok();
+//!     });
+//! }
+//! ```
+//!
+//! Layouts annotated with `#[jlrs(verify)]` are registered by the derive macro,
+//! [`LayoutVerifier::registered_layouts`] adds all registered layouts defined in the crates that
+//! are linked into the test binary instead of listing them manually. Registration requires the
+//! `jlrs-derive` feature.
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use super::valid_layout::ValidLayout;
+use crate::{
+    data::managed::{
+        datatype::DataType, module::Module, union_all::UnionAll, value::Value, Managed as _,
+    },
+    error::{CANNOT_DISPLAY_TYPE, CANNOT_DISPLAY_VALUE},
+    memory::target::{unrooted::Unrooted, Target},
+};
+
+/// The layout of a field of a [`DescribedLayout`].
+#[derive(Clone, Copy, Debug)]
+pub struct FieldLayout {
+    /// The name of the field.
+    pub name: &'static str,
+    /// The name of the Rust type of the field.
+    pub rust_type: &'static str,
+    /// The offset of the field.
+    pub offset: usize,
+    /// The size of the field, or `None` if it's a bits union.
+    pub size: Option<usize>,
+    /// Returns `true` if the field is a valid representation of a field of type `ty`.
+    pub valid_field: fn(ty: Value) -> bool,
+}
+
+/// A layout that describes its fields.
+///
+/// This trait is implemented by the `ValidLayout` derive macro for structs without type
+/// parameters.
+///
+/// Safety:
+///
+/// `JULIA_TYPE` must be the path of the Julia type this layout represents, the fields must be
+/// listed in the same order as the fields of the Julia type and their offsets and sizes must be
+/// correct.
+pub unsafe trait DescribedLayout: ValidLayout {
+    /// The path of the Julia type, e.g. `"Main.MyModule.MyType"`.
+    const JULIA_TYPE: &'static str;
+
+    /// The layouts of the fields of this type.
+    fn field_layouts() -> Vec<FieldLayout>;
+}
+
+/// A mismatch between a layout and the Julia type it represents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The Julia type couldn't be found.
+    TypeNotFound { reason: String },
+    /// The Julia type is not a concrete `DataType`.
+    NotConcrete { julia_type: String },
+    /// The sizes of the layout and the Julia type differ.
+    Size { rust: usize, julia: usize },
+    /// The alignments of the layout and the Julia type differ.
+    Align { rust: usize, julia: usize },
+    /// The number of fields of the layout and the Julia type differ.
+    FieldCount { rust: usize, julia: usize },
+    /// The Julia type has no field with this name.
+    UnknownField { field: &'static str },
+    /// The layout has no field with this name.
+    MissingField { field: String },
+    /// The positions of a field differ.
+    FieldIndex {
+        field: &'static str,
+        rust: usize,
+        julia: usize,
+    },
+    /// The offsets of a field differ.
+    FieldOffset {
+        field: &'static str,
+        rust: usize,
+        julia: usize,
+    },
+    /// The sizes of a field differ.
+    FieldSize {
+        field: &'static str,
+        rust: usize,
+        julia: usize,
+    },
+    /// The type of a field isn't represented by the type of the Rust field.
+    FieldType {
+        field: &'static str,
+        rust: &'static str,
+        julia: String,
+    },
+}
+
+/// All mismatches found for a single layout.
+#[derive(Clone, Debug)]
+pub struct LayoutReport {
+    /// The name of the Rust type.
+    pub rust_type: &'static str,
+    /// The path of the Julia type.
+    pub julia_type: &'static str,
+    /// The mismatches that have been found.
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl LayoutReport {
+    /// Returns `true` if no mismatches have been found.
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl Display for LayoutReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "--- julia: {}", self.julia_type)?;
+        writeln!(f, "+++ rust:  {}", self.rust_type)?;
+
+        for mismatch in self.mismatches.iter() {
+            match mismatch {
+                Mismatch::TypeNotFound { reason } => {
+                    writeln!(f, "  type not found: {}", reason)?;
+                }
+                Mismatch::NotConcrete { julia_type } => {
+                    writeln!(f, "  {} is not a concrete DataType", julia_type)?;
+                }
+                Mismatch::Size { rust, julia } => {
+                    writeln!(f, "- size: {}", julia)?;
+                    writeln!(f, "+ size: {}", rust)?;
+                }
+                Mismatch::Align { rust, julia } => {
+                    writeln!(f, "- align: {}", julia)?;
+                    writeln!(f, "+ align: {}", rust)?;
+                }
+                Mismatch::FieldCount { rust, julia } => {
+                    writeln!(f, "- fields: {}", julia)?;
+                    writeln!(f, "+ fields: {}", rust)?;
+                }
+                Mismatch::UnknownField { field } => {
+                    writeln!(f, "+ {}", field)?;
+                }
+                Mismatch::MissingField { field } => {
+                    writeln!(f, "- {}", field)?;
+                }
+                Mismatch::FieldIndex { field, rust, julia } => {
+                    writeln!(f, "- {}: field {}", field, julia)?;
+                    writeln!(f, "+ {}: field {}", field, rust)?;
+                }
+                Mismatch::FieldOffset { field, rust, julia } => {
+                    writeln!(f, "- {}: offset {}", field, julia)?;
+                    writeln!(f, "+ {}: offset {}", field, rust)?;
+                }
+                Mismatch::FieldSize { field, rust, julia } => {
+                    writeln!(f, "- {}: size {}", field, julia)?;
+                    writeln!(f, "+ {}: size {}", field, rust)?;
+                }
+                Mismatch::FieldType { field, rust, julia } => {
+                    writeln!(f, "- {}::{}", field, julia)?;
+                    writeln!(f, "+ {}: {}", field, rust)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The result of verifying a set of layouts.
+#[derive(Clone, Debug)]
+pub struct VerificationReport {
+    layouts: Vec<LayoutReport>,
+}
+
+impl VerificationReport {
+    /// Returns `true` if no mismatches have been found.
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.layouts.iter().all(LayoutReport::is_ok)
+    }
+
+    /// Returns the reports of all verified layouts.
+    #[inline]
+    pub fn layouts(&self) -> &[LayoutReport] {
+        &self.layouts
+    }
+
+    /// Returns the reports of the layouts with at least one mismatch.
+    pub fn failures(&self) -> impl Iterator<Item = &LayoutReport> {
+        self.layouts.iter().filter(|l| !l.is_ok())
+    }
+
+    /// Panics if any mismatches have been found, the panic message lists all of them.
+    #[track_caller]
+    pub fn assert_ok(&self) {
+        if !self.is_ok() {
+            panic!("{}", self)
+        }
+    }
+}
+
+impl Display for VerificationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let n_failed = self.failures().count();
+        writeln!(
+            f,
+            "{} of {} layouts don't match their Julia type",
+            n_failed,
+            self.layouts.len()
+        )?;
+
+        for failure in self.failures() {
+            writeln!(f)?;
+            write!(f, "{}", failure)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Verifies a set of layouts.
+///
+/// See the [module-level docs] for more information.
+///
+/// [module-level docs]: self
+#[derive(Default)]
+pub struct LayoutVerifier {
+    verifiers: Vec<fn(Unrooted) -> LayoutReport>,
+}
+
+impl LayoutVerifier {
+    /// Creates a new verifier without any layouts.
+    #[inline]
+    pub fn new() -> Self {
+        LayoutVerifier::default()
+    }
+
+    /// Adds the layout `L`.
+    #[inline]
+    pub fn layout<L: DescribedLayout>(mut self) -> Self {
+        self.verifiers.push(verify_layout::<L>);
+        self
+    }
+
+    /// Adds all layouts that have been registered by the `ValidLayout` derive macro.
+    ///
+    /// Only layouts annotated with `#[jlrs(verify)]` that are defined in crates that are linked
+    /// into the current binary are registered.
+    #[cfg(feature = "jlrs-derive")]
+    pub fn registered_layouts(mut self) -> Self {
+        self.verifiers.extend(
+            inventory::iter::<RegisteredLayout>
+                .into_iter()
+                .map(|l| l.verify),
+        );
+        self
+    }
+
+    /// Verifies all layouts.
+    ///
+    /// The Julia types are looked up by their path, the modules that define them must have been
+    /// loaded before calling this method.
+    pub fn verify<'target, Tgt>(&self, target: &Tgt) -> VerificationReport
+    where
+        Tgt: Target<'target>,
+    {
+        let unrooted = target.unrooted();
+        VerificationReport {
+            layouts: self
+                .verifiers
+                .iter()
+                .map(|verify| verify(unrooted))
+                .collect(),
+        }
+    }
+}
+
+/// A layout registered by the `ValidLayout` derive macro.
+#[cfg(feature = "jlrs-derive")]
+#[doc(hidden)]
+pub struct RegisteredLayout {
+    verify: fn(Unrooted) -> LayoutReport,
+}
+
+#[cfg(feature = "jlrs-derive")]
+impl RegisteredLayout {
+    #[inline]
+    pub const fn new<L: DescribedLayout>() -> Self {
+        RegisteredLayout {
+            verify: verify_layout::<L>,
+        }
+    }
+}
+
+#[cfg(feature = "jlrs-derive")]
+inventory::collect!(RegisteredLayout);
+
+#[cfg(feature = "jlrs-derive")]
+#[doc(hidden)]
+pub use inventory;
+
+fn verify_layout<L: DescribedLayout>(unrooted: Unrooted) -> LayoutReport {
+    LayoutReport {
+        rust_type: std::any::type_name::<L>(),
+        julia_type: L::JULIA_TYPE,
+        mismatches: find_mismatches::<L>(unrooted),
+    }
+}
+
+fn find_mismatches<L: DescribedLayout>(unrooted: Unrooted) -> Vec<Mismatch> {
+    let mut mismatches = vec![];
+
+    // Safety: types are globally rooted.
+    let ty = match unsafe { Module::typed_global_cached::<Value, _, _>(&unrooted, L::JULIA_TYPE) } {
+        Ok(ty) => ty,
+        Err(e) => {
+            mismatches.push(Mismatch::TypeNotFound {
+                reason: e.to_string(),
+            });
+            return mismatches;
+        }
+    };
+
+    if ty.is::<UnionAll>() || !ty.is::<DataType>() {
+        mismatches.push(Mismatch::NotConcrete {
+            julia_type: ty.display_string_or(CANNOT_DISPLAY_VALUE),
+        });
+        return mismatches;
+    }
+
+    // Safety: ty is a DataType
+    let ty = unsafe { ty.cast_unchecked::<DataType>() };
+    if !ty.is_concrete_type() || !ty.has_layout() {
+        mismatches.push(Mismatch::NotConcrete {
+            julia_type: ty.display_string_or(CANNOT_DISPLAY_TYPE),
+        });
+        return mismatches;
+    }
+
+    let rust_size = std::mem::size_of::<L>();
+    let julia_size = ty.size().unwrap_or_default() as usize;
+    if rust_size != julia_size {
+        mismatches.push(Mismatch::Size {
+            rust: rust_size,
+            julia: julia_size,
+        });
+    }
+
+    let rust_align = std::mem::align_of::<L>();
+    let julia_align = ty.align().unwrap_or_default() as usize;
+    if rust_align != julia_align {
+        mismatches.push(Mismatch::Align {
+            rust: rust_align,
+            julia: julia_align,
+        });
+    }
+
+    let fields = L::field_layouts();
+    let n_fields = ty.n_fields().unwrap_or_default() as usize;
+    if fields.len() != n_fields {
+        mismatches.push(Mismatch::FieldCount {
+            rust: fields.len(),
+            julia: n_fields,
+        });
+    }
+
+    let julia_names = (0..n_fields)
+        .map(|idx| ty.field_name_str(idx).unwrap_or_default())
+        .collect::<Vec<_>>();
+
+    for julia_name in julia_names.iter() {
+        if !fields
+            .iter()
+            .any(|field| field.name.trim_start_matches("r#") == *julia_name)
+        {
+            mismatches.push(Mismatch::MissingField {
+                field: (*julia_name).into(),
+            });
+        }
+    }
+
+    for (rust_idx, field) in fields.iter().enumerate() {
+        let rust_name = field.name.trim_start_matches("r#");
+        let Some(idx) = julia_names.iter().position(|name| *name == rust_name) else {
+            mismatches.push(Mismatch::UnknownField { field: field.name });
+            continue;
+        };
+
+        if rust_idx != idx {
+            mismatches.push(Mismatch::FieldIndex {
+                field: field.name,
+                rust: rust_idx,
+                julia: idx,
+            });
+        }
+
+        // Safety: idx is in bounds
+        let julia_offset = unsafe { ty.field_offset_unchecked(idx) } as usize;
+        if field.offset != julia_offset {
+            mismatches.push(Mismatch::FieldOffset {
+                field: field.name,
+                rust: field.offset,
+                julia: julia_offset,
+            });
+        }
+
+        if let Some(rust_size) = field.size {
+            // Safety: idx is in bounds
+            let julia_size = unsafe { ty.field_size_unchecked(idx) } as usize;
+            if rust_size != julia_size {
+                mismatches.push(Mismatch::FieldSize {
+                    field: field.name,
+                    rust: rust_size,
+                    julia: julia_size,
+                });
+            }
+        }
+
+        // Safety: idx is in bounds
+        let field_type = unsafe { ty.field_type_unchecked(idx) };
+        if !(field.valid_field)(field_type) {
+            mismatches.push(Mismatch::FieldType {
+                field: field.name,
+                rust: field.rust_type,
+                julia: field_type.display_string_or(CANNOT_DISPLAY_TYPE),
+            });
+        }
+    }
+
+    mismatches
+}
@@ -0,0 +1,122 @@
+//! Managed type for `CodeInstance`.
+//!
+//! A `CodeInstance` contains the inferred and compiled code of a [`MethodInstance`] for a range
+//! of world ages. The layout of `CodeInstance` is opaque to jlrs, its fields are accessed by
+//! name.
+
+use std::{marker::PhantomData, ptr::NonNull};
+
+use jl_sys::{jl_code_instance_t, jl_code_instance_type};
+
+use super::{
+    method_instance::{MethodInstance, MethodInstanceRef},
+    value::{Value, ValueRef},
+    Managed, Ref,
+};
+use crate::{
+    data::managed::private::ManagedPriv,
+    impl_julia_typecheck,
+    memory::target::{TargetResult, TargetType},
+    private::Private,
+};
+
+/// Inferred and compiled code of a method instance.
+#[derive(Copy, Clone, PartialEq)]
+#[repr(transparent)]
+pub struct CodeInstance<'scope>(NonNull<jl_code_instance_t>, PhantomData<&'scope ()>);
+
+impl<'scope> CodeInstance<'scope> {
+    /// The `def` field, the method instance this code has been generated for.
+    pub fn method_instance(self) -> Option<MethodInstanceRef<'scope>> {
+        // Safety: the field is only read.
+        unsafe {
+            let def = self.pointer_field("def")?.as_value();
+            let mi = def.cast::<MethodInstance>().ok()?;
+            Some(MethodInstance::wrap_non_null(mi.unwrap_non_null(Private), Private).as_ref())
+        }
+    }
+
+    /// The `rettype` field, the inferred return type.
+    pub fn return_type(self) -> Option<Value<'scope, 'static>> {
+        // Safety: the field is only read.
+        unsafe { Some(self.pointer_field("rettype")?.as_value()) }
+    }
+
+    /// The `min_world` field, the first world age this code is valid for.
+    pub fn min_world(self) -> Option<usize> {
+        self.as_value()
+            .field_accessor()
+            .field("min_world")
+            .ok()?
+            .access::<usize>()
+            .ok()
+    }
+
+    /// The `max_world` field, the last world age this code is valid for.
+    pub fn max_world(self) -> Option<usize> {
+        self.as_value()
+            .field_accessor()
+            .field("max_world")
+            .ok()?
+            .access::<usize>()
+            .ok()
+    }
+
+    /// The `next` field, the next code instance of the same method instance.
+    pub fn next(self) -> Option<CodeInstanceRef<'scope>> {
+        // Safety: the field is only read.
+        unsafe {
+            let next = self.pointer_field("next")?.as_value();
+            let ci = next.cast::<CodeInstance>().ok()?;
+            Some(CodeInstance::wrap_non_null(ci.unwrap_non_null(Private), Private).as_ref())
+        }
+    }
+
+    fn pointer_field(self, name: &str) -> Option<ValueRef<'scope, 'static>> {
+        self.as_value().get_field_ref(name).ok().flatten()
+    }
+}
+
+impl_julia_typecheck!(CodeInstance<'scope>, jl_code_instance_type, 'scope);
+impl_debug!(CodeInstance<'_>);
+
+impl<'scope> ManagedPriv<'scope, '_> for CodeInstance<'scope> {
+    type Wraps = jl_code_instance_t;
+    type WithLifetimes<'target, 'da> = CodeInstance<'target>;
+    const NAME: &'static str = "CodeInstance";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it.
+    #[inline]
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self(inner, PhantomData)
+    }
+
+    #[inline]
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.0
+    }
+}
+
+impl_construct_type_managed!(CodeInstance, 1, jl_code_instance_type);
+
+/// A reference to a [`CodeInstance`] that has not been explicitly rooted.
+pub type CodeInstanceRef<'scope> = Ref<'scope, 'static, CodeInstance<'scope>>;
+
+/// A [`CodeInstanceRef`] with static lifetimes. This is a useful shorthand for signatures of
+/// `ccall`able functions that return a [`CodeInstance`].
+pub type CodeInstanceRet = Ref<'static, 'static, CodeInstance<'static>>;
+
+impl_valid_layout!(CodeInstanceRef, CodeInstance, jl_code_instance_type);
+
+/// `CodeInstance` or `CodeInstanceRef`, depending on the target type `Tgt`.
+pub type CodeInstanceData<'target, Tgt> =
+    <Tgt as TargetType<'target>>::Data<'static, CodeInstance<'target>>;
+
+/// `JuliaResult<CodeInstance>` or `JuliaResultRef<CodeInstanceRef>`, depending on the target type
+/// `Tgt`.
+pub type CodeInstanceResult<'target, Tgt> =
+    TargetResult<'target, 'static, CodeInstance<'target>, Tgt>;
+
+impl_ccall_arg_managed!(CodeInstance, 1);
+impl_into_typed!(CodeInstance);
@@ -7,10 +7,14 @@ use jl_sys::{
 };
 
 use super::{
-    value::{Value, ValueData},
+    datatype::DataType,
+    module::Module,
+    value::{Value, ValueData, ValueResult},
     Managed,
 };
 use crate::{
+    call::Call,
+    convert::to_symbol::ToSymbol,
     data::managed::{private::ManagedPriv, symbol::Symbol, Ref},
     impl_julia_typecheck,
     memory::target::{TargetResult, TargetType},
@@ -19,10 +23,176 @@ use crate::{
 };
 
 /// A compound expression in Julia ASTs.
+///
+/// New expressions can be built with [`Expr::new`] and helpers like [`Expr::call`] and
+/// [`Expr::block`], and evaluated with [`Value::eval_expr`].
 #[derive(Copy, Clone)]
 #[repr(transparent)]
 pub struct Expr<'scope>(NonNull<jl_expr_t>, PhantomData<&'scope ()>);
 
+impl Expr<'_> {
+    /// Create a new expression with head `head` and arguments `args`, this is equivalent to
+    /// calling `Expr(head, args...)` in Julia.
+    pub fn new<'target, 'value, N, V, Tgt>(
+        target: Tgt,
+        head: N,
+        args: V,
+    ) -> ExprResult<'target, Tgt>
+    where
+        N: ToSymbol,
+        V: AsRef<[Value<'value, 'static>]>,
+        Tgt: Target<'target>,
+    {
+        let head = head.to_symbol(&target);
+        Self::build(target, head, &[], args.as_ref())
+    }
+
+    /// Create a function call, `Expr(:call, func, args...)`.
+    ///
+    /// `func` is typically a `Symbol` with the name of the function, but can be any expression
+    /// that evaluates to a callable value.
+    pub fn call<'target, 'value, V, Tgt>(
+        target: Tgt,
+        func: Value<'_, 'static>,
+        args: V,
+    ) -> ExprResult<'target, Tgt>
+    where
+        V: AsRef<[Value<'value, 'static>]>,
+        Tgt: Target<'target>,
+    {
+        let head = "call".to_symbol(&target);
+        Self::build(target, head, &[func], args.as_ref())
+    }
+
+    /// Create a block of statements, `Expr(:block, statements...)`.
+    pub fn block<'target, 'value, V, Tgt>(target: Tgt, statements: V) -> ExprResult<'target, Tgt>
+    where
+        V: AsRef<[Value<'value, 'static>]>,
+        Tgt: Target<'target>,
+    {
+        let head = "block".to_symbol(&target);
+        Self::build(target, head, &[], statements.as_ref())
+    }
+
+    /// Create a function definition, `Expr(:function, signature, body)`.
+    ///
+    /// The signature is a call expression like `Expr(:call, :foo, :x)`, the body is usually a
+    /// block.
+    pub fn function<'target, Tgt>(
+        target: Tgt,
+        signature: Value<'_, 'static>,
+        body: Value<'_, 'static>,
+    ) -> ExprResult<'target, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        let head = "function".to_symbol(&target);
+        Self::build(target, head, &[signature, body], &[])
+    }
+
+    /// Create a quoted expression, `Expr(:quote, value)`.
+    pub fn quote<'target, Tgt>(target: Tgt, value: Value<'_, 'static>) -> ExprResult<'target, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        let head = "quote".to_symbol(&target);
+        Self::build(target, head, &[value], &[])
+    }
+
+    /// Create an indexing expression, `Expr(:ref, collection, indices...)`.
+    pub fn index<'target, 'value, V, Tgt>(
+        target: Tgt,
+        collection: Value<'_, 'static>,
+        indices: V,
+    ) -> ExprResult<'target, Tgt>
+    where
+        V: AsRef<[Value<'value, 'static>]>,
+        Tgt: Target<'target>,
+    {
+        let head = "ref".to_symbol(&target);
+        Self::build(target, head, &[collection], indices.as_ref())
+    }
+
+    /// Create a macro call, `Expr(:macrocall, name, line, args...)`.
+    ///
+    /// The name of the macro must include the `@`, e.g. `"@time"`. If `line` is `None`,
+    /// `nothing` is used instead of a `LineNumberNode`, see [`Expr::line_number_node`].
+    pub fn macrocall<'target, 'value, N, V, Tgt>(
+        target: Tgt,
+        name: N,
+        line: Option<Value<'_, 'static>>,
+        args: V,
+    ) -> ExprResult<'target, Tgt>
+    where
+        N: ToSymbol,
+        V: AsRef<[Value<'value, 'static>]>,
+        Tgt: Target<'target>,
+    {
+        let head = "macrocall".to_symbol(&target);
+        let name = name.to_symbol(&target).as_value();
+        let line = line.unwrap_or_else(|| Value::nothing(&target));
+        Self::build(target, head, &[name, line], args.as_ref())
+    }
+
+    /// Create a new `LineNumberNode` for line `line` in the file `file`.
+    ///
+    /// Line number nodes can be used as statements to set the location reported in stacktraces
+    /// and by macros.
+    pub fn line_number_node<'target, Tgt>(
+        target: Tgt,
+        line: isize,
+        file: Option<Symbol<'_>>,
+    ) -> ValueResult<'target, 'static, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 1>(|target, mut frame| {
+            let line = Value::new(&mut frame, line);
+            let file = match file {
+                Some(file) => file.as_value(),
+                None => Value::nothing(&frame),
+            };
+
+            // Safety: the LineNumberNode constructor only allocates a new node.
+            unsafe {
+                Module::typed_global_cached::<Value, _, _>(&frame, "Core.LineNumberNode")
+                    .unwrap()
+                    .call2(target, line, file)
+            }
+        })
+    }
+
+    fn build<'target, Tgt>(
+        target: Tgt,
+        head: Symbol,
+        leading: &[Value<'_, 'static>],
+        args: &[Value<'_, 'static>],
+    ) -> ExprResult<'target, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        let mut all_args = Vec::with_capacity(1 + leading.len() + args.len());
+        all_args.push(head.as_value());
+        all_args.extend_from_slice(leading);
+        all_args.extend_from_slice(args);
+
+        // Safety: the Expr constructor only allocates a new expression, the result is rooted
+        // before anything else can be allocated.
+        unsafe {
+            let res = DataType::expr_type(&target)
+                .as_value()
+                .call(&target, all_args.as_slice());
+
+            let res = match res {
+                Ok(expr) => Ok(expr.ptr().cast()),
+                Err(e) => Err(e.ptr()),
+            };
+
+            target.result_from_ptr(res, Private)
+        }
+    }
+}
+
 impl<'scope> Expr<'scope> {
     /// Returns the head of the expression.
     pub fn head(self) -> Option<Symbol<'scope>> {
@@ -6,21 +6,31 @@
 //!
 //! [`Call`]: crate::call::Call
 
-use std::{marker::PhantomData, ptr::NonNull};
+use std::{marker::PhantomData, ptr::NonNull, sync::atomic::Ordering};
 
 use jl_sys::jl_value_t;
 
-use super::{value::ValueResult, Ref};
+use super::{
+    method::{Method, MethodRef},
+    method_instance::MethodInstanceRef,
+    method_table::{MethodTable, MethodTableRef},
+    module::Module,
+    value::ValueResult,
+    Ref,
+};
 use crate::{
     args::Values,
     call::{Call, ProvideKeywords, WithKeywords},
-    convert::ccall_types::{CCallArg, CCallReturn},
+    convert::{
+        ccall_types::{CCallArg, CCallReturn},
+        into_jlrs_result::IntoJlrsResult,
+    },
     data::{
         layout::valid_layout::{ValidField, ValidLayout},
-        managed::{datatype::DataType, private::ManagedPriv, value::Value, Managed},
+        managed::{array::Vector, datatype::DataType, private::ManagedPriv, value::Value, Managed},
         types::{abstract_type::AbstractType, construct_type::ConstructType, typecheck::Typecheck},
     },
-    error::JlrsResult,
+    error::{AccessError, JlrsResult},
     memory::target::{unrooted::Unrooted, Target, TargetResult},
     prelude::ValueData,
     private::Private,
@@ -41,6 +51,192 @@ impl<'scope, 'data> Function<'scope, 'data> {
     pub fn datatype(self) -> DataType<'scope> {
         self.as_value().datatype()
     }
+
+    /// Returns the method table of this function.
+    ///
+    /// Returns `None` if the method table can't be accessed through the `TypeName` of this
+    /// function.
+    pub fn method_table(self) -> Option<MethodTableRef<'scope>> {
+        // Safety: the field is only read, the method table is referenced by the type name.
+        unsafe {
+            let mt = self
+                .datatype()
+                .type_name()
+                .as_value()
+                .get_field_ref("mt")
+                .ok()??
+                .as_value()
+                .cast::<MethodTable>()
+                .ok()?;
+
+            Some(MethodTable::wrap_non_null(mt.unwrap_non_null(Private), Private).as_ref())
+        }
+    }
+
+    /// Returns the methods of this function by calling `Base.methods`.
+    pub fn methods<'target, Tgt>(self, target: &Tgt) -> JlrsResult<Vec<MethodRef<'scope>>>
+    where
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 1>(|target, mut frame| {
+            // Safety: Base.methods only reads the method table.
+            let methods = unsafe {
+                Module::typed_global_cached::<Value, _, _>(&target, "Base.methods")?
+                    .call1(&mut frame, self.as_value())
+                    .into_jlrs_result()?
+            };
+
+            let methods = methods.get_field_ref("ms")?.ok_or(AccessError::UndefRef)?;
+
+            // Safety: the array is referenced by the rooted method list and not mutated, the
+            // methods are referenced by the method table of this function.
+            unsafe {
+                let methods = methods
+                    .as_value()
+                    .cast::<Vector>()?
+                    .try_managed_data::<Method>()?
+                    .as_slice()
+                    .iter()
+                    .filter_map(|m| m.load(Ordering::Relaxed))
+                    .map(|m| Method::wrap_non_null(m.ptr(), Private).as_ref())
+                    .collect();
+
+                Ok(methods)
+            }
+        })
+    }
+
+    /// Returns `true` if this function has a method that can be called with arguments of the
+    /// given types by calling `Base.hasmethod`.
+    ///
+    /// Returns an error if the types can't be used as the parameters of a tuple type.
+    pub fn has_method<'target, Tgt>(
+        self,
+        target: &Tgt,
+        arg_types: &[Value<'_, 'static>],
+    ) -> JlrsResult<bool>
+    where
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 2>(|target, mut frame| {
+            let tt = tuple_type(&mut frame, arg_types)?;
+
+            // Safety: Base.hasmethod only reads the method table, the function is only used to
+            // look up its methods so borrowed data isn't accessed.
+            unsafe {
+                Module::typed_global_cached::<Value, _, _>(&target, "Base.hasmethod")?
+                    .call2(&mut frame, self.as_value().assume_owned(), tt)
+                    .into_jlrs_result()?
+                    .unbox::<bool>()
+                    .map(|b| b.as_bool())
+            }
+        })
+    }
+
+    /// Returns `true` if this function can be called with `args` by calling `Base.applicable`.
+    pub fn is_applicable<'target, Tgt>(
+        self,
+        target: &Tgt,
+        args: &[Value<'_, 'data>],
+    ) -> JlrsResult<bool>
+    where
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 1>(|target, mut frame| {
+            let mut all_args = Vec::with_capacity(args.len() + 1);
+            all_args.push(self.as_value());
+            all_args.extend_from_slice(args);
+
+            // Safety: Base.applicable only reads the method table.
+            unsafe {
+                Module::typed_global_cached::<Value, _, _>(&target, "Base.applicable")?
+                    .call(&mut frame, all_args.as_slice())
+                    .into_jlrs_result()?
+                    .unbox::<bool>()
+                    .map(|b| b.as_bool())
+            }
+        })
+    }
+
+    /// Returns the most specific method of this function that can be called with arguments of
+    /// the given types by calling `Base.which`.
+    ///
+    /// Returns `None` if there's no such method or if the call would be ambiguous. Returns an
+    /// error if the types can't be used as the parameters of a tuple type.
+    pub fn lookup_method<'target, Tgt>(
+        self,
+        target: &Tgt,
+        arg_types: &[Value<'_, 'static>],
+    ) -> JlrsResult<Option<MethodRef<'scope>>>
+    where
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 2>(|target, mut frame| {
+            let tt = tuple_type(&mut frame, arg_types)?;
+
+            // Safety: Base.which only reads the method table, the function is only used to look
+            // up its methods so borrowed data isn't accessed. The method is referenced by the
+            // method table of this function.
+            unsafe {
+                let Ok(method) = Module::typed_global_cached::<Value, _, _>(&target, "Base.which")?
+                    .call2(&mut frame, self.as_value().assume_owned(), tt)
+                else {
+                    return Ok(None);
+                };
+
+                let method = method.cast::<Method>()?;
+                Ok(Some(
+                    Method::wrap_non_null(method.unwrap_non_null(Private), Private).as_ref(),
+                ))
+            }
+        })
+    }
+
+    /// Returns the specialization of the most specific method of this function for arguments
+    /// of the given types.
+    ///
+    /// Specializations are only available after the method has been compiled for these types,
+    /// `None` is returned if there is no such method or it hasn't been specialized yet.
+    pub fn method_instance<'target, Tgt>(
+        self,
+        target: &Tgt,
+        arg_types: &[Value<'_, 'static>],
+    ) -> JlrsResult<Option<MethodInstanceRef<'scope>>>
+    where
+        Tgt: Target<'target>,
+    {
+        let Some(method) = self.lookup_method(target, arg_types)? else {
+            return Ok(None);
+        };
+
+        target.with_local_scope::<_, _, 1>(|_, mut frame| {
+            let mut types = Vec::with_capacity(arg_types.len() + 1);
+            types.push(self.datatype().as_value());
+            types.extend_from_slice(arg_types);
+            let tt = tuple_type(&mut frame, &types)?;
+
+            // Safety: the method is referenced by the method table of this function.
+            let specializations = unsafe { method.as_managed() }.specializations();
+            let mi = specializations.into_iter().find(|mi| {
+                // Safety: the method instance is referenced by the method.
+                let spec_types = unsafe { mi.as_managed() }.spec_types();
+                spec_types == Some(tt)
+            });
+
+            Ok(mi)
+        })
+    }
+}
+
+fn tuple_type<'target, Tgt>(
+    target: Tgt,
+    types: &[Value<'_, 'static>],
+) -> JlrsResult<Value<'target, 'static>>
+where
+    Tgt: Target<'target, Data<'static, Value<'target, 'static>> = Value<'target, 'static>>,
+{
+    let anytuple = DataType::anytuple_type(&target).as_value();
+    anytuple.apply_type(target, types).into_jlrs_result()
 }
 
 // Safety: The trait is implemented correctly by using the implementation
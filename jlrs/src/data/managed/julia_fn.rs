@@ -0,0 +1,262 @@
+//! A Julia function annotated with its argument and return types.
+//!
+//! Calling a Julia function with the [`Call`] trait requires converting every argument to a
+//! [`Value`] and unboxing the result manually. [`JuliaFn`] wraps a Julia function together with
+//! the Rust types of its arguments and its return type. Its arguments are converted with
+//! [`IntoJulia`], the function is called with exception catching, and the result is checked and
+//! unboxed with [`Unbox`].
+//!
+//! `JuliaFn` can be used as an argument type of functions exported with the [`julia_module`]
+//! macro. The generated Julia function only accepts subtypes of `Function` for that argument:
+//!
+//! ```ignore
+//! use jlrs::{data::managed::julia_fn::JuliaFn, prelude::*, weak_handle_unchecked};
+//!
+//! fn minimize(f: JuliaFn<(f64,), f64>, start: f64, step: f64) -> JlrsResult<f64> {
+//!     let handle = unsafe { weak_handle_unchecked!() };
+//!     let mut x = start;
+//!     let mut fx = unsafe { f.call(&handle, (x,))? };
+//!
+//!     loop {
+//!         let next = unsafe { f.call(&handle, (x + step,))? };
+//!         if next >= fx {
+//!             return Ok(x);
+//!         }
+//!
+//!         x += step;
+//!         fx = next;
+//!     }
+//! }
+//!
+//! julia_module! {
+//!     become minimize_init_fn;
+//!     fn minimize(f: JuliaFn<(f64,), f64>, start: f64, step: f64) -> JlrsResult<f64>;
+//! }
+//! ```
+//!
+//! [`Call`]: crate::call::Call
+//! [`IntoJulia`]: crate::convert::into_julia::IntoJulia
+//! [`Unbox`]: crate::convert::unbox::Unbox
+//! [`julia_module`]: ::jlrs_macros::julia_module
+
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+use jl_sys::jl_value_t;
+
+use self::private::JuliaFnArgsPriv;
+use super::{function::Function, private::ManagedPriv, value::Value, Managed, Ref};
+use crate::{
+    convert::{ccall_types::CCallArg, unbox::Unbox},
+    data::types::typecheck::Typecheck,
+    error::{JlrsResult, TypeError, CANNOT_DISPLAY_TYPE, CANNOT_DISPLAY_VALUE},
+    memory::target::{Target, TargetType},
+    private::Private,
+};
+
+/// The arguments of a [`JuliaFn`].
+///
+/// This trait is implemented for tuples with up to eight elements, every element must implement
+/// [`IntoJulia`].
+///
+/// [`IntoJulia`]: crate::convert::into_julia::IntoJulia
+pub trait JuliaFnArgs: JuliaFnArgsPriv {
+    /// The number of arguments.
+    const N_ARGS: usize;
+}
+
+/// A Julia function that takes arguments of type `Args` and returns data that can be unboxed as
+/// `R`.
+#[repr(transparent)]
+pub struct JuliaFn<'scope, 'data, Args, R> {
+    inner: NonNull<jl_value_t>,
+    _scope: PhantomData<&'scope ()>,
+    _data: PhantomData<&'data ()>,
+    _signature: PhantomData<fn(Args) -> R>,
+}
+
+impl<'scope, 'data, Args, R> JuliaFn<'scope, 'data, Args, R>
+where
+    Args: JuliaFnArgs,
+    R: Unbox + Typecheck,
+{
+    /// Convert `value` to a `JuliaFn`.
+    ///
+    /// Returns an error if `value` is not an instance of a subtype of `Function`.
+    pub fn from_value(value: Value<'scope, 'data>) -> JlrsResult<Self> {
+        if !value.is::<Function>() {
+            Err(TypeError::NotA {
+                value: value.display_string_or(CANNOT_DISPLAY_VALUE),
+                field_type: String::from("Function"),
+            })?
+        }
+
+        // Safety: value is a function
+        unsafe { Ok(Self::from_value_unchecked(value, Private)) }
+    }
+
+    /// Call this function with `args`.
+    ///
+    /// The arguments are converted to Julia data and rooted in a new local scope. If the function
+    /// throws, the exception is caught and converted to `JlrsError::Exception`. If the function
+    /// returns successfully, the result is unboxed as `R`, an error is returned if `R` is not a
+    /// valid layout for the returned data.
+    ///
+    /// Safety: this method has the same safety requirements as [`Call::call`].
+    ///
+    /// [`Call::call`]: crate::call::Call::call
+    #[inline]
+    pub unsafe fn call<'target, Tgt>(self, target: &Tgt, args: Args) -> JlrsResult<R::Output>
+    where
+        Tgt: Target<'target>,
+    {
+        args.call_unbox::<R, _>(target, self.as_value(), Private)
+    }
+}
+
+impl<'scope, 'data> Function<'scope, 'data> {
+    /// Annotate this function with the types of its arguments and its return type.
+    #[inline]
+    pub fn as_julia_fn<Args, R>(self) -> JuliaFn<'scope, 'data, Args, R>
+    where
+        Args: JuliaFnArgs,
+        R: Unbox + Typecheck,
+    {
+        // Safety: self is a function
+        unsafe { JuliaFn::from_value_unchecked(self.as_value(), Private) }
+    }
+}
+
+impl<Args, R> Clone for JuliaFn<'_, '_, Args, R> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Args, R> Copy for JuliaFn<'_, '_, Args, R> {}
+
+impl<Args, R> Debug for JuliaFn<'_, '_, Args, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.display_string() {
+            Ok(s) => f.write_str(&s),
+            Err(e) => f.write_fmt(format_args!("<Cannot display value: {}>", e)),
+        }
+    }
+}
+
+impl<'scope, 'data, Args, R> ManagedPriv<'scope, 'data> for JuliaFn<'scope, 'data, Args, R> {
+    type Wraps = jl_value_t;
+    type WithLifetimes<'target, 'da> = JuliaFn<'target, 'da, Args, R>;
+    const NAME: &'static str = "JuliaFn";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it.
+    #[inline]
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self {
+            inner,
+            _scope: PhantomData,
+            _data: PhantomData,
+            _signature: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.inner
+    }
+}
+
+/// A reference to a [`JuliaFn`] that has not been explicitly rooted.
+pub type JuliaFnRef<'scope, 'data, Args, R> = Ref<'scope, 'data, JuliaFn<'scope, 'data, Args, R>>;
+
+/// `JuliaFn` or `JuliaFnRef`, depending on the target type `Tgt`.
+pub type JuliaFnData<'target, 'data, Args, R, Tgt> =
+    <Tgt as TargetType<'target>>::Data<'data, JuliaFn<'target, 'data, Args, R>>;
+
+unsafe impl<'scope, 'data, Args, R> CCallArg for JuliaFn<'scope, 'data, Args, R> {
+    type CCallArgType = Value<'scope, 'data>;
+    type FunctionArgType = Function<'scope, 'data>;
+}
+
+macro_rules! impl_julia_fn_args {
+    ($n_args:literal, $n_roots:literal $(, $name:ident: $ty:ident)*) => {
+        impl<$($ty),*> JuliaFnArgs for ($($ty,)*)
+        where
+            $($ty: $crate::convert::into_julia::IntoJulia),*
+        {
+            const N_ARGS: usize = $n_args;
+        }
+
+        impl<$($ty),*> JuliaFnArgsPriv for ($($ty,)*)
+        where
+            $($ty: $crate::convert::into_julia::IntoJulia),*
+        {
+            #[allow(unused_mut)]
+            unsafe fn call_unbox<'target, R, Tgt>(
+                self,
+                target: &Tgt,
+                func: Value<'_, '_>,
+                _: Private,
+            ) -> JlrsResult<R::Output>
+            where
+                R: Unbox + Typecheck,
+                Tgt: Target<'target>,
+            {
+                use $crate::{
+                    call::Call, convert::into_jlrs_result::IntoJlrsResult, memory::scope::LocalScope,
+                };
+
+                target.local_scope::<_, $n_roots>(|mut frame| {
+                    let ($($name,)*) = self;
+                    let args = [$(Value::new(&mut frame, $name)),*];
+                    let res = func.call(&mut frame, args).into_jlrs_result()?;
+
+                    if !res.is::<R>() {
+                        Err(TypeError::InvalidLayout {
+                            value_type: res.datatype().display_string_or(CANNOT_DISPLAY_TYPE),
+                        })?
+                    }
+
+                    Ok(R::unbox(res))
+                })
+            }
+        }
+    };
+}
+
+impl_julia_fn_args!(0, 1);
+impl_julia_fn_args!(1, 2, a0: A0);
+impl_julia_fn_args!(2, 3, a0: A0, a1: A1);
+impl_julia_fn_args!(3, 4, a0: A0, a1: A1, a2: A2);
+impl_julia_fn_args!(4, 5, a0: A0, a1: A1, a2: A2, a3: A3);
+impl_julia_fn_args!(5, 6, a0: A0, a1: A1, a2: A2, a3: A3, a4: A4);
+impl_julia_fn_args!(6, 7, a0: A0, a1: A1, a2: A2, a3: A3, a4: A4, a5: A5);
+impl_julia_fn_args!(7, 8, a0: A0, a1: A1, a2: A2, a3: A3, a4: A4, a5: A5, a6: A6);
+impl_julia_fn_args!(8, 9, a0: A0, a1: A1, a2: A2, a3: A3, a4: A4, a5: A5, a6: A6, a7: A7);
+
+mod private {
+    use crate::{
+        convert::unbox::Unbox,
+        data::{managed::value::Value, types::typecheck::Typecheck},
+        error::JlrsResult,
+        memory::target::Target,
+        private::Private,
+    };
+
+    pub trait JuliaFnArgsPriv: Sized {
+        unsafe fn call_unbox<'target, R, Tgt>(
+            self,
+            target: &Tgt,
+            func: Value<'_, '_>,
+            _: Private,
+        ) -> JlrsResult<R::Output>
+        where
+            R: Unbox + Typecheck,
+            Tgt: Target<'target>;
+    }
+}
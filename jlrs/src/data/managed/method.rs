@@ -0,0 +1,199 @@
+//! Managed type for `Method`.
+//!
+//! A `Method` is a single method of a generic function, its signature is a tuple type whose
+//! first element is the type of the function. The layout of `Method` is opaque to jlrs, its
+//! fields are accessed by name.
+
+use std::{marker::PhantomData, ptr::NonNull, sync::atomic::Ordering};
+
+use jl_sys::{jl_method_t, jl_method_type};
+
+use super::{
+    method_instance::{MethodInstance, MethodInstanceRef},
+    module::Module,
+    simple_vector::SimpleVector,
+    symbol::Symbol,
+    value::{Value, ValueRef},
+    Managed, Ref,
+};
+use crate::{
+    call::Call,
+    convert::into_jlrs_result::IntoJlrsResult,
+    data::managed::{array::Vector, private::ManagedPriv},
+    error::JlrsResult,
+    impl_julia_typecheck,
+    memory::target::{Target, TargetResult, TargetType},
+    private::Private,
+};
+
+/// A method of a generic function.
+#[derive(Copy, Clone, PartialEq)]
+#[repr(transparent)]
+pub struct Method<'scope>(NonNull<jl_method_t>, PhantomData<&'scope ()>);
+
+impl<'scope> Method<'scope> {
+    /// The `name` field.
+    pub fn name(self) -> Option<Symbol<'scope>> {
+        // Safety: the field is only read.
+        unsafe { Some(self.pointer_field("name")?.as_value().cast_unchecked()) }
+    }
+
+    /// The `module` field, the module this method has been defined in.
+    pub fn module(self) -> Option<Module<'scope>> {
+        // Safety: the field is only read.
+        unsafe { Some(self.pointer_field("module")?.as_value().cast_unchecked()) }
+    }
+
+    /// The `file` field, the file this method has been defined in.
+    pub fn file(self) -> Option<Symbol<'scope>> {
+        // Safety: the field is only read.
+        unsafe { Some(self.pointer_field("file")?.as_value().cast_unchecked()) }
+    }
+
+    /// The `line` field, the line this method has been defined on.
+    pub fn line(self) -> Option<i32> {
+        self.as_value()
+            .field_accessor()
+            .field("line")
+            .ok()?
+            .access::<i32>()
+            .ok()
+    }
+
+    /// The `sig` field, the signature of this method.
+    ///
+    /// The signature is a tuple type whose first element is the type of the function. If the
+    /// method has static parameters the signature is a `UnionAll`.
+    pub fn signature(self) -> Option<Value<'scope, 'static>> {
+        // Safety: the field is only read.
+        unsafe { Some(self.pointer_field("sig")?.as_value()) }
+    }
+
+    /// The `nargs` field, the number of arguments including the function itself.
+    pub fn n_args(self) -> Option<i32> {
+        self.as_value()
+            .field_accessor()
+            .field("nargs")
+            .ok()?
+            .access::<i32>()
+            .ok()
+    }
+
+    /// The `isva` field, `true` if the last argument of this method is a vararg.
+    pub fn is_vararg(self) -> Option<bool> {
+        self.as_value()
+            .field_accessor()
+            .field("isva")
+            .ok()?
+            .access::<bool>()
+            .ok()
+    }
+
+    /// Returns the names of the arguments of this method by calling `Base.method_argnames`.
+    ///
+    /// The name of the function itself is not included. Unnamed arguments are named `#unused#`.
+    pub fn argument_names<'target, Tgt>(self, target: &Tgt) -> JlrsResult<Vec<Symbol<'scope>>>
+    where
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 1>(|target, mut frame| {
+            // Safety: Base.method_argnames only reads the method.
+            let names = unsafe {
+                Module::typed_global_cached::<Value, _, _>(&target, "Base.method_argnames")?
+                    .call1(&mut frame, self.as_value())
+                    .into_jlrs_result()?
+                    .cast::<Vector>()?
+            };
+
+            // Safety: the array is rooted and not mutated, symbols are globally rooted.
+            unsafe {
+                let names = names
+                    .try_managed_data::<Symbol>()?
+                    .as_slice()
+                    .iter()
+                    .skip(1)
+                    .filter_map(|sym| sym.load(Ordering::Relaxed))
+                    .map(|sym| Symbol::wrap_non_null(sym.ptr(), Private))
+                    .collect();
+
+                Ok(names)
+            }
+        })
+    }
+
+    /// Returns the specializations of this method that have been created.
+    ///
+    /// A specialization is created when a method is compiled for specific argument types, it
+    /// contains the compiled code. Methods that have never been called usually have no
+    /// specializations.
+    pub fn specializations(self) -> Vec<MethodInstanceRef<'scope>> {
+        let Some(specializations) = self.pointer_field("specializations") else {
+            return Vec::new();
+        };
+
+        // Safety: the field is only read, its elements are referenced by this method.
+        unsafe {
+            let specializations = specializations.as_value();
+            if let Ok(mi) = specializations.cast::<MethodInstance>() {
+                return vec![mi.as_ref()];
+            }
+
+            let Ok(specializations) = specializations.cast::<SimpleVector>() else {
+                return Vec::new();
+            };
+
+            let data = specializations.data();
+            (0..data.len())
+                .filter_map(|i| data.get(self.unrooted_target(), i))
+                .filter_map(|mi| mi.as_value().cast::<MethodInstance>().ok())
+                .map(|mi| MethodInstance::wrap_non_null(mi.unwrap_non_null(Private), Private))
+                .map(|mi| mi.as_ref())
+                .collect()
+        }
+    }
+
+    fn pointer_field(self, name: &str) -> Option<ValueRef<'scope, 'static>> {
+        self.as_value().get_field_ref(name).ok().flatten()
+    }
+}
+
+impl_julia_typecheck!(Method<'scope>, jl_method_type, 'scope);
+impl_debug!(Method<'_>);
+
+impl<'scope> ManagedPriv<'scope, '_> for Method<'scope> {
+    type Wraps = jl_method_t;
+    type WithLifetimes<'target, 'da> = Method<'target>;
+    const NAME: &'static str = "Method";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it.
+    #[inline]
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self(inner, PhantomData)
+    }
+
+    #[inline]
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.0
+    }
+}
+
+impl_construct_type_managed!(Method, 1, jl_method_type);
+
+/// A reference to a [`Method`] that has not been explicitly rooted.
+pub type MethodRef<'scope> = Ref<'scope, 'static, Method<'scope>>;
+
+/// A [`MethodRef`] with static lifetimes. This is a useful shorthand for signatures of
+/// `ccall`able functions that return a [`Method`].
+pub type MethodRet = Ref<'static, 'static, Method<'static>>;
+
+impl_valid_layout!(MethodRef, Method, jl_method_type);
+
+/// `Method` or `MethodRef`, depending on the target type `Tgt`.
+pub type MethodData<'target, Tgt> = <Tgt as TargetType<'target>>::Data<'static, Method<'target>>;
+
+/// `JuliaResult<Method>` or `JuliaResultRef<MethodRef>`, depending on the target type `Tgt`.
+pub type MethodResult<'target, Tgt> = TargetResult<'target, 'static, Method<'target>, Tgt>;
+
+impl_ccall_arg_managed!(Method, 1);
+impl_into_typed!(Method);
@@ -0,0 +1,109 @@
+//! Managed type for `MethodInstance`.
+//!
+//! A `MethodInstance` is a specialization of a [`Method`] for specific argument types. The
+//! layout of `MethodInstance` is opaque to jlrs, its fields are accessed by name.
+
+use std::{marker::PhantomData, ptr::NonNull};
+
+use jl_sys::{jl_method_instance_t, jl_method_instance_type};
+
+use super::{
+    code_instance::{CodeInstance, CodeInstanceRef},
+    method::{Method, MethodRef},
+    value::{Value, ValueRef},
+    Managed, Ref,
+};
+use crate::{
+    data::managed::private::ManagedPriv,
+    impl_julia_typecheck,
+    memory::target::{TargetResult, TargetType},
+    private::Private,
+};
+
+/// A specialization of a method.
+#[derive(Copy, Clone, PartialEq)]
+#[repr(transparent)]
+pub struct MethodInstance<'scope>(NonNull<jl_method_instance_t>, PhantomData<&'scope ()>);
+
+impl<'scope> MethodInstance<'scope> {
+    /// The `def` field if it's a `Method`, the method this instance is a specialization of.
+    ///
+    /// Top-level thunks are defined in a `Module` rather than a method, `None` is returned in
+    /// that case.
+    pub fn method(self) -> Option<MethodRef<'scope>> {
+        // Safety: the field is only read.
+        unsafe {
+            let def = self.pointer_field("def")?.as_value();
+            let method = def.cast::<Method>().ok()?;
+            Some(Method::wrap_non_null(method.unwrap_non_null(Private), Private).as_ref())
+        }
+    }
+
+    /// The `specTypes` field, the argument types this instance has been specialized for.
+    ///
+    /// This is a tuple type whose first element is the type of the function.
+    pub fn spec_types(self) -> Option<Value<'scope, 'static>> {
+        // Safety: the field is only read.
+        unsafe { Some(self.pointer_field("specTypes")?.as_value()) }
+    }
+
+    /// The `cache` field, the first code instance of this specialization.
+    ///
+    /// Returns `None` if this specialization hasn't been inferred or compiled.
+    pub fn cache(self) -> Option<CodeInstanceRef<'scope>> {
+        // Safety: the field is only read.
+        unsafe {
+            let cache = self.pointer_field("cache")?.as_value();
+            let ci = cache.cast::<CodeInstance>().ok()?;
+            Some(CodeInstance::wrap_non_null(ci.unwrap_non_null(Private), Private).as_ref())
+        }
+    }
+
+    fn pointer_field(self, name: &str) -> Option<ValueRef<'scope, 'static>> {
+        self.as_value().get_field_ref(name).ok().flatten()
+    }
+}
+
+impl_julia_typecheck!(MethodInstance<'scope>, jl_method_instance_type, 'scope);
+impl_debug!(MethodInstance<'_>);
+
+impl<'scope> ManagedPriv<'scope, '_> for MethodInstance<'scope> {
+    type Wraps = jl_method_instance_t;
+    type WithLifetimes<'target, 'da> = MethodInstance<'target>;
+    const NAME: &'static str = "MethodInstance";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it.
+    #[inline]
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self(inner, PhantomData)
+    }
+
+    #[inline]
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.0
+    }
+}
+
+impl_construct_type_managed!(MethodInstance, 1, jl_method_instance_type);
+
+/// A reference to a [`MethodInstance`] that has not been explicitly rooted.
+pub type MethodInstanceRef<'scope> = Ref<'scope, 'static, MethodInstance<'scope>>;
+
+/// A [`MethodInstanceRef`] with static lifetimes. This is a useful shorthand for signatures of
+/// `ccall`able functions that return a [`MethodInstance`].
+pub type MethodInstanceRet = Ref<'static, 'static, MethodInstance<'static>>;
+
+impl_valid_layout!(MethodInstanceRef, MethodInstance, jl_method_instance_type);
+
+/// `MethodInstance` or `MethodInstanceRef`, depending on the target type `Tgt`.
+pub type MethodInstanceData<'target, Tgt> =
+    <Tgt as TargetType<'target>>::Data<'static, MethodInstance<'target>>;
+
+/// `JuliaResult<MethodInstance>` or `JuliaResultRef<MethodInstanceRef>`, depending on the target
+/// type `Tgt`.
+pub type MethodInstanceResult<'target, Tgt> =
+    TargetResult<'target, 'static, MethodInstance<'target>, Tgt>;
+
+impl_ccall_arg_managed!(MethodInstance, 1);
+impl_into_typed!(MethodInstance);
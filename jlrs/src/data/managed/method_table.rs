@@ -0,0 +1,83 @@
+//! Managed type for `MethodTable`.
+//!
+//! A `MethodTable` stores the methods of one or more generic functions. The layout of
+//! `MethodTable` is opaque to jlrs, its fields are accessed by name.
+
+use std::{marker::PhantomData, ptr::NonNull};
+
+use jl_sys::{jl_methtable_t, jl_methtable_type};
+
+use super::{module::Module, symbol::Symbol, value::ValueRef, Managed, Ref};
+use crate::{
+    data::managed::private::ManagedPriv,
+    impl_julia_typecheck,
+    memory::target::{TargetResult, TargetType},
+    private::Private,
+};
+
+/// The method table of a generic function.
+#[derive(Copy, Clone, PartialEq)]
+#[repr(transparent)]
+pub struct MethodTable<'scope>(NonNull<jl_methtable_t>, PhantomData<&'scope ()>);
+
+impl<'scope> MethodTable<'scope> {
+    /// The `name` field.
+    pub fn name(self) -> Option<Symbol<'scope>> {
+        // Safety: the field is only read.
+        unsafe { Some(self.pointer_field("name")?.as_value().cast_unchecked()) }
+    }
+
+    /// The `module` field, the module this method table has been defined in.
+    pub fn module(self) -> Option<Module<'scope>> {
+        // Safety: the field is only read.
+        unsafe { Some(self.pointer_field("module")?.as_value().cast_unchecked()) }
+    }
+
+    fn pointer_field(self, name: &str) -> Option<ValueRef<'scope, 'static>> {
+        self.as_value().get_field_ref(name).ok().flatten()
+    }
+}
+
+impl_julia_typecheck!(MethodTable<'scope>, jl_methtable_type, 'scope);
+impl_debug!(MethodTable<'_>);
+
+impl<'scope> ManagedPriv<'scope, '_> for MethodTable<'scope> {
+    type Wraps = jl_methtable_t;
+    type WithLifetimes<'target, 'da> = MethodTable<'target>;
+    const NAME: &'static str = "MethodTable";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it.
+    #[inline]
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self(inner, PhantomData)
+    }
+
+    #[inline]
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.0
+    }
+}
+
+impl_construct_type_managed!(MethodTable, 1, jl_methtable_type);
+
+/// A reference to a [`MethodTable`] that has not been explicitly rooted.
+pub type MethodTableRef<'scope> = Ref<'scope, 'static, MethodTable<'scope>>;
+
+/// A [`MethodTableRef`] with static lifetimes. This is a useful shorthand for signatures of
+/// `ccall`able functions that return a [`MethodTable`].
+pub type MethodTableRet = Ref<'static, 'static, MethodTable<'static>>;
+
+impl_valid_layout!(MethodTableRef, MethodTable, jl_methtable_type);
+
+/// `MethodTable` or `MethodTableRef`, depending on the target type `Tgt`.
+pub type MethodTableData<'target, Tgt> =
+    <Tgt as TargetType<'target>>::Data<'static, MethodTable<'target>>;
+
+/// `JuliaResult<MethodTable>` or `JuliaResultRef<MethodTableRef>`, depending on the target type
+/// `Tgt`.
+pub type MethodTableResult<'target, Tgt> =
+    TargetResult<'target, 'static, MethodTable<'target>, Tgt>;
+
+impl_ccall_arg_managed!(MethodTable, 1);
+impl_into_typed!(MethodTable);
@@ -288,11 +288,16 @@ macro_rules! impl_debug {
 pub mod array;
 pub mod background_task;
 pub mod ccall_ref;
+pub mod code_instance;
 pub mod datatype;
 #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8")))]
 pub mod delegated_task;
 pub mod expr;
 pub mod function;
+pub mod julia_fn;
+pub mod method;
+pub mod method_instance;
+pub mod method_table;
 pub mod module;
 pub mod parachute;
 pub mod simple_vector;
@@ -515,10 +520,13 @@ impl<'scope, 'data, W: Managed<'scope, 'data>> Ref<'scope, 'data, W> {
     ///
     /// Safety: The data pointed to by `self` must not have been freed by the GC yet.
     #[inline]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
     pub unsafe fn root<'target, Tgt>(self, target: Tgt) -> Tgt::Data<'data, W::InScope<'target>>
     where
         Tgt: Target<'target>,
     {
+        #[cfg(feature = "diagnostics")]
+        crate::memory::diagnostics::check(self.ptr(), std::panic::Location::caller());
         target.data_from_ptr(self.ptr().cast(), Private)
     }
 
@@ -533,7 +541,10 @@ impl<'scope, 'data, W: Managed<'scope, 'data>> Ref<'scope, 'data, W> {
     /// GC root. If the reference is unreachable, the GC can free it. The GC can run whenever a
     /// safepoint is reached, this is typically the case when new Julia data is allocated.
     #[inline]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
     pub unsafe fn as_managed(self) -> W {
+        #[cfg(feature = "diagnostics")]
+        crate::memory::diagnostics::check(self.ptr(), std::panic::Location::caller());
         W::wrap_non_null(self.ptr(), Private)
     }
 
@@ -543,7 +554,10 @@ impl<'scope, 'data, W: Managed<'scope, 'data>> Ref<'scope, 'data, W> {
     /// GC root. If the reference is unreachable, the GC can free it. The GC can run whenever a
     /// safepoint is reached, this is typically the case when new Julia data is allocated.
     #[inline]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
     pub unsafe fn as_value(self) -> Value<'scope, 'data> {
+        #[cfg(feature = "diagnostics")]
+        crate::memory::diagnostics::check(self.ptr(), std::panic::Location::caller());
         Value::wrap_non_null(self.data_ptr().cast(), Private)
     }
 
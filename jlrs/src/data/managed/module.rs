@@ -4,41 +4,53 @@
 //! modules, `Main`, `Base` and `Core`. Any Julia code that you include in jlrs is made available
 //! relative to the `Main` module.
 
-// todo: jl_new_module
-
-use std::{any::TypeId, marker::PhantomData, ptr::NonNull};
+use std::{
+    any::{Any, TypeId},
+    ffi::c_void,
+    marker::PhantomData,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::Path,
+    ptr::NonNull,
+    sync::{atomic::Ordering, Arc},
+};
 
 use jl_sys::{
     jl_base_module, jl_core_module, jl_get_global, jl_is_const, jl_is_imported, jl_main_module,
-    jl_module_t, jl_module_type, jl_set_const, jlrs_module_name, jlrs_module_parent,
-    jlrs_set_global,
+    jl_module_t, jl_module_type, jl_set_const, jl_sym_t, jl_throw, jl_value_t, jlrs_module_name,
+    jlrs_module_parent, jlrs_new_module, jlrs_set_global,
 };
 use jlrs_macros::julia_version;
-use rustc_hash::FxHashMap;
+use parking_lot::{const_rwlock, RwLock};
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use super::{
     erase_scope_lifetime,
     function::FunctionData,
-    value::{ValueData, ValueResult, ValueUnbound},
+    value::{ValueData, ValueRef, ValueResult, ValueUnbound},
     Managed, Ref,
 };
 use crate::{
-    call::Call,
+    call::{Call, ProvideKeywords},
     catch::{catch_exceptions, unwrap_exc},
-    convert::to_symbol::ToSymbol,
+    convert::{into_jlrs_result::IntoJlrsResult, to_symbol::ToSymbol},
     data::{
         layout::nothing::Nothing,
         managed::{
-            function::Function, private::ManagedPriv, symbol::Symbol, union_all::UnionAll,
-            value::Value,
+            array::Vector, function::Function, private::ManagedPriv, string::JuliaString,
+            symbol::Symbol, union_all::UnionAll, value::Value,
         },
         static_data::StaticRef,
         types::{construct_type::ConstructType, typecheck::Typecheck},
     },
-    error::{AccessError, JlrsResult, TypeError},
+    error::{AccessError, IOError, JlrsResult, TypeError},
     gc_safe::{GcSafeOnceLock, GcSafeRwLock},
     impl_julia_typecheck, inline_static_ref,
-    memory::target::{Target, TargetException, TargetResult},
+    memory::{
+        global_root::GlobalRoot,
+        scope::LocalScope,
+        target::{unrooted::Unrooted, Target, TargetException, TargetResult},
+    },
+    named_tuple,
     prelude::DataType,
     private::Private,
 };
@@ -75,6 +87,13 @@ pub(crate) unsafe fn init_global_cache() {
     CACHE.set(GlobalCache::new()).ok();
 }
 
+// The functions defined by `module`, see `module-default-defs` in Julia's `julia-syntax.scm`.
+const MODULE_DEFAULT_DEFS: &str = "
+    eval(x) = Core.eval(@__MODULE__, x)
+    include(x) = Base.include(@__MODULE__, x)
+    include(mapexpr::Function, x) = Base.include(mapexpr, @__MODULE__, x)
+";
+
 /// Functionality in Julia can be accessed through its module system. You can get a handle to the
 /// three standard modules, `Main`, `Base`, and `Core` and access their submodules through them.
 /// If you include your own Julia code with [`Julia::include`] or [`AsyncHandle::include`], its
@@ -90,6 +109,66 @@ pub(crate) unsafe fn init_global_cache() {
 pub struct Module<'scope>(NonNull<jl_module_t>, PhantomData<&'scope ()>);
 
 impl<'scope> Module<'scope> {
+    /// Create a new module named `name` whose parent is `parent`.
+    ///
+    /// The new module is equivalent to a module defined with `module`, it imports `Base` and
+    /// defines its own `eval` and `include` functions. It isn't made available as a global in
+    /// `parent`, use [`Module::set_const`] to do so. A module that isn't referenced anywhere can
+    /// be freed by the GC after it has become unreachable.
+    ///
+    /// Code can be evaluated in the new module with [`Module::eval`], [`Module::eval_string`] and
+    /// [`Module::include`].
+    ///
+    /// If an exception is thrown while `eval` and `include` are defined, it's caught and returned
+    /// as an error.
+    pub fn new<'target, N, Tgt>(
+        target: Tgt,
+        name: N,
+        parent: Module,
+    ) -> JlrsResult<ModuleData<'target, Tgt>>
+    where
+        N: ToSymbol,
+        Tgt: Target<'target>,
+    {
+        // Safety: the pointer points to valid data, the C API function is called with valid
+        // arguments and its result is immediately rooted. The default definitions only define
+        // new functions in the new module.
+        target.with_local_scope::<_, _, 2>(|target, mut frame| unsafe {
+            let name = name.to_symbol_priv(Private);
+            let module = jlrs_new_module(name.unwrap(Private), parent.unwrap(Private), 1);
+            let module =
+                Module::wrap_non_null(NonNull::new_unchecked(module), Private).root(&mut frame);
+
+            module
+                .eval_string(&mut frame, MODULE_DEFAULT_DEFS)
+                .into_jlrs_result()?;
+
+            Ok(target.data_from_ptr(module.unwrap_non_null(Private), Private))
+        })
+    }
+
+    /// Create a new bare module named `name` whose parent is `parent`.
+    ///
+    /// The new module is equivalent to a module defined with `baremodule`, it doesn't import
+    /// `Base`. See [`Module::new`] for more information.
+    pub fn new_bare<'target, N, Tgt>(
+        target: Tgt,
+        name: N,
+        parent: Module,
+    ) -> ModuleData<'target, Tgt>
+    where
+        N: ToSymbol,
+        Tgt: Target<'target>,
+    {
+        // Safety: the pointer points to valid data, the C API function is called with valid
+        // arguments and its result is immediately rooted.
+        unsafe {
+            let name = name.to_symbol_priv(Private);
+            let module = jlrs_new_module(name.unwrap(Private), parent.unwrap(Private), 0);
+            target.data_from_ptr(NonNull::new_unchecked(module), Private)
+        }
+    }
+
     /// Returns the name of this module.
     #[inline]
     pub fn name(self) -> Symbol<'scope> {
@@ -143,23 +222,7 @@ impl<'scope> Module<'scope> {
 
         let mut parts = path.split('.');
         let n_parts = parts.clone().count();
-        let module_name = parts.next().unwrap();
-
-        let mut module = match module_name {
-            "Main" => Module::main(&target),
-            "Base" => Module::base(&target),
-            "Core" => Module::core(&target),
-            "JlrsCore" => Module::jlrs_core(&target),
-            module => {
-                if let Some(module) = Module::package_root_module(&target, module) {
-                    module
-                } else {
-                    Err(AccessError::ModuleNotFound {
-                        module: module_name.into(),
-                    })?
-                }
-            }
-        };
+        let mut module = Module::root_module(target, parts.next().unwrap())?;
 
         let item = match n_parts {
             1 => module.as_value().cast::<T>()?,
@@ -275,6 +338,53 @@ impl<'scope> Module<'scope> {
         }
     }
 
+    /// Returns the module at `path`, e.g. `"Main.MyModule"`.
+    ///
+    /// The first part of the path must be `Main`, `Base`, `Core`, `JlrsCore` or the name of a
+    /// loaded package, every other part must be a submodule of the previous one. Unlike
+    /// [`Module::typed_global_cached`] the path is resolved every time this method is called.
+    ///
+    /// Returns an error if one of the modules doesn't exist.
+    pub fn from_path<'target, S, Tgt>(target: Tgt, path: S) -> JlrsResult<ModuleData<'target, Tgt>>
+    where
+        S: AsRef<str>,
+        Tgt: Target<'target>,
+    {
+        let mut parts = path.as_ref().split('.');
+        let mut module = Module::root_module(&target, parts.next().unwrap())?;
+
+        for part in parts {
+            // Safety: the submodule is reachable from its parent.
+            module = unsafe { module.submodule(&target, part)?.as_managed() };
+        }
+
+        // Safety: the module is reachable from its parent.
+        unsafe { Ok(target.data_from_ptr(module.unwrap_non_null(Private), Private)) }
+    }
+
+    fn root_module<'target, Tgt>(target: &Tgt, name: &str) -> JlrsResult<Module<'target>>
+    where
+        Tgt: Target<'target>,
+    {
+        let module = match name {
+            "Main" => Module::main(target),
+            "Base" => Module::base(target),
+            "Core" => Module::core(target),
+            "JlrsCore" => Module::jlrs_core(target),
+            module => {
+                if let Some(module) = Module::package_root_module(target, module) {
+                    module
+                } else {
+                    Err(AccessError::ModuleNotFound {
+                        module: name.into(),
+                    })?
+                }
+            }
+        };
+
+        Ok(module)
+    }
+
     /// Returns the root module of the package named `name`.
     ///
     /// All loaded packages can be accessed with this method. If the package doesn't exist or
@@ -419,6 +529,173 @@ impl<'scope> Module<'scope> {
         Value::wrap_non_null(value.unwrap_non_null(Private), Private)
     }
 
+    /// Declare the global `name` in this module with the declared type `ty`, i.e. evaluate
+    /// `global name::ty`, and assign `value` to it if it's provided. The value is converted to
+    /// `ty` before it's assigned. This method is available since Julia 1.9.
+    ///
+    /// If an exception is thrown, e.g. because the global already has a different declared type
+    /// or `value` can't be converted, it's caught and returned as an error.
+    ///
+    /// Safety: Mutating Julia data is generally unsafe because it can't be guaranteed mutating
+    /// this value is allowed.
+    #[julia_version(since = "1.9")]
+    pub unsafe fn declare_global<'target, N, Tgt>(
+        self,
+        target: &Tgt,
+        name: N,
+        ty: Value<'_, 'static>,
+        value: Option<Value<'_, 'static>>,
+    ) -> JlrsResult<()>
+    where
+        N: ToSymbol,
+        Tgt: Target<'target>,
+    {
+        use crate::data::managed::expr::Expr;
+
+        let symbol = name.to_symbol_priv(Private);
+
+        target.with_local_scope::<_, _, 4>(|target, mut frame| {
+            let decl = Expr::new(&mut frame, "::", [symbol.as_value(), ty]).into_jlrs_result()?;
+            let global = Expr::new(&mut frame, "global", [decl.as_value()]).into_jlrs_result()?;
+            self.eval(&target, global.as_value())
+                .map_err(|e| e.as_value())
+                .into_jlrs_result()?;
+
+            if let Some(value) = value {
+                let value = Module::typed_global_cached::<Value, _, _>(&target, "Base.convert")?
+                    .call2(&mut frame, ty, value)
+                    .into_jlrs_result()?;
+                self.set_global(&frame, symbol, value)
+                    .map_err(|e| e.as_value())
+                    .into_jlrs_result()?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Set the global `name` in this module by calling `Core.setglobal!` with the memory
+    /// ordering `order`. The assigned value is returned. If an exception is thrown, e.g. because
+    /// the value doesn't match the declared type of the global, it's caught and returned.
+    ///
+    /// Safety: Mutating Julia data is generally unsafe because it can't be guaranteed mutating
+    /// this value is allowed.
+    #[julia_version(since = "1.9")]
+    pub unsafe fn set_global_atomic<'target, N, Tgt>(
+        self,
+        target: Tgt,
+        name: N,
+        value: Value<'_, 'static>,
+        order: MemoryOrder,
+    ) -> ValueResult<'target, 'static, Tgt>
+    where
+        N: ToSymbol,
+        Tgt: Target<'target>,
+    {
+        let symbol = name.to_symbol_priv(Private);
+        let order = order.as_symbol(&target);
+
+        Module::typed_global_cached::<Value, _, _>(&target, "Core.setglobal!")
+            .unwrap()
+            .call(
+                target,
+                [self.as_value(), symbol.as_value(), value, order.as_value()],
+            )
+    }
+
+    /// Atomically replace the value of the global `name` in this module by calling
+    /// `Core.swapglobal!` with the memory ordering `order`. The old value is returned. If an
+    /// exception is thrown it's caught and returned. This method is available since Julia 1.11.
+    ///
+    /// Safety: Mutating Julia data is generally unsafe because it can't be guaranteed mutating
+    /// this value is allowed.
+    #[julia_version(since = "1.11")]
+    pub unsafe fn swap_global<'target, N, Tgt>(
+        self,
+        target: Tgt,
+        name: N,
+        value: Value<'_, 'static>,
+        order: MemoryOrder,
+    ) -> ValueResult<'target, 'static, Tgt>
+    where
+        N: ToSymbol,
+        Tgt: Target<'target>,
+    {
+        let symbol = name.to_symbol_priv(Private);
+        let order = order.as_symbol(&target);
+
+        Module::typed_global_cached::<Value, _, _>(&target, "Core.swapglobal!")
+            .unwrap()
+            .call(
+                target,
+                [self.as_value(), symbol.as_value(), value, order.as_value()],
+            )
+    }
+
+    /// Watch the global `name` in this module: the function `set_{name}!` is defined in this
+    /// module, which assigns its argument to the global and calls `callback` with the new
+    /// value. If the global doesn't exist yet, it's created by the first call to the setter.
+    ///
+    /// Only assignments through the setter are observed, both Julia and Rust code must use it to
+    /// keep the watchers informed. Multiple callbacks can watch the same global, they're called
+    /// in the order they have been registered. The callback is called from the thread that calls
+    /// the setter. If a callback panics, the remaining callbacks are still called and the panic
+    /// is rethrown as a `JlrsCore.JlrsError`. The callback is unregistered when the returned
+    /// [`GlobalWatcher`] is dropped, this module is rooted until then.
+    ///
+    /// Safety: the setter is defined by evaluating Julia code, it replaces any existing method
+    /// of `set_{name}!` that takes a single argument.
+    pub unsafe fn watch_global<'target, N, F, Tgt>(
+        self,
+        target: &Tgt,
+        name: N,
+        callback: F,
+    ) -> JlrsResult<GlobalWatcher>
+    where
+        N: ToSymbol,
+        F: 'static + Send + Sync + Fn(Value<'_, 'static>),
+        Tgt: Target<'target>,
+    {
+        let symbol = name.to_symbol_priv(Private);
+        let name = symbol.as_str()?;
+        let notify = notify_global_set as *const c_void as usize;
+
+        // The setter reads the address of `notify_global_set` from a `Ref` when it's called, so
+        // the address isn't part of the compiled setter. Pointers are reset to `C_NULL` when a
+        // module is serialized, so a setter that has been precompiled in another session
+        // doesn't notify anything until the global is watched again.
+        let code = format!(
+            "let name = Symbol({name:?}),
+                 setter = Symbol(\"set_\", name, \"!\"),
+                 notify = Symbol(\"__jlrs_notify_\", name, \"__\")
+                 isdefined(@__MODULE__, notify) || @eval const $notify = Ref{{Ptr{{Cvoid}}}}(C_NULL)
+                 getfield(@__MODULE__, notify)[] = Ptr{{Cvoid}}({notify:#x})
+                 @eval function $setter(v)
+                     global $name = v
+                     f = $notify[]
+                     f == C_NULL || ccall(f, Cvoid, (Any, Any, Any), @__MODULE__, $(QuoteNode(name)), $name)
+                     $name
+                 end
+             end"
+        );
+
+        target.with_local_scope::<_, _, 1>(|_, mut frame| {
+            self.eval_string(&mut frame, code)
+                .map(|_| ())
+                .into_jlrs_result()
+        })?;
+
+        // The module is rooted while it's watched so its address can't be reused by another
+        // module, symbols are never freed.
+        let module = GlobalRoot::new(self);
+        let id = WATCHED_GLOBALS.write().register(
+            module,
+            symbol.unwrap(Private) as usize,
+            Arc::new(callback),
+        );
+        Ok(GlobalWatcher { id })
+    }
+
     /// Returns the global named `name` in this module.
     /// Returns an error if the global doesn't exist.
     pub fn global<'target, N, Tgt>(
@@ -484,6 +761,208 @@ impl<'scope> Module<'scope> {
         }
     }
 
+    /// Returns the names of the bindings in this module by calling `Base.names`.
+    ///
+    /// If `all` is `false` only exported names are returned, otherwise names that aren't
+    /// exported, deprecated names and compiler-generated names are included as well. If
+    /// `imported` is `true` names that have been explicitly imported from other modules are also
+    /// returned. Returns an error if `Base.names` throws an exception.
+    pub fn names<'target, Tgt>(
+        self,
+        target: &Tgt,
+        all: bool,
+        imported: bool,
+    ) -> JlrsResult<Vec<Symbol<'scope>>>
+    where
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 2>(|target, mut frame| {
+            let all_v = if all {
+                Value::true_v(&target)
+            } else {
+                Value::false_v(&target)
+            };
+            let imported_v = if imported {
+                Value::true_v(&target)
+            } else {
+                Value::false_v(&target)
+            };
+
+            let kws = named_tuple!(&mut frame, "all" => all_v, "imported" => imported_v);
+
+            // Safety: Base.names only reads the content of the module.
+            let names = unsafe {
+                Module::typed_global_cached::<Value, _, _>(&target, "Base.names")?
+                    .provide_keywords(kws)?
+                    .call1(&mut frame, self.as_value())
+                    .into_jlrs_result()?
+                    .cast::<Vector>()?
+            };
+
+            // Safety: the array is rooted and not mutated, symbols are globally rooted.
+            unsafe {
+                let names = names
+                    .try_managed_data::<Symbol>()?
+                    .as_slice()
+                    .iter()
+                    .filter_map(|sym| sym.load(Ordering::Relaxed))
+                    .map(|sym| Symbol::wrap_non_null(sym.ptr(), Private))
+                    .collect();
+
+                Ok(names)
+            }
+        })
+    }
+
+    /// Returns the names exported by this module.
+    ///
+    /// This is equivalent to calling [`Module::names`] with `all` and `imported` set to `false`.
+    /// The name of the module itself is always included. Since Julia 1.11 names that have been
+    /// declared `public` are included too.
+    #[inline]
+    pub fn exported_names<'target, Tgt>(self, target: &Tgt) -> JlrsResult<Vec<Symbol<'scope>>>
+    where
+        Tgt: Target<'target>,
+    {
+        self.names(target, false, false)
+    }
+
+    /// Returns information about the bindings in this module.
+    ///
+    /// The bindings are the names returned by [`Module::names`] called with the same `all` and
+    /// `imported` arguments. Returns an error if the names can't be listed.
+    ///
+    /// Safety: the declared type and owner of every binding are looked up with
+    /// `Core.get_binding_type` and `Base.which`. Both resolve the binding, so a name that is
+    /// implicitly available through `using` becomes an import of that module. Afterwards this
+    /// module can no longer define a global with that name, and resolving a deprecated binding
+    /// prints a warning. This method must not be called while other threads can access this
+    /// module.
+    pub unsafe fn bindings<'target, Tgt>(
+        self,
+        target: &Tgt,
+        all: bool,
+        imported: bool,
+    ) -> JlrsResult<Vec<Binding<'scope>>>
+    where
+        Tgt: Target<'target>,
+    {
+        let names = self.names(target, all, imported)?;
+        let exported = if all {
+            self.exported_names(target)?
+        } else {
+            names.clone()
+        };
+
+        let bindings = names
+            .into_iter()
+            .map(|name| Binding {
+                name,
+                is_const: self.is_const(name),
+                is_exported: exported.contains(&name),
+                is_imported: self.is_imported(name),
+                binding_type: self.binding_type(target, name),
+                owner: self.binding_owner(target, name),
+            })
+            .collect();
+
+        Ok(bindings)
+    }
+
+    /// Returns the submodules of this module.
+    ///
+    /// A submodule is a module stored in a global of this module whose parent is this module,
+    /// imported modules and the module itself are skipped. If `recursive` is `true` the
+    /// submodules of the submodules are returned too, every module is returned at most once.
+    pub fn submodules<'target, Tgt>(
+        self,
+        target: &Tgt,
+        recursive: bool,
+    ) -> JlrsResult<Vec<ModuleRef<'scope>>>
+    where
+        Tgt: Target<'target>,
+    {
+        let mut submodules = Vec::new();
+        let mut visited = FxHashSet::default();
+        visited.insert(self.unwrap_non_null(Private));
+        self.collect_submodules(target, recursive, &mut visited, &mut submodules)?;
+        Ok(submodules)
+    }
+
+    /// Evaluate the expression `expr` in this module by calling `Core.eval`. If an exception is
+    /// thrown it's caught and returned.
+    ///
+    /// Safety: The expression can't be checked for correctness, nothing prevents you from
+    /// causing a segmentation fault by evaluating an expression like
+    /// `unsafe_load(Ptr{Float64}(C_NULL))`.
+    #[inline]
+    pub unsafe fn eval<'target, Tgt>(
+        self,
+        target: Tgt,
+        expr: Value<'_, 'static>,
+    ) -> ValueResult<'target, 'static, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        Value::eval_expr(target, self, expr)
+    }
+
+    /// Parse and evaluate `code` in this module by calling `Base.include_string`, the result of
+    /// the last expression is returned. If an exception is thrown, including syntax errors, it's
+    /// caught and returned.
+    ///
+    /// Safety: The code can't be checked for correctness, nothing prevents you from causing a
+    /// segmentation fault with code like `unsafe_load(Ptr{Float64}(C_NULL))`.
+    pub unsafe fn eval_string<'target, C, Tgt>(
+        self,
+        target: Tgt,
+        code: C,
+    ) -> ValueResult<'target, 'static, Tgt>
+    where
+        C: AsRef<str>,
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 1>(|target, mut frame| {
+            let code = JuliaString::new(&mut frame, code);
+            Module::typed_global_cached::<Value, _, _>(&frame, "Base.include_string")
+                .unwrap()
+                .call2(target, self.as_value(), code.as_value())
+        })
+    }
+
+    /// Evaluate the contents of the file at `path` in this module by calling `Base.include`,
+    /// the result of the last expression is returned. If an exception is thrown it's caught and
+    /// returned.
+    ///
+    /// Returns an error if the file doesn't exist.
+    ///
+    /// Safety: The content of the file can't be checked for correctness, nothing prevents you
+    /// from causing a segmentation fault with code like `unsafe_load(Ptr{Float64}(C_NULL))`.
+    pub unsafe fn include<'target, P, Tgt>(
+        self,
+        target: Tgt,
+        path: P,
+    ) -> JlrsResult<ValueResult<'target, 'static, Tgt>>
+    where
+        P: AsRef<Path>,
+        Tgt: Target<'target>,
+    {
+        if !path.as_ref().exists() {
+            Err(IOError::NotFound {
+                path: path.as_ref().to_string_lossy().into(),
+            })?
+        }
+
+        let res = target.with_local_scope::<_, _, 1>(|target, mut frame| {
+            let path = JuliaString::new(&mut frame, path.as_ref().to_string_lossy());
+            Module::typed_global_cached::<Value, _, _>(&frame, "Base.include")
+                .unwrap()
+                .call2(target, self.as_value(), path.as_value())
+        });
+
+        Ok(res)
+    }
+
     /// Load a module by calling `Base.require` and return this module if it has been loaded
     /// successfully. This method can be used to load parts of the standard library like
     /// `LinearAlgebra`. This requires one slot on the GC stack. Note that the loaded module is
@@ -514,6 +993,316 @@ impl<'scope> Module<'scope> {
     }
 }
 
+impl<'scope> Module<'scope> {
+    fn collect_submodules<'target, Tgt>(
+        self,
+        target: &Tgt,
+        recursive: bool,
+        visited: &mut FxHashSet<NonNull<jl_module_t>>,
+        submodules: &mut Vec<ModuleRef<'scope>>,
+    ) -> JlrsResult<()>
+    where
+        Tgt: Target<'target>,
+    {
+        for name in self.names(target, true, false)? {
+            // Safety: the pointer points to valid data, the C API function is called with
+            // valid arguments and its result is checked. The submodule is reachable from this
+            // module.
+            unsafe {
+                let global = jl_get_global(self.unwrap(Private), name.unwrap(Private));
+                let Some(global) = NonNull::new(global) else {
+                    continue;
+                };
+
+                let global = Value::wrap_non_null(global, Private);
+                let Ok(submodule) = global.cast::<Module>() else {
+                    continue;
+                };
+
+                if submodule.parent() != self || !visited.insert(submodule.unwrap_non_null(Private))
+                {
+                    continue;
+                }
+
+                submodules.push(submodule.as_ref());
+                if recursive {
+                    submodule.collect_submodules(target, recursive, visited, submodules)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[julia_version(since = "1.9")]
+    fn binding_type<'target, Tgt>(
+        self,
+        target: &Tgt,
+        name: Symbol,
+    ) -> Option<ValueRef<'scope, 'static>>
+    where
+        Tgt: Target<'target>,
+    {
+        // Safety: Core.get_binding_type resolves the binding, the caller of Module::bindings is
+        // responsible for this side effect. The declared type is referenced by the binding.
+        unsafe {
+            let ty = Module::typed_global_cached::<Value, _, _>(target, "Core.get_binding_type")
+                .ok()?
+                .call2(target, self.as_value(), name.as_value())
+                .ok()?
+                .as_value();
+
+            if ty == DataType::any_type(target).as_value() {
+                return None;
+            }
+
+            Some(Value::wrap_non_null(ty.unwrap_non_null(Private), Private).as_ref())
+        }
+    }
+
+    #[julia_version(until = "1.8")]
+    fn binding_type<'target, Tgt>(
+        self,
+        _target: &Tgt,
+        _name: Symbol,
+    ) -> Option<ValueRef<'scope, 'static>>
+    where
+        Tgt: Target<'target>,
+    {
+        None
+    }
+
+    fn binding_owner<'target, Tgt>(self, target: &Tgt, name: Symbol) -> Option<ModuleRef<'scope>>
+    where
+        Tgt: Target<'target>,
+    {
+        // Safety: Base.which resolves the binding, the caller of Module::bindings is responsible
+        // for this side effect. The owner is reachable from this module.
+        unsafe {
+            let owner = Module::typed_global_cached::<Value, _, _>(target, "Base.which")
+                .ok()?
+                .call2(target, self.as_value(), name.as_value())
+                .ok()?
+                .as_value()
+                .cast::<Module>()
+                .ok()?;
+
+            Some(Module::wrap_non_null(owner.unwrap_non_null(Private), Private).as_ref())
+        }
+    }
+}
+
+/// Information about a binding in a module, returned by [`Module::bindings`].
+#[derive(Clone, Copy, Debug)]
+pub struct Binding<'scope> {
+    name: Symbol<'scope>,
+    is_const: bool,
+    is_exported: bool,
+    is_imported: bool,
+    binding_type: Option<ValueRef<'scope, 'static>>,
+    owner: Option<ModuleRef<'scope>>,
+}
+
+impl<'scope> Binding<'scope> {
+    /// The name of the binding.
+    #[inline]
+    pub fn name(&self) -> Symbol<'scope> {
+        self.name
+    }
+
+    /// Returns `true` if the binding is a constant.
+    #[inline]
+    pub fn is_const(&self) -> bool {
+        self.is_const
+    }
+
+    /// Returns `true` if the binding is exported by the module.
+    #[inline]
+    pub fn is_exported(&self) -> bool {
+        self.is_exported
+    }
+
+    /// Returns `true` if the binding has been explicitly imported from another module.
+    #[inline]
+    pub fn is_imported(&self) -> bool {
+        self.is_imported
+    }
+
+    /// The declared type of the binding, or `None` if the global is untyped.
+    ///
+    /// Globals can only be typed since Julia 1.9, `None` is always returned with older versions.
+    #[inline]
+    pub fn binding_type(&self) -> Option<ValueRef<'scope, 'static>> {
+        self.binding_type
+    }
+
+    /// The module that owns the binding, or `None` if it can't be resolved.
+    #[inline]
+    pub fn owner(&self) -> Option<ModuleRef<'scope>> {
+        self.owner
+    }
+}
+
+/// Memory orderings of atomic operations on globals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoryOrder {
+    /// `:unordered`
+    Unordered,
+    /// `:monotonic`
+    Monotonic,
+    /// `:acquire`
+    Acquire,
+    /// `:release`
+    Release,
+    /// `:acquire_release`
+    AcquireRelease,
+    /// `:sequentially_consistent`
+    SequentiallyConsistent,
+}
+
+impl MemoryOrder {
+    /// Returns the symbol Julia uses for this ordering.
+    pub fn as_symbol<'target, Tgt>(self, target: &Tgt) -> Symbol<'target>
+    where
+        Tgt: Target<'target>,
+    {
+        let name = match self {
+            MemoryOrder::Unordered => "unordered",
+            MemoryOrder::Monotonic => "monotonic",
+            MemoryOrder::Acquire => "acquire",
+            MemoryOrder::Release => "release",
+            MemoryOrder::AcquireRelease => "acquire_release",
+            MemoryOrder::SequentiallyConsistent => "sequentially_consistent",
+        };
+
+        Symbol::new(target, name)
+    }
+}
+
+type GlobalCallback = Arc<dyn Fn(Value<'_, 'static>) + Send + Sync>;
+
+struct WatchedGlobal {
+    module: GlobalRoot<Module<'static>>,
+    symbol: usize,
+    id: u64,
+    callback: GlobalCallback,
+}
+
+struct WatchedGlobals {
+    next_id: u64,
+    callbacks: Vec<WatchedGlobal>,
+}
+
+impl WatchedGlobals {
+    fn register(
+        &mut self,
+        module: GlobalRoot<Module<'static>>,
+        symbol: usize,
+        callback: GlobalCallback,
+    ) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.callbacks.push(WatchedGlobal {
+            module,
+            symbol,
+            id,
+            callback,
+        });
+        id
+    }
+}
+
+// This lock is never held while a safepoint can be reached, so it can be a normal lock. This
+// lets a `GlobalWatcher` be dropped on any thread.
+static WATCHED_GLOBALS: RwLock<WatchedGlobals> = const_rwlock(WatchedGlobals {
+    next_id: 0,
+    callbacks: Vec::new(),
+});
+
+// Called by the setters defined by `Module::watch_global`.
+unsafe extern "C" fn notify_global_set(
+    module: *mut jl_module_t,
+    name: *mut jl_sym_t,
+    value: *mut jl_value_t,
+) {
+    let callbacks = WATCHED_GLOBALS
+        .read()
+        .callbacks
+        .iter()
+        .filter(|w| w.module.ptr().as_ptr() == module.cast() && w.symbol == name as usize)
+        .map(|w| w.callback.clone())
+        .collect::<Vec<_>>();
+
+    let value = Value::wrap_non_null(NonNull::new_unchecked(value), Private);
+    let mut panic_msg = None;
+    for callback in callbacks {
+        if let Err(e) = catch_unwind(AssertUnwindSafe(|| callback(value))) {
+            panic_msg.get_or_insert_with(|| panic_message(e));
+        }
+    }
+
+    if let Some(msg) = panic_msg {
+        throw_watcher_panicked(msg)
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        String::from(*msg)
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        String::from("unknown panic payload")
+    }
+}
+
+// Throws a `JlrsCore.JlrsError`, nothing that must be dropped may be alive when this function is
+// called.
+#[cold]
+#[inline(never)]
+unsafe fn throw_watcher_panicked(msg: String) -> ! {
+    let unrooted = Unrooted::new();
+    let err = unrooted.local_scope::<_, 1>(|mut frame| {
+        let msg = format!("global watcher panicked: {msg}");
+        let msg = JuliaString::new(&mut frame, msg).as_value();
+        JlrsCore::jlrs_error(&frame)
+            .instantiate_unchecked(&frame, [msg])
+            .leak()
+    });
+
+    jl_throw(err.ptr().as_ptr())
+}
+
+/// A callback registered with [`Module::watch_global`].
+///
+/// The callback is unregistered when the watcher is dropped.
+#[derive(Debug)]
+pub struct GlobalWatcher {
+    id: u64,
+}
+
+impl GlobalWatcher {
+    /// Unregister the callback. The setter isn't removed.
+    #[inline]
+    pub fn unwatch(self) {}
+}
+
+impl Drop for GlobalWatcher {
+    fn drop(&mut self) {
+        // The module root is dropped after the lock has been released.
+        let removed = {
+            let mut watched = WATCHED_GLOBALS.write();
+            watched
+                .callbacks
+                .iter()
+                .position(|w| w.id == self.id)
+                .map(|idx| watched.callbacks.remove(idx))
+        };
+
+        std::mem::drop(removed);
+    }
+}
+
 impl_julia_typecheck!(Module<'target>, jl_module_type, 'target);
 impl_debug!(Module<'_>);
 
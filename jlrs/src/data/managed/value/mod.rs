@@ -148,7 +148,9 @@ use crate::{
     args::Values,
     call::{Call, ProvideKeywords, WithKeywords},
     catch::{catch_exceptions, unwrap_exc},
-    convert::{into_julia::IntoJulia, to_symbol::ToSymbol, unbox::Unbox},
+    convert::{
+        into_jlrs_result::IntoJlrsResult, into_julia::IntoJulia, to_symbol::ToSymbol, unbox::Unbox,
+    },
     data::{
         layout::{
             is_bits::IsBits,
@@ -157,6 +159,7 @@ use crate::{
         },
         managed::{
             datatype::DataType,
+            expr::Expr,
             module::Module,
             private::ManagedPriv,
             string::JuliaString,
@@ -168,10 +171,14 @@ use crate::{
         },
         types::{
             construct_type::ConstructType,
+            foreign_type::trait_object,
             typecheck::{NamedTuple, Typecheck},
         },
     },
-    error::{AccessError, IOError, JlrsError, JlrsResult, TypeError, CANNOT_DISPLAY_TYPE},
+    error::{
+        AccessError, IOError, JlrsError, JlrsResult, ParseError, TypeError, CANNOT_DISPLAY_TYPE,
+        CANNOT_DISPLAY_VALUE,
+    },
     memory::{
         context::ledger::Ledger,
         get_tls,
@@ -666,6 +673,38 @@ impl<'scope, 'data> Value<'scope, 'data> {
         Ok(TrackedMut::new(self))
     }
 
+    /// Track `self` immutably as the trait object type `T`.
+    ///
+    /// This method is equivalent to [`Value::track_shared`], except that the data is accessed
+    /// through the implementation of the trait `T` registered for the type of `self` with
+    /// [`register_trait_impl`]. An error is returned if no such implementation exists.
+    ///
+    /// [`register_trait_impl`]: crate::data::types::foreign_type::register_trait_impl
+    pub fn track_shared_dyn<'borrow, T: ?Sized + 'static>(
+        &'borrow self,
+    ) -> JlrsResult<Tracked<'borrow, 'scope, 'data, T>> {
+        // The value is borrowed first so an error is returned if it has been destroyed.
+        let mutable = self.datatype().mutable();
+        if mutable {
+            Ledger::try_borrow_shared(*self)?;
+        }
+
+        unsafe {
+            let Some(tracked) = trait_object::<T>(*self) else {
+                if mutable {
+                    Ledger::unborrow_shared(*self)?;
+                }
+
+                Err(TypeError::NotA {
+                    value: self.display_string_or(CANNOT_DISPLAY_VALUE),
+                    field_type: std::any::type_name::<T>().into(),
+                })?
+            };
+
+            Ok(Tracked::new_dyn(self, tracked.as_ref()))
+        }
+    }
+
     /// Returns `true` if `self` is currently tracked.
     #[inline]
     pub fn is_tracked(self) -> JlrsResult<bool> {
@@ -1282,6 +1321,127 @@ impl Value<'_, '_> {
         target.result_from_ptr(output, Private)
     }
 
+    /// Evaluate the expression `expr` in `module` by calling `Core.eval`. If an exception is
+    /// thrown it's caught and returned.
+    ///
+    /// The expression can be any value that can be evaluated, e.g. an [`Expr`] built with
+    /// [`Expr::new`], a `Symbol`, or a literal.
+    ///
+    /// Safety: The expression can't be checked for correctness, nothing prevents you from
+    /// causing a segmentation fault by evaluating an expression like
+    /// `unsafe_load(Ptr{Float64}(C_NULL))`.
+    ///
+    /// [`Expr`]: crate::data::managed::expr::Expr
+    /// [`Expr::new`]: crate::data::managed::expr::Expr::new
+    pub unsafe fn eval_expr<'target, Tgt>(
+        target: Tgt,
+        module: Module,
+        expr: Value<'_, 'static>,
+    ) -> ValueResult<'target, 'static, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        Module::typed_global_cached::<Value, _, _>(&target, "Core.eval")
+            .unwrap()
+            .call2(target, module.as_value(), expr)
+    }
+
+    /// Parse `code` as a single expression without evaluating it by calling `Meta.parse`.
+    ///
+    /// The result is usually an [`Expr`], but can also be a `Symbol` or a literal. If the code
+    /// can't be parsed or is incomplete, a `JlrsError::ParseError` is returned. Other
+    /// exceptions are returned as `JlrsError::Exception`.
+    ///
+    /// [`Expr`]: crate::data::managed::expr::Expr
+    pub fn parse<'target, C, Tgt>(
+        target: Tgt,
+        code: C,
+    ) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+    where
+        C: AsRef<str>,
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 2>(|target, mut frame| {
+            let code = JuliaString::new(&mut frame, code);
+
+            // Safety: Meta.parse only parses the code, nothing is evaluated.
+            let res = unsafe {
+                Module::typed_global_cached::<Value, _, _>(&frame, "Base.Meta.parse")?
+                    .call1(&mut frame, code.as_value())
+            };
+
+            match res {
+                Ok(parsed) => {
+                    check_parsed(parsed, None)?;
+                    Ok(parsed.root(target))
+                }
+                Err(e) if e.datatype_name() == "ParseError" => {
+                    Err(ParseError::from_value(e, None))?
+                }
+                Err(e) => Err(JlrsError::from_exception(e))?,
+            }
+        })
+    }
+
+    /// Parse all expressions in `code` without evaluating them by calling `Meta.parseall`.
+    ///
+    /// The result is an [`Expr`] with the head `:toplevel`, its arguments are the parsed
+    /// expressions preceded by `LineNumberNode`s. `filename` is used as the file of these
+    /// `LineNumberNode`s, it defaults to `"none"`. If the code can't be parsed or is incomplete,
+    /// a `JlrsError::ParseError` is returned. Other exceptions are returned as
+    /// `JlrsError::Exception`.
+    ///
+    /// [`Expr`]: crate::data::managed::expr::Expr
+    pub fn parse_all<'target, C, Tgt>(
+        target: Tgt,
+        code: C,
+        filename: Option<&str>,
+    ) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+    where
+        C: AsRef<str>,
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 4>(|target, mut frame| {
+            let code = JuliaString::new(&mut frame, code);
+            let filename = JuliaString::new(&mut frame, filename.unwrap_or("none")).as_value();
+            let kws = named_tuple!(&mut frame, "filename" => filename);
+
+            // Safety: Meta.parseall only parses the code, nothing is evaluated.
+            let parsed = unsafe {
+                Module::typed_global_cached::<Value, _, _>(&frame, "Base.Meta.parseall")?
+                    .provide_keywords(kws)?
+                    .call1(&mut frame, code.as_value())
+                    .into_jlrs_result()?
+            };
+
+            check_parsed(parsed, None)?;
+
+            if let Ok(expr) = parsed.cast::<Expr>() {
+                let mut line = None;
+                for i in 0..expr.n_args() {
+                    let Some(arg) = expr.arg(&frame, i) else {
+                        continue;
+                    };
+
+                    // Safety: the argument is referenced by the rooted expression.
+                    let arg = unsafe { arg.as_value() };
+                    if arg.datatype_name() == "LineNumberNode" {
+                        line = arg
+                            .field_accessor()
+                            .field("line")
+                            .and_then(|f| f.access::<isize>())
+                            .ok()
+                            .map(|l| l as usize);
+                    } else {
+                        check_parsed(arg, line)?;
+                    }
+                }
+            }
+
+            Ok(parsed.root(target))
+        })
+    }
+
     /// Calls `include` in the `Main` module in Julia, which evaluates the file's contents in that
     /// module. This has the same effect as calling `include` in the Julia REPL.
     ///
@@ -1312,6 +1472,29 @@ impl Value<'_, '_> {
     }
 }
 
+// Returns an error if `parsed` is an `:error` or `:incomplete` expression.
+fn check_parsed(parsed: Value, line: Option<usize>) -> JlrsResult<()> {
+    let Ok(expr) = parsed.cast::<Expr>() else {
+        return Ok(());
+    };
+
+    let Some(head) = expr.head() else {
+        return Ok(());
+    };
+
+    match head.as_str() {
+        Ok("error") | Ok("incomplete") => {
+            // Safety: the argument is referenced by the expression.
+            let error = unsafe { expr.arg(Unrooted::new(), 0) };
+            match error {
+                Some(error) => Err(ParseError::from_value(unsafe { error.as_value() }, line))?,
+                None => Err(ParseError::from_value(head.as_value(), line))?,
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
 /// # Equality
 impl Value<'_, '_> {
     /// Returns the object id of this value.
@@ -23,7 +23,7 @@ use crate::{
 // TODO: Clone
 /// Immutable tracked data.
 #[repr(transparent)]
-pub struct Tracked<'tracked, 'scope, 'data, T> {
+pub struct Tracked<'tracked, 'scope, 'data, T: ?Sized> {
     tracked: &'tracked T,
     _s: PhantomData<&'scope ()>,
     _d: PhantomData<&'data ()>,
@@ -40,6 +40,21 @@ impl<'tracked, 'scope, 'data, T: ValidLayout> Tracked<'tracked, 'scope, 'data, T
     }
 }
 
+impl<'tracked, 'scope, 'data, T: ?Sized> Tracked<'tracked, 'scope, 'data, T> {
+    // Safety: `tracked` must point to the data of `value`, which must have been tracked.
+    #[inline]
+    pub(crate) unsafe fn new_dyn(
+        _value: &'tracked Value<'scope, 'data>,
+        tracked: &'tracked T,
+    ) -> Self {
+        Tracked {
+            tracked,
+            _s: PhantomData,
+            _d: PhantomData,
+        }
+    }
+}
+
 impl<'scope, 'data, T: ValidLayout> Tracked<'scope, 'scope, 'data, T> {
     #[inline]
     pub(crate) unsafe fn new_owned(value: Value<'scope, 'data>) -> Self {
@@ -51,7 +66,7 @@ impl<'scope, 'data, T: ValidLayout> Tracked<'scope, 'scope, 'data, T> {
     }
 }
 
-impl<'tracked, 'scope, 'data, T: ValidLayout> Deref for Tracked<'tracked, 'scope, 'data, T> {
+impl<'tracked, 'scope, 'data, T: ?Sized> Deref for Tracked<'tracked, 'scope, 'data, T> {
     type Target = T;
 
     #[inline]
@@ -65,7 +80,7 @@ unsafe impl<'tracked, 'scope, 'data, T: ValidLayout + Send> Send
 {
 }
 
-impl<T> Drop for Tracked<'_, '_, '_, T> {
+impl<T: ?Sized> Drop for Tracked<'_, '_, '_, T> {
     fn drop(&mut self) {
         unsafe {
             let v = Value::wrap_non_null(
@@ -0,0 +1,427 @@
+//! Add methods to functions from `Base` that are backed by Rust trait implementations.
+//!
+//! By default, an opaque type that has been exported with the [`julia_module`] macro is shown
+//! as an opaque struct and instances are only equal if they're identical. If the Rust type
+//! implements `Display` or `Debug`, `PartialEq`, `Hash`, or `Clone`, methods for `Base.show`,
+//! `Base.:(==)`, `Base.hash`, and `Base.copy` and `Base.deepcopy_internal` can be added by
+//! annotating the exported type:
+//!
+//! ```ignore
+//! #[derive(Clone, Debug, PartialEq, Hash)]
+//! struct Point {
+//!     x: i64,
+//!     y: i64,
+//! }
+//!
+//! unsafe impl OpaqueType for Point {}
+//!
+//! julia_module! {
+//!     become point_init_fn;
+//!
+//!     #[show(Debug)]
+//!     #[eq]
+//!     #[hash]
+//!     #[copy]
+//!     struct Point as RustPoint;
+//! }
+//! ```
+//!
+//! `#[show]` uses the implementation of `Display`, `#[show(Debug)]` the implementation of
+//! `Debug`. `#[copy]` adds methods for both `Base.copy` and `Base.deepcopy_internal`. These
+//! attributes are only supported by types that are not generic.
+//!
+//! The `#[close]` attribute adds methods to `Base.close` and `Base.isopen`. Closing an instance
+//! drops its Rust data immediately with [`destroy`] instead of waiting for the GC to finalize it.
+//! Calling an exported method with a closed instance as `self`, or one of the methods added by
+//! the other attributes, throws a `JlrsCore.JlrsError`. Closing an instance more than once is
+//! allowed, `Base.finalize` must not be called on a closed instance.
+//!
+//! [`destroy`]: super::foreign_type::destroy
+//!
+//! [`julia_module`]: ::jlrs_macros::julia_module
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    ffi::c_void,
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+};
+
+use super::{
+    construct_type::ConstructType,
+    foreign_type::{destroy, is_destroyed, throw_if_destroyed, OpaqueType},
+};
+use crate::{
+    call::Call,
+    convert::ccall_types::CCallReturn,
+    data::{
+        layout::bool::Bool,
+        managed::{
+            array::{
+                data::accessor::{Accessor as _, AccessorMut as _, AccessorMut1D as _},
+                dimensions::Dims as _,
+                Vector,
+            },
+            datatype::DataType,
+            module::Module,
+            simple_vector::SimpleVector,
+            string::JuliaString,
+            symbol::Symbol,
+            union_all::UnionAll,
+            value::{Value, ValueRet},
+            Managed,
+        },
+    },
+    error::JlrsResult,
+    memory::{
+        scope::LocalScope,
+        target::{unrooted::Unrooted, Target},
+    },
+};
+
+/// Add a method to `Base.show` that shows `T` with its implementation of `Display`.
+///
+/// Safety: must only be called by the init function generated by `julia_module`.
+#[doc(hidden)]
+pub unsafe fn add_show_display<'target, T, Tgt>(
+    target: &Tgt,
+    functions: &mut Vector<'_, 'static>,
+    function_info_ty: DataType,
+) where
+    T: OpaqueType + Display,
+    Tgt: Target<'target>,
+{
+    add_show::<T, Tgt>(
+        target,
+        functions,
+        function_info_ty,
+        show_display::<T> as *mut c_void,
+    )
+}
+
+/// Add a method to `Base.show` that shows `T` with its implementation of `Debug`.
+///
+/// Safety: must only be called by the init function generated by `julia_module`.
+#[doc(hidden)]
+pub unsafe fn add_show_debug<'target, T, Tgt>(
+    target: &Tgt,
+    functions: &mut Vector<'_, 'static>,
+    function_info_ty: DataType,
+) where
+    T: OpaqueType + Debug,
+    Tgt: Target<'target>,
+{
+    add_show::<T, Tgt>(
+        target,
+        functions,
+        function_info_ty,
+        show_debug::<T> as *mut c_void,
+    )
+}
+
+/// Add a method to `Base.:(==)` that compares two instances of `T` with its implementation of
+/// `PartialEq`.
+///
+/// Safety: must only be called by the init function generated by `julia_module`.
+#[doc(hidden)]
+pub unsafe fn add_eq<'target, T, Tgt>(
+    target: &Tgt,
+    functions: &mut Vector<'_, 'static>,
+    function_info_ty: DataType,
+) where
+    T: OpaqueType + PartialEq,
+    Tgt: Target<'target>,
+{
+    target.local_scope::<_, 1>(|mut frame| {
+        let base = Module::base(&frame);
+        let any = DataType::any_type(&frame).as_value();
+        let bool_ty = DataType::bool_type(&frame).as_value();
+        let ty = T::construct_type(&mut frame);
+
+        add_method(
+            &frame,
+            functions,
+            function_info_ty,
+            base,
+            "==",
+            eq::<T> as *mut c_void,
+            &[(any, ty), (any, ty)],
+            (bool_ty, bool_ty),
+        );
+    })
+}
+
+/// Add a method to `Base.hash` that hashes `T` with its implementation of `Hash`.
+///
+/// Safety: must only be called by the init function generated by `julia_module`.
+#[doc(hidden)]
+pub unsafe fn add_hash<'target, T, Tgt>(
+    target: &Tgt,
+    functions: &mut Vector<'_, 'static>,
+    function_info_ty: DataType,
+) where
+    T: OpaqueType + Hash,
+    Tgt: Target<'target>,
+{
+    target.local_scope::<_, 2>(|mut frame| {
+        let base = Module::base(&frame);
+        let any = DataType::any_type(&frame).as_value();
+        let uint = usize::construct_type(&mut frame);
+        let ty = T::construct_type(&mut frame);
+
+        add_method(
+            &frame,
+            functions,
+            function_info_ty,
+            base,
+            "hash",
+            hash::<T> as *mut c_void,
+            &[(any, ty), (uint, uint)],
+            (uint, uint),
+        );
+    })
+}
+
+/// Add methods to `Base.copy` and `Base.deepcopy_internal` that copy `T` with its
+/// implementation of `Clone`.
+///
+/// Safety: must only be called by the init function generated by `julia_module`.
+#[doc(hidden)]
+pub unsafe fn add_copy<'target, T, Tgt>(
+    target: &Tgt,
+    functions: &mut Vector<'_, 'static>,
+    function_info_ty: DataType,
+) where
+    T: OpaqueType + Clone,
+    Tgt: Target<'target>,
+{
+    target.local_scope::<_, 1>(|mut frame| {
+        let base = Module::base(&frame);
+        let any = DataType::any_type(&frame).as_value();
+        let ty = T::construct_type(&mut frame);
+
+        add_method(
+            &frame,
+            functions,
+            function_info_ty,
+            base,
+            "copy",
+            copy::<T> as *mut c_void,
+            &[(any, ty)],
+            (any, ty),
+        );
+
+        add_method(
+            &frame,
+            functions,
+            function_info_ty,
+            base,
+            "deepcopy_internal",
+            deepcopy_internal::<T> as *mut c_void,
+            &[(any, ty), (any, any)],
+            (any, ty),
+        );
+    })
+}
+
+/// Add methods to `Base.close` and `Base.isopen` that destroy `T` and check if it has been
+/// destroyed.
+///
+/// Safety: must only be called by the init function generated by `julia_module`.
+#[doc(hidden)]
+pub unsafe fn add_close<'target, T, Tgt>(
+    target: &Tgt,
+    functions: &mut Vector<'_, 'static>,
+    function_info_ty: DataType,
+) where
+    T: OpaqueType,
+    Tgt: Target<'target>,
+{
+    target.local_scope::<_, 1>(|mut frame| {
+        let base = Module::base(&frame);
+        let any = DataType::any_type(&frame).as_value();
+        let bool_ty = DataType::bool_type(&frame).as_value();
+        let ty = T::construct_type(&mut frame);
+
+        add_method(
+            &frame,
+            functions,
+            function_info_ty,
+            base,
+            "close",
+            close::<T> as *mut c_void,
+            &[(any, ty)],
+            (any, any),
+        );
+
+        add_method(
+            &frame,
+            functions,
+            function_info_ty,
+            base,
+            "isopen",
+            isopen as *mut c_void,
+            &[(any, ty)],
+            (bool_ty, bool_ty),
+        );
+    })
+}
+
+// Adds a method to the functions exported by the init function generated by `julia_module`.
+// Argument and return types are provided as (ccall type, julia type).
+#[allow(clippy::too_many_arguments)]
+pub(crate) unsafe fn add_method<'target, Tgt>(
+    target: &Tgt,
+    functions: &mut Vector<'_, 'static>,
+    function_info_ty: DataType,
+    module: Module,
+    name: &str,
+    func: *mut c_void,
+    arg_types: &[(Value, Value)],
+    ret_type: (Value, Value),
+) where
+    Tgt: Target<'target>,
+{
+    target.local_scope::<_, 4>(|mut frame| {
+        let name = Symbol::new(&frame, name);
+        let n_args = arg_types.len();
+
+        let type_type = UnionAll::type_type(&frame).as_value();
+        let mut ccall_arg_types = Vector::new_for_unchecked(&mut frame, type_type, n_args);
+        let any_type = DataType::any_type(&frame).as_value();
+        let mut julia_arg_types = Vector::new_for_unchecked(&mut frame, any_type, n_args);
+
+        {
+            let mut ccall_arg_types = ccall_arg_types.indeterminate_data_mut();
+            let mut julia_arg_types = julia_arg_types.indeterminate_data_mut();
+            for (idx, (ccall_ty, julia_ty)) in arg_types.iter().copied().enumerate() {
+                ccall_arg_types.set_value_unchecked(idx, ccall_ty);
+                julia_arg_types.set_value_unchecked(idx, julia_ty);
+            }
+        }
+
+        let func = Value::new(&mut frame, func);
+        let env = SimpleVector::emptysvec(&frame);
+
+        let instance = function_info_ty.instantiate_unchecked(
+            &mut frame,
+            [
+                name.as_value(),
+                ccall_arg_types.as_value(),
+                julia_arg_types.as_value(),
+                ret_type.0,
+                ret_type.1,
+                func,
+                module.as_value(),
+                env.as_value(),
+            ],
+        );
+
+        let mut accessor = functions.indeterminate_data_mut();
+        accessor.grow_end_unchecked(1);
+        let n = accessor.array().dimensions().size();
+        accessor.set_value_unchecked(n - 1, instance);
+    })
+}
+
+unsafe fn add_show<'target, T, Tgt>(
+    target: &Tgt,
+    functions: &mut Vector<'_, 'static>,
+    function_info_ty: DataType,
+    show: *mut c_void,
+) where
+    T: OpaqueType,
+    Tgt: Target<'target>,
+{
+    target.local_scope::<_, 1>(|mut frame| {
+        let base = Module::base(&frame);
+        let any = DataType::any_type(&frame).as_value();
+        let io = Module::typed_global_cached::<Value, _, _>(&frame, "Base.IO")
+            .expect("Base.IO not found");
+        let ty = T::construct_type(&mut frame);
+
+        add_method(
+            &frame,
+            functions,
+            function_info_ty,
+            base,
+            "show",
+            show,
+            &[(any, io), (any, ty)],
+            (any, any),
+        );
+    })
+}
+
+// Calls `Base.print(io, s)`, exceptions are rethrown.
+unsafe fn print(io: Value<'static, 'static>, s: String) -> ValueRet {
+    let unrooted = Unrooted::new();
+    let res = unrooted.local_scope::<_, 1>(|mut frame| {
+        let s = JuliaString::new(&mut frame, s).as_value();
+        Module::typed_global_cached::<Value, _, _>(&unrooted, "Base.print")
+            .expect("Base.print not found")
+            .call2(unrooted, io, s)
+    });
+
+    res.return_or_throw()
+}
+
+unsafe extern "C" fn show_display<T: OpaqueType + Display>(
+    io: Value<'static, 'static>,
+    value: Value<'static, 'static>,
+) -> ValueRet {
+    throw_if_destroyed(value);
+    let s = value.data_ptr().cast::<T>().as_ref().to_string();
+    print(io, s)
+}
+
+unsafe extern "C" fn show_debug<T: OpaqueType + Debug>(
+    io: Value<'static, 'static>,
+    value: Value<'static, 'static>,
+) -> ValueRet {
+    throw_if_destroyed(value);
+    let s = format!("{:?}", value.data_ptr().cast::<T>().as_ref());
+    print(io, s)
+}
+
+unsafe extern "C" fn eq<T: OpaqueType + PartialEq>(
+    a: Value<'static, 'static>,
+    b: Value<'static, 'static>,
+) -> Bool {
+    throw_if_destroyed(a);
+    throw_if_destroyed(b);
+    let a = a.data_ptr().cast::<T>().as_ref();
+    let b = b.data_ptr().cast::<T>().as_ref();
+    Bool::new(a == b)
+}
+
+unsafe extern "C" fn hash<T: OpaqueType + Hash>(value: Value<'static, 'static>, h: usize) -> usize {
+    throw_if_destroyed(value);
+    let value = value.data_ptr().cast::<T>().as_ref();
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    h.hash(&mut hasher);
+    hasher.finish() as usize
+}
+
+unsafe extern "C" fn copy<T: OpaqueType + Clone>(value: Value<'static, 'static>) -> ValueRet {
+    throw_if_destroyed(value);
+    let value = value.data_ptr().cast::<T>().as_ref().clone();
+    Value::new(Unrooted::new(), value).leak()
+}
+
+unsafe extern "C" fn deepcopy_internal<T: OpaqueType + Clone>(
+    value: Value<'static, 'static>,
+    _dict: Value<'static, 'static>,
+) -> ValueRet {
+    copy::<T>(value)
+}
+
+unsafe extern "C" fn close<T: OpaqueType>(value: Value<'static, 'static>) -> ValueRet {
+    let res: JlrsResult<_> = destroy::<T>(value).map(|_| Value::nothing(&Unrooted::new()).leak());
+    res.return_or_throw()
+}
+
+unsafe extern "C" fn isopen(value: Value<'static, 'static>) -> Bool {
+    Bool::new(!is_destroyed(value))
+}
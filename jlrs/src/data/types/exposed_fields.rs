@@ -0,0 +1,247 @@
+//! Access fields of opaque and foreign types from Julia.
+//!
+//! Opaque and foreign types have no fields in Julia, so normally a method has to be exported for
+//! every field that should be accessible. Instead, fields can be exposed by deriving
+//! [`ExposedFields`] and annotating them with `#[jlrs(expose)]`. If such a type is exported with
+//! the [`julia_module`] macro and annotated with `#[properties]`, methods are added to
+//! `Base.getproperty`, `Base.setproperty!` and `Base.propertynames`:
+//!
+//! ```ignore
+//! #[derive(ExposedFields)]
+//! struct Particle {
+//!     #[jlrs(expose)]
+//!     mass: f64,
+//!     #[jlrs(expose)]
+//!     charge: i32,
+//!     history: Vec<f64>,
+//! }
+//!
+//! unsafe impl OpaqueType for Particle {}
+//!
+//! julia_module! {
+//!     become particle_init_fn;
+//!
+//!     #[properties]
+//!     struct Particle;
+//! }
+//! ```
+//!
+//! In Julia, `particle.mass` returns a copy of the field converted with `IntoJulia`, and
+//! `particle.mass = 2` converts the new value to the type of the field with `Base.convert`,
+//! unboxes it and replaces the field. The type of an exposed
+//! field must implement `IntoJulia`, `Clone`, `Typecheck` and `Unbox` with itself as `Output`.
+//!
+//! Like exported methods that take `self`, the instance is tracked while a field is accessed. If
+//! it's already tracked incompatibly, a `JlrsCore.BorrowError` is thrown. Other errors, e.g. if
+//! the new value has the wrong type or the field isn't exposed, are thrown as a
+//! `JlrsCore.JlrsError`.
+//!
+//! [`julia_module`]: ::jlrs_macros::julia_module
+
+use std::ffi::c_void;
+
+use jl_sys::jl_throw;
+
+use super::{
+    base_methods::add_method,
+    construct_type::ConstructType,
+    foreign_type::{throw_if_destroyed, OpaqueType},
+};
+use crate::{
+    call::Call,
+    convert::ccall_types::CCallReturn,
+    data::managed::{
+        array::Vector,
+        datatype::{DataType, DataTypeData},
+        module::{JlrsCore, Module},
+        private::ManagedPriv as _,
+        symbol::Symbol,
+        value::{Value, ValueData, ValueRet},
+        Managed,
+    },
+    error::{AccessError, JlrsResult, CANNOT_DISPLAY_TYPE},
+    memory::{
+        scope::LocalScope,
+        target::{unrooted::Unrooted, Target},
+    },
+    private::Private,
+};
+
+/// Fields of an opaque or foreign type that can be accessed from Julia.
+///
+/// This trait should be derived, see the [module-level docs] for more information.
+///
+/// [module-level docs]: self
+pub trait ExposedFields: OpaqueType {
+    /// The names of the exposed fields.
+    const FIELD_NAMES: &'static [&'static str];
+
+    /// Convert a copy of the field at index `idx` of [`ExposedFields::FIELD_NAMES`] to Julia
+    /// data.
+    ///
+    /// Panics if `idx` is out of bounds.
+    fn get_field<'target, Tgt>(&self, target: Tgt, idx: usize) -> ValueData<'target, 'static, Tgt>
+    where
+        Tgt: Target<'target>;
+
+    /// Unbox `value` and replace the field at index `idx` of [`ExposedFields::FIELD_NAMES`].
+    ///
+    /// Returns an error if `value` has the wrong type. Panics if `idx` is out of bounds.
+    fn set_field(&mut self, idx: usize, value: Value) -> JlrsResult<()>;
+
+    /// Returns the Julia type of the field at index `idx` of [`ExposedFields::FIELD_NAMES`].
+    ///
+    /// Panics if `idx` is out of bounds.
+    fn field_type<'target, Tgt>(target: Tgt, idx: usize) -> DataTypeData<'target, Tgt>
+    where
+        Tgt: Target<'target>;
+
+    /// Returns the index of the exposed field named `name`.
+    #[inline]
+    fn field_index(name: &str) -> Option<usize> {
+        Self::FIELD_NAMES.iter().position(|n| *n == name)
+    }
+}
+
+/// Add methods to `Base.getproperty`, `Base.setproperty!` and `Base.propertynames` for `T`.
+///
+/// Safety: must only be called by the init function generated by `julia_module`.
+#[doc(hidden)]
+pub unsafe fn add_property_methods<'target, T, Tgt>(
+    target: &Tgt,
+    functions: &mut Vector<'_, 'static>,
+    function_info_ty: DataType,
+) where
+    T: ExposedFields,
+    Tgt: Target<'target>,
+{
+    target.local_scope::<_, 1>(|mut frame| {
+        let base = Module::base(&frame);
+        let any = DataType::any_type(&frame).as_value();
+        let symbol = DataType::symbol_type(&frame).as_value();
+        let ty = T::construct_type(&mut frame);
+
+        add_method(
+            &frame,
+            functions,
+            function_info_ty,
+            base,
+            "getproperty",
+            getproperty::<T> as *mut c_void,
+            &[(any, ty), (any, symbol)],
+            (any, any),
+        );
+
+        add_method(
+            &frame,
+            functions,
+            function_info_ty,
+            base,
+            "setproperty!",
+            setproperty::<T> as *mut c_void,
+            &[(any, ty), (any, symbol), (any, any)],
+            (any, any),
+        );
+
+        add_method(
+            &frame,
+            functions,
+            function_info_ty,
+            base,
+            "propertynames",
+            propertynames::<T> as *mut c_void,
+            &[(any, ty)],
+            (any, any),
+        );
+    })
+}
+
+unsafe extern "C" fn getproperty<T: ExposedFields>(
+    value: Value<'static, 'static>,
+    name: Value<'static, 'static>,
+) -> ValueRet {
+    throw_if_destroyed(value);
+    let res = field_index::<T>(value, name).map(|idx| {
+        let Ok(tracked) = value.track_shared::<T>() else {
+            throw_borrow_exception()
+        };
+
+        tracked.get_field(Unrooted::new(), idx).leak()
+    });
+
+    res.return_or_throw()
+}
+
+unsafe extern "C" fn setproperty<T: ExposedFields>(
+    mut value: Value<'static, 'static>,
+    name: Value<'static, 'static>,
+    new_value: Value<'static, 'static>,
+) -> ValueRet {
+    throw_if_destroyed(value);
+    let idx = match field_index::<T>(value, name) {
+        Ok(idx) => idx,
+        Err(e) => return Err::<ValueRet, _>(e).return_or_throw(),
+    };
+
+    // Like `Base.setproperty!`, the new value is converted to the type of the field first. The
+    // converted value isn't rooted, nothing is allocated before it's returned.
+    let converted = Unrooted::new().local_scope::<_, 1>(|mut frame| {
+        let field_type = T::field_type(&mut frame, idx).as_value();
+        Module::base(&frame)
+            .global(&frame, "convert")
+            .expect("Base.convert not found")
+            .as_value()
+            .call(Unrooted::new(), [field_type, new_value])
+    });
+
+    let converted = match converted {
+        Ok(converted) => converted.as_value(),
+        Err(exc) => jl_throw(exc.ptr().as_ptr()),
+    };
+
+    let Ok(mut tracked) = value.track_exclusive::<T>() else {
+        throw_borrow_exception()
+    };
+
+    tracked
+        .set_field(idx, converted)
+        .map(|_| converted.leak())
+        .return_or_throw()
+}
+
+unsafe extern "C" fn propertynames<T: ExposedFields>(value: Value<'static, 'static>) -> ValueRet {
+    throw_if_destroyed(value);
+    let unrooted = Unrooted::new();
+
+    // Symbols are never freed by the GC, so they don't have to be rooted.
+    let mut names = T::FIELD_NAMES
+        .iter()
+        .map(|name| Symbol::new(&unrooted, name).as_value())
+        .collect::<std::vec::Vec<_>>();
+
+    Module::core(&unrooted)
+        .global(&unrooted, "tuple")
+        .expect("Core.tuple not found")
+        .as_value()
+        .call(unrooted, names.as_mut_slice())
+        .return_or_throw()
+}
+
+unsafe fn field_index<T: ExposedFields>(value: Value, name: Value) -> JlrsResult<usize> {
+    let name = name.cast_unchecked::<Symbol>().as_str()?;
+    match T::field_index(name) {
+        Some(idx) => Ok(idx),
+        None => Err(AccessError::NoSuchField {
+            type_name: value.datatype().display_string_or(CANNOT_DISPLAY_TYPE),
+            field_name: name.into(),
+        })?,
+    }
+}
+
+#[cold]
+#[inline(never)]
+unsafe fn throw_borrow_exception() -> ! {
+    let unrooted = Unrooted::new();
+    let err = JlrsCore::borrow_error(&unrooted).instance().unwrap();
+    jl_throw(err.unwrap(Private))
+}
@@ -49,12 +49,56 @@
 //! `julia_module` automatically takes care of this, otherwise you must manually call
 //! `OpaqueType::create_type` or `OpaqueType::reinit_type`. The first must be called if the
 //! type doesn't exist yet, the second if the module that defines the type has been precompiled.
+//!
+//! A Rust trait can be exported as an abstract Julia type. Exported types that implement the
+//! trait can use this abstract type as their supertype, and methods of the trait that take
+//! `&self` can be exported once for all implementors:
+//!
+//! ```ignore
+//! pub trait Shape {
+//!     fn area(&self) -> f64;
+//! }
+//!
+//! pub struct Circle {
+//!     radius: f64,
+//! }
+//!
+//! impl Shape for Circle {
+//!     fn area(&self) -> f64 {
+//!         std::f64::consts::PI * self.radius * self.radius
+//!     }
+//! }
+//!
+//! unsafe impl OpaqueType for Circle {}
+//!
+//! julia_module! {
+//!     become module_jl_init;
+//!
+//!     trait Shape;
+//!     struct Circle: Shape;
+//!     in dyn Shape fn area(&self) -> f64;
+//! }
+//! ```
+//!
+//! Here `Circle <: Shape` in Julia, and a single method `area(::Shape)` is generated. When it's
+//! called, the Rust implementation of the trait is looked up by the type of the argument. These
+//! abstract types are managed with [`create_trait_type`], [`reinit_trait_type`] and
+//! [`register_trait_impl`], which are called automatically by the init function generated by
+//! `julia_module`.
+//!
+//! Memory owned by an opaque or foreign type is invisible to the GC. If such a type owns large
+//! buffers, they can be allocated with [`counted_malloc`] so the GC takes them into account when
+//! deciding if it should collect.
+//!
+//! [`counted_malloc`]: crate::memory::gc::counted_malloc
 use std::{
     any::{Any, TypeId},
+    collections::BTreeSet,
     ffi::c_void,
     marker::PhantomData,
     mem::MaybeUninit,
     ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
 use fnv::FnvHashMap;
@@ -63,9 +107,11 @@ use jl_sys::jl_gc_schedule_foreign_sweepfunc;
 #[julia_version(since = "1.9")]
 use jl_sys::jl_reinit_foreign_type;
 use jl_sys::{
-    jl_any_type, jl_emptysvec, jl_gc_alloc_typed, jl_new_datatype, jl_new_foreign_type, jl_value_t,
+    jl_any_type, jl_datatype_t, jl_emptysvec, jl_gc_alloc_typed, jl_new_datatype,
+    jl_new_foreign_type, jl_throw, jl_type_error, jl_value_t,
 };
 use jlrs_macros::julia_version;
+use parking_lot::{const_mutex, Mutex};
 
 use super::{
     construct_type::{TypeVarConstructor, TypeVarName},
@@ -76,32 +122,51 @@ use crate::{
     data::{
         layout::valid_layout::ValidLayout,
         managed::{
+            array::Vector,
             datatype::{DataType, DataTypeData},
             erase_scope_lifetime,
-            module::Module,
+            module::{JlrsCore, Module},
             private::ManagedPriv,
             simple_vector::{SimpleVector, SimpleVectorData},
+            string::JuliaString,
             symbol::Symbol,
             value::{Value, ValueData, ValueRef},
-            Managed,
+            Managed, Ref,
         },
         types::construct_type::ConstructType,
     },
+    error::{JlrsResult, CANNOT_DISPLAY_TYPE},
     gc_safe::{GcSafeOnceLock, GcSafeRwLock},
-    memory::{get_tls, target::Target, PTls},
+    memory::{
+        gc::mark_queue_obj,
+        get_tls,
+        scope::LocalScope,
+        target::{unrooted::Unrooted, Target},
+        PTls,
+    },
     private::Private,
 };
 
 static FOREIGN_TYPE_REGISTRY: GcSafeOnceLock<ForeignTypes> = GcSafeOnceLock::new();
 
+// The addresses of instances that have been destroyed but haven't been freed by the GC yet. This
+// lock is never held while Julia data is allocated, so it's safe to lock it during GC.
+static DESTROYED: Mutex<BTreeSet<usize>> = const_mutex(BTreeSet::new());
+static N_DESTROYED: AtomicUsize = AtomicUsize::new(0);
+
 pub(crate) unsafe fn init_foreign_type_registry() {
     FOREIGN_TYPE_REGISTRY.set(ForeignTypes::new()).ok();
 }
 
-struct Key<K>(PhantomData<K>);
+struct Key<K: ?Sized>(PhantomData<K>);
+
+// Casts from the data of an implementor to a trait object, keyed by the `TypeId` of the trait
+// object and the address of the implementor's `DataType`.
+type TraitImpls = FnvHashMap<(TypeId, usize), Box<dyn Any + Send + Sync>>;
 
 struct ForeignTypes {
     data: GcSafeRwLock<FnvHashMap<TypeId, DataType<'static>>>,
+    trait_impls: GcSafeRwLock<TraitImpls>,
 }
 
 impl ForeignTypes {
@@ -109,6 +174,7 @@ impl ForeignTypes {
     fn new() -> Self {
         ForeignTypes {
             data: GcSafeRwLock::default(),
+            trait_impls: GcSafeRwLock::default(),
         }
     }
 
@@ -187,6 +253,41 @@ pub unsafe trait OpaqueType: Sized + Send + 'static {
     unsafe fn reinit_type(datatype: DataType) -> bool {
         reinit_opaque_type::<Self>(datatype)
     }
+
+    #[doc(hidden)]
+    #[inline]
+    unsafe fn create_subtype<'target, Tgt>(
+        target: Tgt,
+        name: Symbol,
+        module: Module,
+        super_type: DataType,
+    ) -> DataTypeData<'target, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        create_opaque_type_with_super::<Self, Tgt>(target, name, module, Some(super_type))
+    }
+
+    /// Called right before the data of an instance is dropped.
+    ///
+    /// The data is dropped when an instance is finalized by the GC or when it's destroyed
+    /// explicitly with [`destroy`]. If it's safe to call into Julia, `target` is `Some`; this is
+    /// the case unless the data is dropped during the sweep phase of the GC. Like finalizers
+    /// defined in Julia, this method must not switch tasks or wait for other tasks. It does
+    /// nothing by default.
+    #[inline]
+    fn finalize(&mut self, _target: Option<Unrooted<'_>>) {}
+
+    #[doc(hidden)]
+    #[inline]
+    unsafe fn add_methods<'target, Tgt>(
+        _target: &Tgt,
+        _functions: &mut Vector<'_, 'static>,
+        _function_info_ty: DataType,
+    ) where
+        Tgt: Target<'target>,
+    {
+    }
 }
 
 pub trait Bounds {
@@ -658,6 +759,10 @@ pub unsafe trait ParametricVariant: ParametricBase {
     unsafe fn reinit_variant(datatype: DataType) -> bool {
         reinit_parametric_opaque_variant::<Self>(datatype)
     }
+
+    /// Called right before the data of an instance is dropped, see [`OpaqueType::finalize`].
+    #[inline]
+    fn finalize(&mut self, _target: Option<Unrooted<'_>>) {}
 }
 
 /// A trait that allows Rust data with internal references to Julia data to be converted to Julia.
@@ -741,11 +846,163 @@ pub unsafe trait ForeignType: Sized + Send + 'static {
     /// method should return the number of times `mark_queue_obj` returned `true`, or 0 if only
     /// `mark_queue_objarray` is called.
     ///
+    /// This method can be generated with `#[derive(ForeignType)]`, see [`Mark`] for more
+    /// information.
+    ///
     /// [`mark_queue_obj`]: crate::memory::gc::mark_queue_obj
     /// [`mark_queue_objarray`]: crate::memory::gc::mark_queue_objarray
     fn mark(ptls: PTls, data: &Self) -> usize;
+
+    /// Called right before the data of an instance is dropped, see [`OpaqueType::finalize`].
+    #[inline]
+    fn finalize(&mut self, _target: Option<Unrooted<'_>>) {}
+}
+
+/// Mark the references to Julia data contained in some value.
+///
+/// This trait is used by the `mark` method generated by `#[derive(ForeignType)]`. It's
+/// implemented for all `Ref`-types, primitive types, `str` and `String`, and for `Option`,
+/// `Box`, `Vec`, arrays, and the mutexes and read-write locks from [`gc_safe`] that contain
+/// implementations of this trait. It can be derived for structs that are used as fields of a
+/// foreign type with `#[derive(Mark)]`.
+///
+/// The derive macros mark every field, so the type of each field must implement this trait.
+/// Fields that don't contain references to Julia data and whose type doesn't implement `Mark`,
+/// or fields that must not be marked, can be ignored by annotating them with `#[jlrs(skip)]`:
+///
+/// ```ignore
+/// #[derive(Mark)]
+/// struct Inner {
+///     values: Vec<Option<ValueRef<'static, 'static>>>,
+/// }
+///
+/// #[derive(ForeignType)]
+/// struct Outer {
+///     value: ValueRef<'static, 'static>,
+///     locked: GcSafeMutex<Option<ModuleRef<'static>>>,
+///     inner: Inner,
+///     #[jlrs(skip)]
+///     global: ValueRef<'static, 'static>,
+/// }
+/// ```
+///
+/// Mutexes and read-write locks are accessed without locking them, because the lock might be
+/// held by a thread that is waiting for the GC to finish.
+///
+/// Safety:
+///
+/// `mark` must call [`mark_queue_obj`] for every reference to Julia data and return the number
+/// of times it returned `true`. It's called by the GC during its marking phase, so it must not
+/// call into Julia or allocate Julia data. Data protected by a lock must only be modified by
+/// threads that are in a GC-unsafe state.
+///
+/// [`gc_safe`]: crate::gc_safe
+/// [`mark_queue_obj`]: crate::memory::gc::mark_queue_obj
+pub unsafe trait Mark {
+    /// Mark all references to Julia data in `self`.
+    ///
+    /// Safety: this method must only be called from the `mark` method of a `ForeignType`.
+    unsafe fn mark(&self, ptls: PTls) -> usize;
+}
+
+unsafe impl<'scope, 'data, T: Managed<'scope, 'data>> Mark for Ref<'scope, 'data, T> {
+    #[inline]
+    unsafe fn mark(&self, ptls: PTls) -> usize {
+        let value = ValueRef::wrap(self.ptr().cast());
+        mark_queue_obj(ptls, value) as usize
+    }
+}
+
+unsafe impl<M: Mark> Mark for Option<M> {
+    #[inline]
+    unsafe fn mark(&self, ptls: PTls) -> usize {
+        match self {
+            Some(m) => m.mark(ptls),
+            None => 0,
+        }
+    }
+}
+
+unsafe impl<M: Mark + ?Sized> Mark for Box<M> {
+    #[inline]
+    unsafe fn mark(&self, ptls: PTls) -> usize {
+        M::mark(self, ptls)
+    }
+}
+
+unsafe impl<M: Mark> Mark for [M] {
+    #[inline]
+    unsafe fn mark(&self, ptls: PTls) -> usize {
+        self.iter().map(|m| m.mark(ptls)).sum()
+    }
+}
+
+unsafe impl<M: Mark, const N: usize> Mark for [M; N] {
+    #[inline]
+    unsafe fn mark(&self, ptls: PTls) -> usize {
+        self.as_slice().mark(ptls)
+    }
+}
+
+unsafe impl<M: Mark> Mark for Vec<M> {
+    #[inline]
+    unsafe fn mark(&self, ptls: PTls) -> usize {
+        self.as_slice().mark(ptls)
+    }
+}
+
+unsafe impl<R: lock_api::RawMutex, M: Mark + ?Sized> Mark for lock_api::Mutex<R, M> {
+    #[inline]
+    unsafe fn mark(&self, ptls: PTls) -> usize {
+        (&*self.data_ptr()).mark(ptls)
+    }
 }
 
+unsafe impl<R: lock_api::RawRwLock, M: Mark + ?Sized> Mark for lock_api::RwLock<R, M> {
+    #[inline]
+    unsafe fn mark(&self, ptls: PTls) -> usize {
+        (&*self.data_ptr()).mark(ptls)
+    }
+}
+
+macro_rules! impl_mark_plain {
+    ($($t:ty),+) => {
+        $(
+            unsafe impl Mark for $t {
+                #[inline]
+                unsafe fn mark(&self, _ptls: PTls) -> usize {
+                    0
+                }
+            }
+        )+
+    };
+}
+
+impl_mark_plain!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    str,
+    String
+);
+
+#[cfg(feature = "f16")]
+impl_mark_plain!(half::f16);
+
 unsafe impl<T: ForeignType> OpaqueType for T {
     const IS_FOREIGN: bool = true;
     const TYPE_FN: Option<unsafe fn() -> DataType<'static>> = <T as ForeignType>::TYPE_FN;
@@ -770,10 +1027,33 @@ unsafe impl<T: ForeignType> OpaqueType for T {
         create_foreign_type::<Self, Tgt>(target, name, module)
     }
 
+    #[inline]
+    unsafe fn create_subtype<'target, Tgt>(
+        target: Tgt,
+        name: Symbol,
+        module: Module,
+        super_type: DataType,
+    ) -> DataTypeData<'target, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        create_foreign_type_with_super::<Self, Tgt>(
+            target,
+            name,
+            module,
+            super_type.unwrap(Private),
+        )
+    }
+
     #[inline]
     unsafe fn reinit_type(datatype: DataType) -> bool {
         reinit_foreign_type::<Self>(datatype)
     }
+
+    #[inline]
+    fn finalize(&mut self, target: Option<Unrooted<'_>>) {
+        <Self as ForeignType>::finalize(self, target)
+    }
 }
 
 unsafe impl<T: OpaqueType> ParametricBase for T {
@@ -839,6 +1119,189 @@ unsafe impl<T: OpaqueType> ParametricVariant for T {
     unsafe fn reinit_variant(_datatype: DataType) -> bool {
         unimplemented!("OpaqueTypes can't have variants")
     }
+
+    #[inline]
+    fn finalize(&mut self, target: Option<Unrooted<'_>>) {
+        <Self as OpaqueType>::finalize(self, target)
+    }
+}
+
+/// Creates a new abstract type named `name` in `module` for the trait object type `T`.
+///
+/// The abstract type is a direct subtype of `Core.Any`. It must be created if it doesn't exist yet
+/// in `module`, this function is called automatically by init functions generated with the
+/// `julia_module` macro for every exported trait.
+///
+/// Safety:
+///
+/// The new type is not set as a constant in `module`, you must do this manually after calling
+/// this function.
+pub unsafe fn create_trait_type<'target, T, Tgt>(
+    target: Tgt,
+    name: Symbol,
+    module: Module,
+) -> DataTypeData<'target, Tgt>
+where
+    T: ?Sized + 'static,
+    Tgt: Target<'target>,
+{
+    if let Some(ty) = FOREIGN_TYPE_REGISTRY.get_unchecked().find::<Key<T>>() {
+        return target.data_from_ptr(ty.unwrap_non_null(Private), Private);
+    }
+
+    #[cfg(feature = "julia-1-6")]
+    let ty = jl_new_datatype(
+        name.unwrap(Private),
+        module.unwrap(Private),
+        jl_any_type,
+        jl_emptysvec,
+        jl_emptysvec,
+        jl_emptysvec,
+        1,
+        0,
+        0,
+    );
+
+    #[cfg(not(feature = "julia-1-6"))]
+    let ty = jl_new_datatype(
+        name.unwrap(Private),
+        module.unwrap(Private),
+        jl_any_type,
+        jl_emptysvec,
+        jl_emptysvec,
+        jl_emptysvec,
+        jl_emptysvec,
+        1,
+        0,
+        0,
+    );
+
+    debug_assert!(!ty.is_null());
+    FOREIGN_TYPE_REGISTRY
+        .get_unchecked()
+        .insert::<Key<T>>(DataType::wrap_non_null(NonNull::new_unchecked(ty), Private));
+
+    target.data_from_ptr(NonNull::new_unchecked(ty), Private)
+}
+
+/// Reinitializes the previously created abstract type `datatype` for the trait object type `T`.
+///
+/// The abstract type must be reinitialized if it has been created in a precompiled module and
+/// this module is loaded. This function is called automatically by init functions generated
+/// with the `julia_module` macro.
+///
+/// Safety:
+///
+/// The datatype must have been originally created by calling `create_trait_type::<T, _>`.
+pub unsafe fn reinit_trait_type<T>(datatype: DataType) -> bool
+where
+    T: ?Sized + 'static,
+{
+    if FOREIGN_TYPE_REGISTRY
+        .get_unchecked()
+        .find::<Key<T>>()
+        .is_some()
+    {
+        return true;
+    }
+
+    FOREIGN_TYPE_REGISTRY
+        .get_unchecked()
+        .insert::<Key<T>>(erase_scope_lifetime(datatype));
+    true
+}
+
+/// Returns the abstract type associated with the trait object type `T`, or `None` if it hasn't
+/// been created yet.
+pub fn trait_type<'target, T, Tgt>(_target: &Tgt) -> Option<DataType<'target>>
+where
+    T: ?Sized + 'static,
+    Tgt: Target<'target>,
+{
+    unsafe { FOREIGN_TYPE_REGISTRY.get_unchecked().find::<Key<T>>() }
+}
+
+/// Registers `cast` as the conversion from instances of `datatype` to the trait object type `T`.
+///
+/// The cast is used by [`trait_object`] to find the implementation of a trait for some value.
+/// This function is called automatically by init functions generated with the `julia_module`
+/// macro for every exported type that has a trait as its supertype.
+///
+/// Safety:
+///
+/// `cast` must convert a pointer to the data of an instance of `datatype` to a pointer to the
+/// trait object `T` with the same address.
+pub unsafe fn register_trait_impl<T>(datatype: DataType, cast: unsafe fn(*mut c_void) -> *mut T)
+where
+    T: ?Sized + 'static,
+{
+    let key = (TypeId::of::<T>(), datatype.unwrap(Private) as usize);
+    FOREIGN_TYPE_REGISTRY
+        .get_unchecked()
+        .trait_impls
+        .write()
+        .insert(key, Box::new(cast));
+}
+
+/// Converts `value` to a pointer to the trait object type `T`.
+///
+/// Returns `None` if no implementation of `T` has been registered for the type of `value`, or if
+/// `value` has been destroyed.
+///
+/// Safety:
+///
+/// The pointer must only be dereferenced while `value` is reachable and hasn't been destroyed.
+/// Unlike tracking, this function doesn't check whether the data is already borrowed.
+pub unsafe fn trait_object<T>(value: Value) -> Option<NonNull<T>>
+where
+    T: ?Sized + 'static,
+{
+    if is_destroyed(value) {
+        return None;
+    }
+
+    let key = (TypeId::of::<T>(), value.datatype().unwrap(Private) as usize);
+    let cast = *FOREIGN_TYPE_REGISTRY
+        .get_unchecked()
+        .trait_impls
+        .read()
+        .get(&key)?
+        .downcast_ref::<unsafe fn(*mut c_void) -> *mut T>()?;
+
+    NonNull::new(cast(value.data_ptr().as_ptr()))
+}
+
+/// Throw a `TypeError` if no implementation of the trait object type `T` has been registered for
+/// the type of `value`.
+///
+/// `fname` is the name of the function that is reported in the error, it must be
+/// null-terminated.
+///
+/// Safety: must only be called from a function called from Julia, pending drops are skipped.
+#[doc(hidden)]
+#[inline]
+pub unsafe fn throw_if_not_implemented<T>(fname: &'static str, value: Value)
+where
+    T: ?Sized + 'static,
+{
+    if trait_object::<T>(value).is_none() {
+        throw_not_implemented::<T>(fname, value)
+    }
+}
+
+#[cold]
+#[inline(never)]
+unsafe fn throw_not_implemented<T>(fname: &'static str, value: Value) -> !
+where
+    T: ?Sized + 'static,
+{
+    debug_assert!(fname.ends_with('\0'));
+    let expected = match FOREIGN_TYPE_REGISTRY.get_unchecked().find::<Key<T>>() {
+        Some(ty) => ty.unwrap(Private).cast(),
+        None => jl_any_type.cast(),
+    };
+
+    jl_type_error(fname.as_ptr().cast(), expected, value.unwrap(Private))
 }
 
 #[inline]
@@ -854,11 +1317,25 @@ where
     create_foreign_type_nostack::<U, _>(target, name, module)
 }
 
+#[inline]
 pub(crate) unsafe fn create_foreign_type_nostack<'target, U, Tgt>(
     target: Tgt,
     name: Symbol,
     module: Module,
 ) -> DataTypeData<'target, Tgt>
+where
+    U: ForeignType,
+    Tgt: Target<'target>,
+{
+    create_foreign_type_with_super::<U, _>(target, name, module, jl_any_type)
+}
+
+unsafe fn create_foreign_type_with_super<'target, U, Tgt>(
+    target: Tgt,
+    name: Symbol,
+    module: Module,
+    super_type: *mut jl_datatype_t,
+) -> DataTypeData<'target, Tgt>
 where
     U: ForeignType,
     Tgt: Target<'target>,
@@ -870,22 +1347,12 @@ where
     let large = U::LARGE as _;
     let has_pointers = U::HAS_POINTERS as _;
 
-    unsafe extern "C" fn mark<T: ForeignType>(ptls: PTls, value: *mut jl_value_t) -> usize {
-        T::mark(ptls, NonNull::new_unchecked(value.cast()).as_ref())
-    }
-
-    unsafe extern "C" fn sweep<T: ForeignType>(value: *mut jl_value_t) {
-        do_sweep::<T>(&mut *value.cast())
-    }
-
-    let super_type = jl_any_type;
-
     let ty = jl_new_foreign_type(
         name.unwrap(Private),
         module.unwrap(Private),
         super_type,
-        mark::<U>,
-        sweep::<U>,
+        mark_foreign::<U>,
+        sweep_foreign::<U>,
         has_pointers,
         large,
     );
@@ -897,11 +1364,25 @@ where
     target.data_from_ptr(NonNull::new_unchecked(ty), Private)
 }
 
+#[inline]
 unsafe fn create_opaque_type<'target, U, Tgt>(
     target: Tgt,
     name: Symbol,
     module: Module,
 ) -> DataTypeData<'target, Tgt>
+where
+    U: OpaqueType,
+    Tgt: Target<'target>,
+{
+    create_opaque_type_with_super::<U, Tgt>(target, name, module, None)
+}
+
+unsafe fn create_opaque_type_with_super<'target, U, Tgt>(
+    target: Tgt,
+    name: Symbol,
+    module: Module,
+    super_type: Option<DataType>,
+) -> DataTypeData<'target, Tgt>
 where
     U: OpaqueType,
     Tgt: Target<'target>,
@@ -911,7 +1392,10 @@ where
     }
 
     target.with_local_scope::<_, _, 1>(|target, mut frame| {
-        let super_type = U::super_type(&mut frame).unwrap(Private);
+        let super_type = match super_type {
+            Some(super_type) => super_type.unwrap(Private),
+            None => U::super_type(&mut frame).unwrap(Private),
+        };
 
         #[cfg(feature = "julia-1-6")]
         let ty = jl_new_datatype(
@@ -1052,22 +1536,14 @@ where
     let large = U::LARGE as _;
     let has_pointers = U::HAS_POINTERS as _;
 
-    unsafe extern "C" fn mark<T: ForeignType>(ptls: PTls, value: *mut jl_value_t) -> usize {
-        T::mark(ptls, NonNull::new_unchecked(value.cast()).as_ref())
-    }
-
-    unsafe extern "C" fn sweep<T: ForeignType>(value: *mut jl_value_t) {
-        do_sweep::<T>(&mut *value.cast())
-    }
-
     let super_type = jl_any_type;
 
     let ty = jl_new_foreign_type(
         name.unwrap(Private),
         module.unwrap(Private),
         super_type,
-        mark::<U>,
-        sweep::<U>,
+        mark_foreign::<U>,
+        sweep_foreign::<U>,
         has_pointers,
         large,
     );
@@ -1084,16 +1560,8 @@ where
         return true;
     }
 
-    unsafe extern "C" fn mark<T: ForeignType>(ptls: PTls, value: *mut jl_value_t) -> usize {
-        T::mark(ptls, NonNull::new_unchecked(value.cast()).as_ref())
-    }
-
-    unsafe extern "C" fn sweep<T: ForeignType>(value: *mut jl_value_t) {
-        do_sweep::<T>(&mut *value.cast())
-    }
-
     let ty = datatype.unwrap(Private);
-    let ret = jl_reinit_foreign_type(ty, mark::<U>, sweep::<U>);
+    let ret = jl_reinit_foreign_type(ty, mark_foreign::<U>, sweep_foreign::<U>);
     if ret != 0 {
         FOREIGN_TYPE_REGISTRY
             .get_unchecked()
@@ -1155,6 +1623,21 @@ where
     true
 }
 
+// The mark function of every foreign type. The data of destroyed instances has already been
+// dropped, so it must not be marked.
+unsafe extern "C" fn mark_foreign<T: ForeignType>(ptls: PTls, value: *mut jl_value_t) -> usize {
+    if is_destroyed_ptr(value as usize) {
+        return 0;
+    }
+
+    T::mark(ptls, NonNull::new_unchecked(value.cast()).as_ref())
+}
+
+// The sweep function of every foreign type.
+unsafe extern "C" fn sweep_foreign<T: ForeignType>(value: *mut jl_value_t) {
+    do_sweep::<T>(&mut *value.cast())
+}
+
 #[julia_version(since = "1.7", until = "1.7")]
 #[inline]
 unsafe fn do_sweep<T>(_: &mut ForeignValue<T>)
@@ -1170,7 +1653,13 @@ unsafe fn do_sweep<T>(data: &mut ForeignValue<T>)
 where
     T: ForeignType,
 {
-    data.data.assume_init_drop();
+    if take_destroyed(data as *mut _ as usize) {
+        return;
+    }
+
+    let data = data.data.assume_init_mut();
+    <T as ForeignType>::finalize(data, None);
+    std::ptr::drop_in_place(data);
 }
 
 unsafe impl<F: ParametricVariant> IntoJulia for F {
@@ -1295,8 +1784,105 @@ struct ForeignValue<T: ForeignType> {
 
 #[inline]
 unsafe extern "C" fn drop_opaque<T: ParametricVariant>(data: *mut c_void) {
-    let p = data as *mut MaybeUninit<T>;
-    NonNull::new_unchecked(p).as_mut().assume_init_drop()
+    if take_destroyed(data as usize) {
+        return;
+    }
+
+    let data = NonNull::new_unchecked(data as *mut T).as_mut();
+    <T as ParametricVariant>::finalize(data, Some(Unrooted::new()));
+    std::ptr::drop_in_place(data);
+}
+
+/// Drop the data of an opaque or foreign value eagerly.
+///
+/// The finalizer hook of `T` is called and the data is dropped immediately. The value itself
+/// remains valid until it's freed by the GC, but its data can't be accessed anymore. Methods of
+/// exported types check if an instance has been destroyed and throw a `JlrsCore.JlrsError` if it
+/// has, tracking a destroyed instance returns an error and [`trait_object`] returns `None`. This
+/// function is used by the `Base.close` method that is added to types exported with the `#[close]`
+/// attribute.
+///
+/// Returns `Ok(true)` if the data has been dropped, and `Ok(false)` if it has already been
+/// destroyed. An error is returned if `value` is not an instance of `T` or if it's currently
+/// tracked.
+///
+/// Safety: there must be no references to the data of `value` that aren't tracked. Don't call
+/// `Base.finalize` on a destroyed instance.
+pub unsafe fn destroy<T: ParametricVariant>(mut value: Value) -> JlrsResult<bool> {
+    if is_destroyed(value) {
+        return Ok(false);
+    }
+
+    let ptr = value.unwrap(Private) as usize;
+    let mut tracked = value.track_exclusive::<T>()?;
+    let data: *mut T = &mut *tracked;
+    <T as ParametricVariant>::finalize(&mut *data, Some(Unrooted::new()));
+    std::ptr::drop_in_place(data);
+
+    DESTROYED.lock().insert(ptr);
+    N_DESTROYED.fetch_add(1, Ordering::Relaxed);
+
+    // Untracking the value doesn't access its data.
+    std::mem::drop(tracked);
+    Ok(true)
+}
+
+/// Returns `true` if `value` has been destroyed with [`destroy`].
+#[inline]
+pub fn is_destroyed(value: Value) -> bool {
+    is_destroyed_ptr(value.unwrap(Private) as usize)
+}
+
+/// Throw a `JlrsCore.JlrsError` if `value` has been destroyed.
+///
+/// Safety: must only be called from a function called from Julia, pending drops are skipped.
+#[doc(hidden)]
+#[inline]
+pub unsafe fn throw_if_destroyed<'scope, 'data, M: Managed<'scope, 'data>>(value: M) {
+    let value = value.as_value();
+    if is_destroyed(value) {
+        throw_destroyed(value)
+    }
+}
+
+#[cold]
+#[inline(never)]
+unsafe fn throw_destroyed(value: Value) -> ! {
+    let unrooted = Unrooted::new();
+    let err = unrooted.local_scope::<_, 1>(|mut frame| {
+        let ty = value.datatype().display_string_or(CANNOT_DISPLAY_TYPE);
+        let msg = format!("instance of {ty} has been closed");
+        let msg = JuliaString::new(&mut frame, msg).as_value();
+        JlrsCore::jlrs_error(&frame)
+            .instantiate_unchecked(&frame, [msg])
+            .leak()
+    });
+
+    jl_throw(err.ptr().as_ptr())
+}
+
+#[inline]
+fn is_destroyed_ptr(ptr: usize) -> bool {
+    if N_DESTROYED.load(Ordering::Relaxed) == 0 {
+        return false;
+    }
+
+    DESTROYED.lock().contains(&ptr)
+}
+
+// Removes `ptr` from the set of destroyed instances, returns `true` if it was present.
+#[inline]
+fn take_destroyed(ptr: usize) -> bool {
+    if N_DESTROYED.load(Ordering::Relaxed) == 0 {
+        return false;
+    }
+
+    let removed = DESTROYED.lock().remove(&ptr);
+    if removed {
+        N_DESTROYED.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    removed
 }
 
 unsafe impl<T: ParametricVariant> ConstructType for T {
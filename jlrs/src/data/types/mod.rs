@@ -1,7 +1,12 @@
 //! Construct Julia types, check their properties, and create types that contain Rust data.
 
 pub mod abstract_type;
+pub mod base_methods;
 pub mod construct_type;
+pub mod exposed_fields;
 pub mod foreign_type;
 pub mod primitive_type;
+pub mod rust_iterator;
+pub mod serializable_opaque;
+pub mod struct_type;
 pub mod typecheck;
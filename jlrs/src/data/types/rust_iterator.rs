@@ -0,0 +1,227 @@
+//! Expose Rust iterators to Julia.
+//!
+//! A [`RustIterator`] wraps an arbitrary Rust iterator whose items can be converted to Julia data
+//! with [`IntoJulia`]. It's an [`OpaqueType`], when a `RustIterator` is exported with the
+//! [`julia_module`] macro, methods for `Base.iterate`, `Base.IteratorSize` and `Base.eltype` are
+//! added automatically. This makes it possible to use it in a `for`-loop, or with functions like
+//! `collect` and `sum`:
+//!
+//! ```ignore
+//! use jlrs::{data::types::rust_iterator::RustIterator, prelude::*};
+//!
+//! type Squares = RustIterator<u64>;
+//!
+//! fn squares(n: u64) -> Squares {
+//!     RustIterator::new((0..n).map(|i| i * i))
+//! }
+//!
+//! julia_module! {
+//!     become squares_init_fn;
+//!     struct Squares;
+//!     fn squares(n: u64) -> Squares;
+//! }
+//! ```
+//!
+//! Julia code can't tell in advance how many items the iterator yields, so `Base.IteratorSize`
+//! returns `Base.SizeUnknown()`. Iterators are fused: once the Rust iterator has returned `None`
+//! iterating will always be finished.
+//!
+//! Only exported types that are not generic get these methods; each element type must be
+//! exported separately, e.g. by exporting type aliases of `RustIterator`.
+//!
+//! [`IntoJulia`]: crate::convert::into_julia::IntoJulia
+//! [`julia_module`]: ::jlrs_macros::julia_module
+
+use std::{ffi::c_void, marker::PhantomData};
+
+use super::{
+    base_methods::add_method,
+    construct_type::{ConstructType, UnionTypeConstructor},
+    foreign_type::{throw_if_destroyed, OpaqueType},
+};
+use crate::{
+    convert::{ccall_types::CCallReturn, into_julia::IntoJulia},
+    data::{
+        layout::{nothing::Nothing, tuple::Tuple, tuple::Tuple2},
+        managed::{
+            array::Vector,
+            datatype::DataType,
+            module::Module,
+            union_all::UnionAll,
+            value::{
+                typed::{TypedValue, TypedValueRet},
+                Value, ValueRet,
+            },
+            Managed,
+        },
+    },
+    gc_safe::GcSafeMutex,
+    memory::{
+        scope::LocalScope,
+        target::{unrooted::Unrooted, Target},
+    },
+};
+
+type BoxedIterator<T> = Box<dyn Iterator<Item = T> + Send>;
+
+/// A Rust iterator that can be iterated over in Julia.
+///
+/// See the [module-level docs] for more information.
+///
+/// [module-level docs]: self
+pub struct RustIterator<T> {
+    iter: GcSafeMutex<BoxedIterator<T>>,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T> RustIterator<T>
+where
+    T: IntoJulia + ConstructType,
+{
+    /// Wrap `iter`.
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: Send + 'static,
+    {
+        RustIterator {
+            iter: GcSafeMutex::new(Box::new(iter.into_iter().fuse())),
+            _item: PhantomData,
+        }
+    }
+
+    /// Returns the next item of the wrapped iterator.
+    pub fn next_item(&self) -> Option<T> {
+        self.iter.lock().next()
+    }
+}
+
+unsafe impl<T> OpaqueType for RustIterator<T>
+where
+    T: IntoJulia + ConstructType,
+{
+    unsafe fn add_methods<'target, Tgt>(
+        target: &Tgt,
+        functions: &mut Vector<'_, 'static>,
+        function_info_ty: DataType,
+    ) where
+        Tgt: Target<'target>,
+    {
+        target.local_scope::<_, 3>(|mut frame| {
+            let base = Module::base(&frame);
+            let any = DataType::any_type(&frame).as_value();
+            let nothing = DataType::nothing_type(&frame).as_value();
+            let ty = Self::construct_type(&mut frame);
+            let type_ty = UnionAll::type_type(&frame)
+                .as_value()
+                .apply_type_unchecked(&mut frame, [ty]);
+            let next_ty =
+                UnionTypeConstructor::<Nothing, Tuple2<T, Nothing>>::construct_type(&mut frame);
+
+            add_method(
+                &frame,
+                functions,
+                function_info_ty,
+                base,
+                "iterate",
+                iterate::<T> as *mut c_void,
+                &[(any, ty)],
+                (any, next_ty),
+            );
+
+            add_method(
+                &frame,
+                functions,
+                function_info_ty,
+                base,
+                "iterate",
+                iterate_with_state::<T> as *mut c_void,
+                &[(any, ty), (any, nothing)],
+                (any, next_ty),
+            );
+
+            add_method(
+                &frame,
+                functions,
+                function_info_ty,
+                base,
+                "eltype",
+                eltype::<T> as *mut c_void,
+                &[(any, type_ty)],
+                (any, any),
+            );
+
+            add_method(
+                &frame,
+                functions,
+                function_info_ty,
+                base,
+                "IteratorSize",
+                iterator_size as *mut c_void,
+                &[(any, type_ty)],
+                (any, any),
+            );
+        })
+    }
+}
+
+unsafe impl<T> CCallReturn for RustIterator<T>
+where
+    T: IntoJulia + ConstructType,
+{
+    type FunctionReturnType = Self;
+    type CCallReturnType = Value<'static, 'static>;
+    type ReturnAs = TypedValueRet<Self>;
+
+    #[inline]
+    unsafe fn return_or_throw(self) -> Self::ReturnAs {
+        TypedValue::new(Unrooted::new(), self).leak()
+    }
+}
+
+unsafe extern "C" fn iterate<T>(iter: Value<'static, 'static>) -> ValueRet
+where
+    T: IntoJulia + ConstructType,
+{
+    throw_if_destroyed(iter);
+    let unrooted = Unrooted::new();
+    let iter = iter.data_ptr().cast::<RustIterator<T>>().as_ref();
+
+    match iter.next_item() {
+        Some(item) => unrooted.local_scope::<_, 1>(|mut frame| {
+            let item = Value::new(&mut frame, item);
+            let nothing = Value::nothing(&frame);
+            Tuple::new_unchecked(unrooted, [item, nothing]).leak()
+        }),
+        None => Value::nothing(&unrooted).leak(),
+    }
+}
+
+unsafe extern "C" fn iterate_with_state<T>(
+    iter: Value<'static, 'static>,
+    _state: Value<'static, 'static>,
+) -> ValueRet
+where
+    T: IntoJulia + ConstructType,
+{
+    iterate::<T>(iter)
+}
+
+unsafe extern "C" fn eltype<T>(_ty: Value<'static, 'static>) -> ValueRet
+where
+    T: IntoJulia + ConstructType,
+{
+    T::construct_type(Unrooted::new()).leak()
+}
+
+unsafe extern "C" fn iterator_size(_ty: Value<'static, 'static>) -> ValueRet {
+    let unrooted = Unrooted::new();
+    Module::base(&unrooted)
+        .global(unrooted, "SizeUnknown")
+        .unwrap()
+        .as_value()
+        .cast_unchecked::<DataType>()
+        .instance()
+        .unwrap()
+        .leak()
+}
@@ -0,0 +1,203 @@
+//! Serialize opaque and foreign types.
+//!
+//! The content of an opaque or foreign type is Rust data that Julia knows nothing about, so
+//! `Serialization.serialize` can't serialize it. This also prevents these types from being sent
+//! to other processes with the `Distributed` standard library. Implementations of
+//! [`SerializableOpaque`] can convert themselves from and to bytes, if such a type is exported
+//! with the [`julia_module`] macro and annotated with `#[serialize]`, methods for
+//! `Serialization.serialize` and `Serialization.deserialize` are added that use this
+//! implementation:
+//!
+//! ```ignore
+//! struct Counter {
+//!     count: u64,
+//! }
+//!
+//! unsafe impl OpaqueType for Counter {}
+//!
+//! impl SerializableOpaque for Counter {
+//!     fn to_bytes(&self) -> Vec<u8> {
+//!         self.count.to_le_bytes().to_vec()
+//!     }
+//!
+//!     fn from_bytes(bytes: &[u8]) -> JlrsResult<Self> {
+//!         let count = bytes.try_into().map_err(|_| JlrsError::exception("invalid counter"))?;
+//!         Ok(Counter { count: u64::from_le_bytes(count) })
+//!     }
+//! }
+//!
+//! julia_module! {
+//!     become counter_init_fn;
+//!
+//!     #[serialize]
+//!     struct Counter;
+//! }
+//! ```
+//!
+//! An instance is serialized as its type followed by the bytes returned by `to_bytes` as a
+//! `Vector{UInt8}`. The process that deserializes it must have loaded the same library, and the
+//! bytes must be compatible with its implementation of `from_bytes`.
+//!
+//! Foreign types can implement this trait too, but references to Julia data can't be serialized
+//! this way.
+//!
+//! [`julia_module`]: ::jlrs_macros::julia_module
+
+use std::ffi::c_void;
+
+use super::{
+    base_methods::add_method,
+    construct_type::ConstructType,
+    foreign_type::{throw_if_destroyed, OpaqueType},
+};
+use crate::{
+    call::Call,
+    convert::{ccall_types::CCallReturn, into_jlrs_result::IntoJlrsResult as _},
+    data::managed::{
+        array::{ConstructTypedArray as _, TypedVector, Vector},
+        datatype::DataType,
+        module::Module,
+        union_all::UnionAll,
+        value::{Value, ValueRet},
+        Managed,
+    },
+    error::JlrsResult,
+    memory::{
+        scope::LocalScope,
+        target::{unrooted::Unrooted, Target},
+    },
+};
+
+/// Convert an opaque or foreign type from and to bytes.
+///
+/// See the [module-level docs] for more information.
+///
+/// [module-level docs]: self
+pub trait SerializableOpaque: OpaqueType {
+    /// Serialize `self`.
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Deserialize an instance of this type from `bytes`, which have been returned by
+    /// [`SerializableOpaque::to_bytes`].
+    ///
+    /// If an error is returned, it's thrown as a `JlrsCore.JlrsError`.
+    fn from_bytes(bytes: &[u8]) -> JlrsResult<Self>;
+}
+
+/// Add methods to `Serialization.serialize` and `Serialization.deserialize` for `T`.
+///
+/// Safety: must only be called by the init function generated by `julia_module`.
+#[doc(hidden)]
+pub unsafe fn add_serialization_methods<'target, T, Tgt>(
+    target: &Tgt,
+    functions: &mut Vector<'_, 'static>,
+    function_info_ty: DataType,
+) where
+    T: SerializableOpaque,
+    Tgt: Target<'target>,
+{
+    target.local_scope::<_, 4>(|mut frame| {
+        let serialization = Module::main(&frame)
+            .require(&mut frame, "Serialization")
+            .expect("cannot load Serialization")
+            .cast::<Module>()
+            .expect("Serialization is not a module");
+        let serializer = serialization
+            .global(&frame, "AbstractSerializer")
+            .expect("AbstractSerializer not found")
+            .as_value();
+
+        let any = DataType::any_type(&frame).as_value();
+        let ty = T::construct_type(&mut frame);
+        let type_ty = UnionAll::type_type(&frame)
+            .as_value()
+            .apply_type_unchecked(&mut frame, [ty]);
+
+        add_method(
+            &frame,
+            functions,
+            function_info_ty,
+            serialization,
+            "serialize",
+            serialize::<T> as *mut c_void,
+            &[(any, serializer), (any, ty)],
+            (any, any),
+        );
+
+        add_method(
+            &frame,
+            functions,
+            function_info_ty,
+            serialization,
+            "deserialize",
+            deserialize::<T> as *mut c_void,
+            &[(any, serializer), (any, type_ty)],
+            (any, ty),
+        );
+    })
+}
+
+// Serializes the value as an object tag, the type of the value, and the serialized bytes.
+unsafe extern "C" fn serialize<T: SerializableOpaque>(
+    serializer: Value<'static, 'static>,
+    value: Value<'static, 'static>,
+) -> ValueRet {
+    throw_if_destroyed(value);
+    let unrooted = Unrooted::new();
+    let bytes = value.data_ptr().cast::<T>().as_ref().to_bytes();
+
+    let res = unrooted.local_scope::<_, 4>(|mut frame| -> JlrsResult<_> {
+        let serialization = serialization_module(&frame);
+        let n_bytes = bytes.len();
+        let bytes = TypedVector::<u8>::from_vec(&mut frame, bytes, n_bytes)?.into_jlrs_result()?;
+
+        let io = serializer.get_field(&mut frame, "io")?;
+        let tag = serialization.global(&frame, "OBJECT_TAG")?.as_value();
+        let writetag = serialization.global(&frame, "writetag")?.as_value();
+        if let Err(e) = writetag.call2(&mut frame, io, tag) {
+            return Ok(Err(e.leak()));
+        }
+
+        let func = serialization.global(&frame, "serialize")?.as_value();
+        let ty = value.datatype().as_value();
+        if let Err(e) = func.call2(&mut frame, serializer, ty) {
+            return Ok(Err(e.leak()));
+        }
+
+        let res = func
+            .call2(unrooted, serializer, bytes.as_value())
+            .map(|_| Value::nothing(&frame).leak());
+        Ok(res)
+    });
+
+    res.return_or_throw().return_or_throw()
+}
+
+unsafe extern "C" fn deserialize<T: SerializableOpaque>(
+    serializer: Value<'static, 'static>,
+    _ty: Value<'static, 'static>,
+) -> ValueRet {
+    let unrooted = Unrooted::new();
+
+    let res = unrooted.local_scope::<_, 1>(|mut frame| -> JlrsResult<_> {
+        let serialization = serialization_module(&frame);
+        let func = serialization.global(&frame, "deserialize")?.as_value();
+        let bytes = match func.call1(&mut frame, serializer) {
+            Ok(bytes) => bytes.cast::<TypedVector<u8>>()?,
+            Err(e) => return Ok(Err(e.leak())),
+        };
+
+        let data = T::from_bytes(bytes.bits_data().as_slice())?;
+        Ok(Ok(Value::new(unrooted, data).leak()))
+    });
+
+    res.return_or_throw().return_or_throw()
+}
+
+// The Serialization module has been loaded by the init function.
+unsafe fn serialization_module<'target, Tgt>(target: &Tgt) -> Module<'target>
+where
+    Tgt: Target<'target>,
+{
+    Module::package_root_module(target, "Serialization").expect("Serialization is not loaded")
+}
@@ -0,0 +1,245 @@
+//! Define new Julia struct types at runtime.
+//!
+//! Types with fields are normally defined in Julia code. A [`StructType`] describes a new
+//! concrete struct type with named, typed fields that can be defined from Rust, which makes it
+//! possible to create types from a schema that is only known at runtime:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::data::types::struct_type::StructType;
+//! # fn main() {
+//! # let mut julia = Builder::new().start_local().unwrap();
+//! julia
+//!     .local_scope::<_, 6>(|mut frame| -> JlrsResult<()> {
+//!         let main = Module::main(&frame);
+//!         let f64_ty = DataType::float64_type(&frame).as_value();
+//!         let i64_ty = DataType::int64_type(&frame).as_value();
+//!
+//!         let ty = StructType::new("Measurement")
+//!             .field("value", f64_ty)
+//!             .field("count", i64_ty)
+//!             .mutable(true)
+//!             .define(&mut frame, main)?;
+//!
+//!         let value = Value::new(&mut frame, 1.5f64);
+//!         let count = Value::new(&mut frame, 3i64);
+//!         let measurement = ty
+//!             .instantiate(&mut frame, [value, count])?
+//!             .into_jlrs_result()?;
+//!
+//!         assert_eq!(measurement.get_field(&mut frame, "count")?.unbox::<i64>()?, 3);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # }
+//! ```
+//!
+//! The new type is set as a constant in the module it's defined in. No constructors are defined,
+//! instances can be created with [`DataType::instantiate`]. If the type has type parameters, the
+//! constant is a `UnionAll` that must be applied to concrete types before it can be instantiated,
+//! this `UnionAll` can be accessed with `DataType::type_name` and `TypeName::wrapper`.
+
+use std::ptr::NonNull;
+
+use jl_sys::jl_new_datatype;
+
+use crate::{
+    catch::{catch_exceptions, unwrap_exc},
+    convert::into_jlrs_result::IntoJlrsResult,
+    data::managed::{
+        datatype::{DataType, DataTypeData},
+        module::Module,
+        private::ManagedPriv as _,
+        simple_vector::SimpleVector,
+        symbol::Symbol,
+        type_var::TypeVar,
+        value::Value,
+        Managed,
+    },
+    error::{JlrsResult, TypeError, CANNOT_DISPLAY_VALUE},
+    memory::target::{private::TargetPriv, Target},
+    private::Private,
+};
+
+/// The definition of a new struct type.
+///
+/// The type is immutable, has no type parameters and is a subtype of `Core.Any` unless
+/// configured otherwise.
+#[derive(Clone)]
+pub struct StructType<'scope> {
+    name: String,
+    fields: Vec<(String, Value<'scope, 'static>)>,
+    parameters: Vec<TypeVar<'scope>>,
+    super_type: Option<DataType<'scope>>,
+    mutable: bool,
+}
+
+impl<'scope> StructType<'scope> {
+    /// Start the definition of a new struct type named `name`.
+    #[inline]
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        StructType {
+            name: name.into(),
+            fields: Vec::new(),
+            parameters: Vec::new(),
+            super_type: None,
+            mutable: false,
+        }
+    }
+
+    /// Add a field named `name` of type `ty`.
+    ///
+    /// The field type must be a type or one of the type parameters of this type. Fields are
+    /// stored in the order in which they're added.
+    #[inline]
+    pub fn field<S: Into<String>>(mut self, name: S, ty: Value<'scope, 'static>) -> Self {
+        self.fields.push((name.into(), ty));
+        self
+    }
+
+    /// Add the type parameter `tvar`.
+    #[inline]
+    pub fn parameter(mut self, tvar: TypeVar<'scope>) -> Self {
+        self.parameters.push(tvar);
+        self
+    }
+
+    /// Set the supertype of this type, which must be an abstract type.
+    #[inline]
+    pub fn super_type(mut self, super_type: DataType<'scope>) -> Self {
+        self.super_type = Some(super_type);
+        self
+    }
+
+    /// Make the type mutable or immutable.
+    #[inline]
+    pub fn mutable(mut self, mutable: bool) -> Self {
+        self.mutable = mutable;
+        self
+    }
+
+    /// Define the type in `module` and set it as a constant named after this type.
+    ///
+    /// Returns an error if the supertype isn't abstract, a field type is neither a type nor a
+    /// parameter of this type, multiple fields have the same name, or if Julia throws an
+    /// exception, e.g. because a different constant with this name already exists.
+    pub fn define<'target, Tgt>(
+        &self,
+        target: Tgt,
+        module: Module,
+    ) -> JlrsResult<DataTypeData<'target, Tgt>>
+    where
+        Tgt: Target<'target>,
+    {
+        self.validate()?;
+
+        target.with_local_scope::<_, _, 5>(|target, mut frame| unsafe {
+            let n_fields = self.fields.len();
+
+            let names = SimpleVector::with_capacity(&mut frame, n_fields);
+            let types = SimpleVector::with_capacity(&mut frame, n_fields);
+            {
+                let names = names.data();
+                let types = types.data();
+                for (idx, (name, ty)) in self.fields.iter().enumerate() {
+                    names.set(idx, Some(Symbol::new(&frame, name.as_str()).as_value()))?;
+                    types.set(idx, Some(*ty))?;
+                }
+            }
+
+            let parameters = SimpleVector::with_capacity(&mut frame, self.parameters.len());
+            {
+                let parameters = parameters.data();
+                for (idx, tvar) in self.parameters.iter().enumerate() {
+                    parameters.set(idx, Some(tvar.as_value()))?;
+                }
+            }
+
+            let name = Symbol::new(&frame, self.name.as_str());
+            let super_type = self
+                .super_type
+                .unwrap_or_else(|| DataType::any_type(&frame));
+
+            #[cfg(feature = "julia-1-6")]
+            let callback = || {
+                jl_new_datatype(
+                    name.unwrap(Private),
+                    module.unwrap(Private),
+                    super_type.unwrap(Private),
+                    parameters.unwrap(Private),
+                    names.unwrap(Private),
+                    types.unwrap(Private),
+                    0,
+                    self.mutable as _,
+                    n_fields as _,
+                )
+            };
+
+            #[cfg(not(feature = "julia-1-6"))]
+            let callback = || {
+                jl_new_datatype(
+                    name.unwrap(Private),
+                    module.unwrap(Private),
+                    super_type.unwrap(Private),
+                    parameters.unwrap(Private),
+                    names.unwrap(Private),
+                    types.unwrap(Private),
+                    SimpleVector::emptysvec(&frame).unwrap(Private),
+                    0,
+                    self.mutable as _,
+                    n_fields as _,
+                )
+            };
+
+            let res = match catch_exceptions(callback, unwrap_exc) {
+                Ok(ty) => Ok(NonNull::new_unchecked(ty)),
+                Err(e) => Err(e),
+            };
+
+            let ty: DataType = (&mut frame)
+                .result_from_ptr(res, Private)
+                .into_jlrs_result()?;
+
+            let constant = ty.type_name().wrapper();
+            module
+                .set_const(&mut frame, name, constant)
+                .into_jlrs_result()?;
+
+            Ok(ty.root(target))
+        })
+    }
+
+    fn validate(&self) -> JlrsResult<()> {
+        if let Some(super_type) = self.super_type {
+            if !super_type.is_abstract() {
+                Err(TypeError::NotAbstract {
+                    value: super_type.display_string_or(CANNOT_DISPLAY_VALUE),
+                })?
+            }
+        }
+
+        for (idx, (name, ty)) in self.fields.iter().enumerate() {
+            if self.fields[..idx].iter().any(|(other, _)| other == name) {
+                Err(TypeError::DuplicateField {
+                    type_name: self.name.clone(),
+                    field_name: name.clone(),
+                })?
+            }
+
+            let is_parameter = ty.is::<TypeVar>()
+                && self
+                    .parameters
+                    .iter()
+                    .any(|tvar| tvar.unwrap(Private).cast() == ty.unwrap(Private));
+
+            if !ty.is_type() && !is_parameter {
+                Err(TypeError::InvalidFieldType {
+                    value: ty.display_string_or(CANNOT_DISPLAY_VALUE),
+                    type_name: self.name.clone(),
+                })?
+            }
+        }
+
+        Ok(())
+    }
+}
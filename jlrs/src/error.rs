@@ -1,16 +1,33 @@
 //! Everything related to errors.
 
-use std::{error::Error as StdErr, pin::Pin, sync::Arc};
+use std::{
+    error::Error as StdErr,
+    fmt::{Display, Formatter, Result as FmtResult},
+    pin::Pin,
+    sync::Arc,
+};
 
+use jl_sys::jl_value_t;
+use jlrs_macros::julia_version;
 use thiserror::Error;
 
 use crate::{
+    call::Call,
     data::managed::{
-        array::dimensions::Dimensions,
+        array::{data::accessor::Accessor, dimensions::Dimensions, Array},
+        datatype::DataType,
+        module::Module,
+        private::ManagedPriv as _,
+        string::JuliaString,
+        symbol::{Symbol, SymbolRef},
         value::{Value, ValueRef, ValueRet},
         RefRet, Ret,
     },
+    inline_static_ref,
+    memory::scope::LocalScope,
     prelude::{Managed, ManagedRef, Target, TargetType, ValueData},
+    private::Private,
+    weak_handle_unchecked,
 };
 
 pub(crate) static CANNOT_DISPLAY_TYPE: &'static str = "<Cannot display type>";
@@ -76,6 +93,9 @@ pub trait JuliaResultExt<'scope, V> {
         self,
         target: Tgt,
     ) -> JuliaResultDataU<'target, 'static, V, Tgt>;
+
+    /// Classify the exception if `self` is an `Err`.
+    fn classify_exception(self) -> Result<V, JuliaException<'scope>>;
 }
 
 impl<'scope, V> JuliaResultExt<'scope, V> for JuliaResult<'scope, 'static, V> {
@@ -118,6 +138,11 @@ impl<'scope, V> JuliaResultExt<'scope, V> for JuliaResult<'scope, 'static, V> {
             Err(e) => Err(e.root(target)),
         }
     }
+
+    #[inline]
+    fn classify_exception(self) -> Result<V, JuliaException<'scope>> {
+        self.map_err(JuliaException::from_value)
+    }
 }
 
 /// Extension trait for `JuliaResultRef`.
@@ -217,6 +242,22 @@ pub enum IOError {
     NotFound { path: String },
 }
 
+/// Package management errors.
+#[derive(Debug, Error, Clone)]
+pub enum PkgError {
+    #[error("project does not exist: {path}")]
+    ProjectNotFound { path: String },
+    #[error("package does not exist: {path}")]
+    PackageNotFound { path: String },
+    #[error("depot does not exist: {path}")]
+    DepotNotFound { path: String },
+    #[error("Pkg.{operation} failed: {msg}")]
+    Failed {
+        operation: &'static str,
+        msg: String,
+    },
+}
+
 /// Type errors.
 #[derive(Debug, Error, Clone)]
 pub enum TypeError {
@@ -250,6 +291,15 @@ pub enum TypeError {
     LayoutNone { ty: String },
     #[error("The layout of this type is incompatible with {base_type}")]
     IncompatibleBaseType { base_type: String },
+    #[error("{value} is not an abstract type")]
+    NotAbstract { value: String },
+    #[error("{value} is not a type or a parameter of {type_name}")]
+    InvalidFieldType { value: String, type_name: String },
+    #[error("{type_name} has multiple fields named {field_name}")]
+    DuplicateField {
+        type_name: String,
+        field_name: String,
+    },
 }
 
 /// Array layout errors.
@@ -296,6 +346,8 @@ pub enum AccessError {
     },
     #[error("Data is already borrowed")]
     BorrowError,
+    #[error("instance of {value_type} has been closed")]
+    Destroyed { value_type: String },
     #[error("field at index {idx} does not exist: {value_type} has {n_fields} fields")]
     OutOfBoundsField {
         idx: usize,
@@ -329,18 +381,591 @@ pub enum InstantiationError {
     ArrayRankMismatch { expected: usize, found: usize },
 }
 
+/// A Julia exception classified by its type.
+///
+/// Exceptions are normally returned as a `Value`, to distinguish between them you'd have to
+/// check their type or parse the error message. [`JuliaException::from_value`] classifies an
+/// exception and provides access to the fields of common exception types. Exceptions of other
+/// types are returned as [`JuliaException::Other`].
+///
+/// A `JuliaResult` can be classified with [`JuliaResultExt::classify_exception`], a `ValueRet`
+/// must be converted to a `Value` first.
+#[derive(Clone, Debug)]
+pub enum JuliaException<'scope> {
+    /// `ArgumentError(msg)`
+    ArgumentError { msg: String },
+    /// `BoundsError(a, i)`, both fields are undefined if the error has been created without
+    /// arguments.
+    BoundsError {
+        a: Option<Value<'scope, 'static>>,
+        i: Option<Value<'scope, 'static>>,
+    },
+    /// `DimensionMismatch(msg)`
+    DimensionMismatch { msg: String },
+    /// `DivideError()`
+    DivideError,
+    /// `DomainError(val, msg)`, `msg` is undefined if the error has been created with
+    /// `DomainError(val)`.
+    DomainError {
+        val: Value<'scope, 'static>,
+        msg: Option<String>,
+    },
+    /// `ErrorException(msg)`, thrown by `error`.
+    ErrorException { msg: String },
+    /// `InexactError(func, T, val)`
+    InexactError {
+        func: Symbol<'scope>,
+        ty: Value<'scope, 'static>,
+        val: Value<'scope, 'static>,
+    },
+    /// `InitError(mod, error)`
+    InitError {
+        module: Symbol<'scope>,
+        error: Value<'scope, 'static>,
+    },
+    /// `InterruptException()`
+    InterruptException,
+    /// `KeyError(key)`
+    KeyError { key: Value<'scope, 'static> },
+    /// `LoadError(file, line, error)`
+    LoadError {
+        file: String,
+        line: isize,
+        error: Value<'scope, 'static>,
+    },
+    /// `MethodError(f, args)`
+    MethodError {
+        f: Value<'scope, 'static>,
+        args: Value<'scope, 'static>,
+    },
+    /// `OutOfMemoryError()`
+    OutOfMemoryError,
+    /// `OverflowError(msg)`
+    OverflowError { msg: String },
+    /// `StackOverflowError()`
+    StackOverflowError,
+    /// `TypeError(func, context, expected, got)`
+    TypeError {
+        func: Symbol<'scope>,
+        expected: Value<'scope, 'static>,
+        got: Value<'scope, 'static>,
+    },
+    /// `UndefRefError()`
+    UndefRefError,
+    /// `UndefVarError(var)`
+    UndefVarError { var: Symbol<'scope> },
+    /// An exception of any other type.
+    Other(Value<'scope, 'static>),
+}
+
+impl<'scope> JuliaException<'scope> {
+    /// Classifies `exception`.
+    ///
+    /// If the fields of a known exception type can't be accessed, e.g. because they have been
+    /// changed in the current version of Julia, `JuliaException::Other` is returned.
+    pub fn from_value(exception: Value<'scope, 'static>) -> Self {
+        Self::classify(exception).unwrap_or(JuliaException::Other(exception))
+    }
+
+    /// Returns the name of the Julia type of this exception, or `None` if it's
+    /// `JuliaException::Other`.
+    pub fn type_name(&self) -> Option<&'static str> {
+        let name = match self {
+            JuliaException::ArgumentError { .. } => "ArgumentError",
+            JuliaException::BoundsError { .. } => "BoundsError",
+            JuliaException::DimensionMismatch { .. } => "DimensionMismatch",
+            JuliaException::DivideError => "DivideError",
+            JuliaException::DomainError { .. } => "DomainError",
+            JuliaException::ErrorException { .. } => "ErrorException",
+            JuliaException::InexactError { .. } => "InexactError",
+            JuliaException::InitError { .. } => "InitError",
+            JuliaException::InterruptException => "InterruptException",
+            JuliaException::KeyError { .. } => "KeyError",
+            JuliaException::LoadError { .. } => "LoadError",
+            JuliaException::MethodError { .. } => "MethodError",
+            JuliaException::OutOfMemoryError => "OutOfMemoryError",
+            JuliaException::OverflowError { .. } => "OverflowError",
+            JuliaException::StackOverflowError => "StackOverflowError",
+            JuliaException::TypeError { .. } => "TypeError",
+            JuliaException::UndefRefError => "UndefRefError",
+            JuliaException::UndefVarError { .. } => "UndefVarError",
+            JuliaException::Other(_) => return None,
+        };
+
+        Some(name)
+    }
+
+    fn classify(exc: Value<'scope, 'static>) -> Option<Self> {
+        let unrooted = exc.unrooted_target();
+        let ty = exc.datatype();
+
+        // Safety: the fields are reachable from the exception.
+        let field = |name: &str| unsafe { Some(exc.get_field_ref(name).ok()??.as_value()) };
+        let symbol_field = |name: &str| field(name)?.cast::<Symbol>().ok();
+        let string_field = |name: &str| {
+            let s = field(name)?;
+            match s.cast::<JuliaString>() {
+                Ok(s) => s.as_str().ok().map(String::from),
+                Err(_) => s.display_string().ok(),
+            }
+        };
+
+        let exc = if ty == DataType::argumenterror_type(&unrooted) {
+            JuliaException::ArgumentError {
+                msg: string_field("msg")?,
+            }
+        } else if ty == DataType::boundserror_type(&unrooted) {
+            JuliaException::BoundsError {
+                a: field("a"),
+                i: field("i"),
+            }
+        } else if ty == DataType::errorexception_type(&unrooted) {
+            JuliaException::ErrorException {
+                msg: string_field("msg")?,
+            }
+        } else if ty == DataType::initerror_type(&unrooted) {
+            JuliaException::InitError {
+                module: symbol_field("mod")?,
+                error: field("error")?,
+            }
+        } else if ty == DataType::loaderror_type(&unrooted) {
+            JuliaException::LoadError {
+                file: string_field("file")?,
+                line: exc.field_accessor().field("line").ok()?.access().ok()?,
+                error: field("error")?,
+            }
+        } else if ty == DataType::methoderror_type(&unrooted) {
+            JuliaException::MethodError {
+                f: field("f")?,
+                args: field("args")?,
+            }
+        } else if ty == DataType::typeerror_type(&unrooted) {
+            JuliaException::TypeError {
+                func: symbol_field("func")?,
+                expected: field("expected")?,
+                got: field("got")?,
+            }
+        } else if ty == DataType::undefvarerror_type(&unrooted) {
+            JuliaException::UndefVarError {
+                var: symbol_field("var")?,
+            }
+        } else if ty
+            == inline_static_ref!(
+                DIMENSION_MISMATCH,
+                DataType,
+                "Base.DimensionMismatch",
+                unrooted
+            )
+        {
+            JuliaException::DimensionMismatch {
+                msg: string_field("msg")?,
+            }
+        } else if ty == inline_static_ref!(DIVIDE_ERROR, DataType, "Core.DivideError", unrooted) {
+            JuliaException::DivideError
+        } else if ty == inline_static_ref!(DOMAIN_ERROR, DataType, "Core.DomainError", unrooted) {
+            JuliaException::DomainError {
+                val: field("val")?,
+                msg: string_field("msg"),
+            }
+        } else if ty == inline_static_ref!(INEXACT_ERROR, DataType, "Core.InexactError", unrooted) {
+            JuliaException::InexactError {
+                func: symbol_field("func")?,
+                ty: field("T")?,
+                val: field("val")?,
+            }
+        } else if ty
+            == inline_static_ref!(
+                INTERRUPT_EXCEPTION,
+                DataType,
+                "Core.InterruptException",
+                unrooted
+            )
+        {
+            JuliaException::InterruptException
+        } else if ty == inline_static_ref!(KEY_ERROR, DataType, "Base.KeyError", unrooted) {
+            JuliaException::KeyError { key: field("key")? }
+        } else if ty
+            == inline_static_ref!(
+                OUT_OF_MEMORY_ERROR,
+                DataType,
+                "Core.OutOfMemoryError",
+                unrooted
+            )
+        {
+            JuliaException::OutOfMemoryError
+        } else if ty == inline_static_ref!(OVERFLOW_ERROR, DataType, "Core.OverflowError", unrooted)
+        {
+            JuliaException::OverflowError {
+                msg: string_field("msg")?,
+            }
+        } else if ty
+            == inline_static_ref!(
+                STACK_OVERFLOW_ERROR,
+                DataType,
+                "Core.StackOverflowError",
+                unrooted
+            )
+        {
+            JuliaException::StackOverflowError
+        } else if ty
+            == inline_static_ref!(UNDEF_REF_ERROR, DataType, "Core.UndefRefError", unrooted)
+        {
+            JuliaException::UndefRefError
+        } else {
+            return None;
+        };
+
+        Some(exc)
+    }
+}
+
 /// Julia exception converted to a string.
+///
+/// If the exception has been converted from a Julia exception with [`Exception::from_value`],
+/// e.g. by calling [`IntoJlrsResult::into_jlrs_result`], the name of its type is available too.
+/// Its fields and backtrace are only captured by [`Exception::from_value_with_details`] and
+/// [`IntoJlrsResult::into_jlrs_result_with_details`].
+///
+/// [`IntoJlrsResult::into_jlrs_result`]: crate::convert::into_jlrs_result::IntoJlrsResult::into_jlrs_result
+/// [`IntoJlrsResult::into_jlrs_result_with_details`]: crate::convert::into_jlrs_result::IntoJlrsResult::into_jlrs_result_with_details
 #[derive(Debug, Error, Clone)]
 #[error("{msg}")]
 pub struct Exception {
     msg: String,
+    type_name: Option<String>,
+    fields: Vec<ExceptionField>,
+    backtrace: Vec<BacktraceFrame>,
 }
 
 impl Exception {
+    /// Converts a Julia exception to an `Exception`.
+    ///
+    /// The error message is the string shown by `Base.showerror`. The fields and backtrace of
+    /// the exception aren't captured, use [`Exception::from_value_with_details`] if you need them.
+    pub fn from_value(exception: Value) -> Self {
+        let type_name = exception.datatype().display_string_or(CANNOT_DISPLAY_TYPE);
+        let msg = exception.error_string_or(CANNOT_DISPLAY_VALUE);
+
+        Exception {
+            msg,
+            type_name: Some(type_name),
+            fields: Vec::new(),
+            backtrace: Vec::new(),
+        }
+    }
+
+    /// Converts a Julia exception to an `Exception`, capturing its fields and backtrace.
+    ///
+    /// In addition to what [`Exception::from_value`] captures, the value of every field is
+    /// converted to the string shown by `Base.show`. If `exception` is the exception that is
+    /// currently being handled, i.e. if no other exception has been caught since it was caught,
+    /// the frames of its backtrace are captured too. Symbolicating the backtrace is expensive,
+    /// so this method should only be used if the details are actually needed.
+    pub fn from_value_with_details(exception: Value) -> Self {
+        // Capture the backtrace first, the other steps call into Julia.
+        let backtrace = capture_backtrace(exception);
+        let fields = capture_fields(exception);
+
+        Exception {
+            fields,
+            backtrace,
+            ..Exception::from_value(exception)
+        }
+    }
+
+    /// Returns a reference to the error message.
+    pub fn get_message(&self) -> &str {
+        &self.msg
+    }
+
+    /// Returns the name of the type of the exception, e.g. `"BoundsError"`.
+    ///
+    /// Returns `None` if the exception wasn't converted from a Julia exception.
+    pub fn type_name(&self) -> Option<&str> {
+        self.type_name.as_deref()
+    }
+
+    /// Returns the fields of the exception.
+    ///
+    /// The fields are only captured by [`Exception::from_value_with_details`].
+    pub fn fields(&self) -> &[ExceptionField] {
+        &self.fields
+    }
+
+    /// Returns the field named `name` if it exists.
+    pub fn field(&self, name: &str) -> Option<&ExceptionField> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Returns the frames of the Julia backtrace, the innermost frame comes first.
+    ///
+    /// The backtrace is only captured by [`Exception::from_value_with_details`], it's empty if it
+    /// couldn't be captured.
+    pub fn backtrace(&self) -> &[BacktraceFrame] {
+        &self.backtrace
+    }
+}
+
+/// A field of an [`Exception`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExceptionField {
+    /// The name of the field.
+    pub name: String,
+    /// The name of the type of the value of the field.
+    pub type_name: String,
+    /// The value of the field converted to a string with `Base.show`, `#undef` if the field is
+    /// undefined.
+    pub value: String,
+}
+
+/// A frame of the Julia backtrace of an [`Exception`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BacktraceFrame {
+    /// The name of the function.
+    pub function: String,
+    /// The file that defines the function.
+    pub file: String,
+    /// The line number.
+    pub line: isize,
+    /// Whether the frame has been inlined.
+    pub inlined: bool,
+    /// Whether the frame is a C or Rust function.
+    pub from_c: bool,
+}
+
+impl Display for BacktraceFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{} at {}:{}", self.function, self.file, self.line)?;
+        if self.inlined {
+            write!(f, " [inlined]")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A syntax error reported by the Julia parser.
+///
+/// The line and column are only available if the parser reports them, the column is only
+/// reported since Julia 1.10.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("{msg}")]
+pub struct ParseError {
+    msg: String,
+    line: Option<usize>,
+    column: Option<usize>,
+}
+
+impl ParseError {
+    // Converts a thrown `Meta.ParseError` or the argument of an `:error` or `:incomplete`
+    // expression to a `ParseError`. If the location can't be extracted from the error, `line` is
+    // used instead.
+    pub(crate) fn from_value(error: Value, line: Option<usize>) -> Self {
+        let (msg, detail) = if let Ok(msg) = error.cast::<JuliaString>() {
+            (msg.as_str().unwrap_or(CANNOT_DISPLAY_VALUE).into(), None)
+        } else if let Some(msg) = julia_string_field(error, "msg") {
+            // Safety: the detail is referenced by the error.
+            let detail = error
+                .get_field_ref("detail")
+                .ok()
+                .flatten()
+                .map(|d| unsafe { d.as_value() });
+            (msg, detail)
+        } else {
+            (error.error_string_or(CANNOT_DISPLAY_VALUE), Some(error))
+        };
+
+        let location = detail.and_then(parse_error_location);
+        ParseError {
+            msg,
+            line: location.map(|l| l.0).or(line),
+            column: location.map(|l| l.1),
+        }
+    }
+
     /// Returns a reference to the error message.
     pub fn get_message(&self) -> &str {
         &self.msg
     }
+
+    /// Returns the line of the syntax error, the first line is line 1.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Returns the column of the syntax error, the first column is column 1.
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+}
+
+fn julia_string_field(value: Value, name: &str) -> Option<String> {
+    // Safety: the field is referenced by value.
+    let field = unsafe { value.get_field_ref(name).ok()??.as_value() };
+    let s = field.cast::<JuliaString>().ok()?;
+    s.as_str().ok().map(Into::into)
+}
+
+// Since Julia 1.10 the detail of a parse error is a `JuliaSyntax.ParseError`, its first
+// diagnostic contains the byte offset of the error which can be converted to a line and column.
+fn parse_error_location(detail: Value) -> Option<(usize, usize)> {
+    // Safety: all data is rooted while it's used.
+    unsafe {
+        weak_handle_unchecked!().local_scope::<_, 2>(|mut frame| {
+            let source = detail.get_field_ref("source").ok()??.as_value();
+            let diagnostics = detail
+                .get_field_ref("diagnostics")
+                .ok()??
+                .as_value()
+                .cast::<Array>()
+                .ok()?;
+
+            if diagnostics.length() == 0 {
+                return None;
+            }
+
+            let diagnostic = diagnostics
+                .indeterminate_data()
+                .get_value(&mut frame, [0])?
+                .ok()?;
+            let first_byte = diagnostic
+                .field_accessor()
+                .field("first_byte")
+                .ok()?
+                .access::<isize>()
+                .ok()?;
+
+            let first_byte = Value::new(&mut frame, first_byte);
+            let location = Module::typed_global_cached::<Value, _, _>(
+                &frame,
+                "Base.JuliaSyntax.source_location",
+            )
+            .ok()?
+            .call2(&frame, source, first_byte)
+            .ok()?
+            .as_value();
+
+            let line = location
+                .field_accessor()
+                .field(0)
+                .ok()?
+                .access::<isize>()
+                .ok()?;
+            let column = location
+                .field_accessor()
+                .field(1)
+                .ok()?
+                .access::<isize>()
+                .ok()?;
+
+            Some((line as usize, column as usize))
+        })
+    }
+}
+
+fn capture_fields(exception: Value) -> Vec<ExceptionField> {
+    let names = exception.field_names();
+    let mut fields = Vec::with_capacity(names.len());
+
+    for (idx, name) in names.iter().enumerate() {
+        let name = name.as_string().unwrap_or_else(|_| idx.to_string());
+
+        // Safety: the field is rooted while it's converted to a string.
+        let field = unsafe {
+            weak_handle_unchecked!().local_scope::<_, 1>(|mut frame| {
+                exception.get_nth_field(&mut frame, idx).ok().map(|v| {
+                    let type_name = v.datatype().display_string_or(CANNOT_DISPLAY_TYPE);
+                    let value = v.display_string_or(CANNOT_DISPLAY_VALUE);
+                    (type_name, value)
+                })
+            })
+        };
+
+        let (type_name, value) = field.unwrap_or_else(|| (String::new(), "#undef".into()));
+        fields.push(ExceptionField {
+            name,
+            type_name,
+            value,
+        });
+    }
+
+    fields
+}
+
+fn capture_backtrace(exception: Value) -> Vec<BacktraceFrame> {
+    // Safety: the current exception is only compared to the exception, all data is rooted
+    // while it's used.
+    unsafe {
+        let current = current_exception();
+        if current.is_null() || current != exception.unwrap(Private) {
+            return Vec::new();
+        }
+
+        weak_handle_unchecked!()
+            .local_scope::<_, 2>(|mut frame| {
+                let base = Module::base(&frame);
+                let bt = base
+                    .global(&frame, "catch_backtrace")
+                    .ok()?
+                    .as_value()
+                    .call0(&mut frame)
+                    .ok()?;
+                let frames = base
+                    .global(&frame, "stacktrace")
+                    .ok()?
+                    .as_value()
+                    .call1(&mut frame, bt)
+                    .ok()?
+                    .cast::<Array>()
+                    .ok()?;
+
+                let data = frames.indeterminate_data();
+                let n = frames.length();
+                let mut backtrace = Vec::with_capacity(n);
+                for i in 0..n {
+                    let bt_frame = frame.local_scope::<_, 1>(|mut frame| {
+                        let sf = data.get_value(&mut frame, [i])?.ok()?;
+                        backtrace_frame(sf).ok()
+                    });
+
+                    if let Some(bt_frame) = bt_frame {
+                        backtrace.push(bt_frame);
+                    }
+                }
+
+                Some(backtrace)
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn backtrace_frame(sf: Value) -> JlrsResult<BacktraceFrame> {
+    let function = sf.field_accessor().field("func")?.access::<SymbolRef>()?;
+    let file = sf.field_accessor().field("file")?.access::<SymbolRef>()?;
+    let line = sf.field_accessor().field("line")?.access::<isize>()?;
+    let inlined = sf.field_accessor().field("inlined")?.access::<bool>()?;
+    let from_c = sf.field_accessor().field("from_c")?.access::<bool>()?;
+
+    // Safety: symbols are globally rooted.
+    unsafe {
+        Ok(BacktraceFrame {
+            function: function.as_managed().as_string()?,
+            file: file.as_managed().as_string()?,
+            line,
+            inlined,
+            from_c,
+        })
+    }
+}
+
+#[julia_version(until = "1.10")]
+unsafe fn current_exception() -> *mut jl_value_t {
+    jl_sys::jl_current_exception()
+}
+
+#[julia_version(since = "1.11")]
+unsafe fn current_exception() -> *mut jl_value_t {
+    jl_sys::jl_current_exception(jl_sys::jlrs_current_task())
 }
 
 /// All different errors.
@@ -362,6 +987,10 @@ pub enum JlrsError {
     InstantiationError(InstantiationError),
     #[error("Array layout error: {0}")]
     ArrayLayoutError(ArrayLayoutError),
+    #[error("Parse error: {0}")]
+    ParseError(ParseError),
+    #[error("Pkg error: {0}")]
+    PkgError(PkgError),
 }
 
 impl JlrsError {
@@ -374,7 +1003,28 @@ impl JlrsError {
     /// Convert an error message to `JlrsError::Exception`.
     #[inline]
     pub fn exception<S: Into<String>>(msg: S) -> Self {
-        JlrsError::Exception(Exception { msg: msg.into() })
+        JlrsError::Exception(Exception {
+            msg: msg.into(),
+            type_name: None,
+            fields: Vec::new(),
+            backtrace: Vec::new(),
+        })
+    }
+
+    /// Convert a Julia exception to `JlrsError::Exception`.
+    ///
+    /// See [`Exception::from_value`] for more information.
+    #[inline]
+    pub fn from_exception(exception: Value) -> Self {
+        JlrsError::Exception(Exception::from_value(exception))
+    }
+
+    /// Convert a Julia exception to `JlrsError::Exception`, capturing its fields and backtrace.
+    ///
+    /// See [`Exception::from_value_with_details`] for more information.
+    #[inline]
+    pub fn from_exception_with_details(exception: Value) -> Self {
+        JlrsError::Exception(Exception::from_value_with_details(exception))
     }
 
     /// Convert an arbitrary error to `Err(JlrsError::Other)`.
@@ -414,3 +1064,5 @@ impl_from!(IOError);
 impl_from!(AccessError);
 impl_from!(InstantiationError);
 impl_from!(ArrayLayoutError);
+impl_from!(ParseError);
+impl_from!(PkgError);
@@ -196,7 +196,9 @@
 //!
 //! - `diagnostics`
 //!
-//!   Enable custom diagnostics for several traits because the default lint is unhelpful. This feature
+//!   Enable custom diagnostics for several traits because the default lint is unhelpful, and
+//!   detect the use of unrooted data that has been freed by the GC. See the
+//!   `jlrs::memory::diagnostics` module for more information. This feature
 //!   requires Rust 1.78.
 //!
 //! - `i686`
@@ -1019,6 +1021,7 @@ use crate::{
     },
     memory::{
         context::{ledger::init_ledger, stack::Stack},
+        global_root::RootSet,
         target::unrooted::Unrooted,
     },
 };
@@ -1183,4 +1186,5 @@ pub(crate) unsafe fn init_jlrs(install_jlrs_core: &InstallJlrsCore) {
 
     init_ledger();
     Stack::init();
+    RootSet::init();
 }
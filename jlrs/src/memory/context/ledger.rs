@@ -5,13 +5,17 @@
 //
 // The ledger is not a lock, nothing prevents you from creating a copy of a `Value` that's
 // currently tracked. It does work well with opaque types, because tracking is the only way to
-// access a managed instance of an opaque type.
+// access a managed instance of an opaque type. For the same reason, instances of opaque and
+// foreign types that have been destroyed can't be borrowed.
 
 use std::ffi::c_void;
 
 use crate::{
-    data::managed::{module::Module, private::ManagedPriv, value::Value},
-    error::{JlrsError, JlrsResult},
+    data::{
+        managed::{module::Module, private::ManagedPriv, value::Value, Managed},
+        types::foreign_type::is_destroyed,
+    },
+    error::{AccessError, JlrsError, JlrsResult, CANNOT_DISPLAY_TYPE},
     gc_safe::GcSafeOnceLock,
     memory::target::unrooted::Unrooted,
     private::Private,
@@ -196,6 +200,7 @@ impl Ledger {
 
     #[inline]
     pub(crate) fn try_borrow_shared(data: Value) -> JlrsResult<bool> {
+        Self::check_not_destroyed(data)?;
         unsafe {
             match (LEDGER.get_unchecked().try_borrow_shared)(data.data_ptr().as_ptr()) {
                 LedgerResult::OkFalse => Ok(false),
@@ -207,6 +212,7 @@ impl Ledger {
 
     #[inline]
     pub(crate) fn try_borrow_exclusive(data: Value) -> JlrsResult<bool> {
+        Self::check_not_destroyed(data)?;
         unsafe {
             match (LEDGER.get_unchecked().try_borrow_exclusive)(data.data_ptr().as_ptr()) {
                 LedgerResult::OkFalse => Ok(false),
@@ -218,6 +224,7 @@ impl Ledger {
 
     #[inline]
     pub(crate) unsafe fn borrow_shared_unchecked(data: Value) -> JlrsResult<bool> {
+        Self::check_not_destroyed(data)?;
         unsafe {
             match (LEDGER.get_unchecked().borrow_shared_unchecked)(data.data_ptr().as_ptr()) {
                 LedgerResult::OkFalse => Ok(false),
@@ -227,6 +234,16 @@ impl Ledger {
         }
     }
 
+    #[inline]
+    fn check_not_destroyed(data: Value) -> JlrsResult<()> {
+        if is_destroyed(data) {
+            let value_type = data.datatype().display_string_or(CANNOT_DISPLAY_TYPE);
+            Err(AccessError::Destroyed { value_type })?;
+        }
+
+        Ok(())
+    }
+
     #[inline]
     pub(crate) unsafe fn unborrow_shared(data: Value) -> JlrsResult<bool> {
         unsafe {
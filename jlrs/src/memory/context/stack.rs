@@ -193,6 +193,19 @@ impl Stack {
         jlrs_gc_wb(self as *const _ as *mut _, root.as_ptr().cast());
     }
 
+    // Set the `n` roots starting at `offset` to null.
+    //
+    // Safety: the slots must have been reserved.
+    #[inline]
+    pub(crate) unsafe fn clear_roots(&self, offset: usize, n: usize) {
+        // We can only get here while the GC isn't running, so there are
+        // no active borrows.
+        let slots = &*self.slots.get();
+        for slot in &slots[offset..offset + n] {
+            slot.set(null_mut());
+        }
+    }
+
     // Pop roots from the stack, the new length is `offset`.
     //
     // Safety: must be called when a frame is popped from the stack.
@@ -215,6 +228,17 @@ impl Stack {
         }
     }
 
+    // Returns true if `ptr` is stored in one of the slots of the stack.
+    #[cfg(feature = "diagnostics")]
+    pub(crate) fn contains(&self, ptr: *mut c_void) -> bool {
+        unsafe {
+            // We can only get here while the GC isn't running, so there are
+            // no active borrows.
+            let slots = &*self.slots.get();
+            slots.iter().any(|slot| slot.get() == ptr)
+        }
+    }
+
     // Create a new stack and move it to Julia.
     // Safety: root after allocating
     #[inline]
@@ -0,0 +1,235 @@
+//! Detect the use of unrooted Julia data.
+//!
+//! Data returned by methods that take an [`Unrooted`] target isn't rooted, it's only guaranteed
+//! to be valid until the GC runs. Using such data after a collection without rooting it is a
+//! common source of crashes that are hard to track down because the data is typically freed long
+//! before it's used.
+//!
+//! When the `diagnostics` feature is enabled, every pointer that is returned through an
+//! `Unrooted` target is registered together with the GC epoch it was created in. Whenever a
+//! [`Ref`] is converted to managed data with [`Ref::as_managed`], [`Ref::as_value`] or
+//! [`Ref::root`], the pointer is checked: if it was registered and the GC has run since, the GC
+//! frames of the current thread are searched for a root that points to the data. If it's not
+//! rooted, [`DiagnosticKind::UnrootedAfterGc`] is reported.
+//!
+//! The pointer itself is never dereferenced, because the data might have been freed. As a result
+//! data that has survived a collection because it's reachable from some other root, e.g. a
+//! global, is reported too. Diagnostics are reported by calling the handler set with
+//! [`set_diagnostics_handler`], the default handler prints the diagnostic and a backtrace of the
+//! offending call site to stderr. These checks are expensive, this feature should only be enabled
+//! while debugging.
+//!
+//! [`Unrooted`]: crate::memory::target::unrooted::Unrooted
+//! [`Ref`]: crate::data::managed::Ref
+//! [`Ref::as_managed`]: crate::data::managed::Ref::as_managed
+//! [`Ref::as_value`]: crate::data::managed::Ref::as_value
+//! [`Ref::root`]: crate::data::managed::Ref::root
+
+use std::{
+    backtrace::Backtrace,
+    cell::{Cell, RefCell},
+    ffi::c_void,
+    fmt,
+    panic::Location,
+    ptr::NonNull,
+    sync::RwLock,
+};
+
+use fnv::FnvHashMap;
+use jl_sys::{jl_gc_total_hrtime, jl_value_t, jlrs_ppgcstack};
+
+use crate::{
+    data::managed::{private::ManagedPriv, value::Value},
+    memory::context::stack::Stack,
+    private::Private,
+};
+
+// The registry is cleared when it exceeds this number of entries to bound its memory usage.
+const MAX_REGISTERED: usize = 1 << 16;
+
+thread_local! {
+    static REGISTRY: RefCell<FnvHashMap<usize, u64>> = RefCell::new(FnvHashMap::default());
+    static REPORTING: Cell<bool> = const { Cell::new(false) };
+}
+
+static HANDLER: RwLock<fn(&RootingDiagnostic)> = RwLock::new(default_handler);
+
+/// The kind of problem that has been detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// The data was returned through an `Unrooted` target and is used after the GC has run
+    /// without being rooted.
+    UnrootedAfterGc,
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticKind::UnrootedAfterGc => {
+                write!(f, "unrooted reference has been used after the GC has run")
+            }
+        }
+    }
+}
+
+/// A detected use of unrooted Julia data.
+#[derive(Debug)]
+pub struct RootingDiagnostic {
+    kind: DiagnosticKind,
+    ptr: *mut c_void,
+    location: &'static Location<'static>,
+    backtrace: Backtrace,
+}
+
+impl RootingDiagnostic {
+    /// The kind of problem that has been detected.
+    #[inline]
+    pub fn kind(&self) -> DiagnosticKind {
+        self.kind
+    }
+
+    /// The offending pointer.
+    #[inline]
+    pub fn ptr(&self) -> *mut c_void {
+        self.ptr
+    }
+
+    /// The location where the reference was used.
+    #[inline]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// The backtrace of the offending call.
+    #[inline]
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+}
+
+impl fmt::Display for RootingDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({:p}) at {}", self.kind, self.ptr, self.location)
+    }
+}
+
+/// Set the function that is called when a diagnostic is reported.
+///
+/// The default handler prints the diagnostic and its backtrace to stderr. The handler must not
+/// call into Julia.
+pub fn set_diagnostics_handler(handler: fn(&RootingDiagnostic)) {
+    *HANDLER.write().expect("poisoned") = handler;
+}
+
+fn default_handler(diagnostic: &RootingDiagnostic) {
+    eprintln!(
+        "jlrs rooting diagnostic: {diagnostic}\nbacktrace:\n{}",
+        diagnostic.backtrace
+    );
+}
+
+// Register data returned through an `Unrooted` target.
+#[inline]
+pub(crate) fn register<T>(ptr: NonNull<T>) {
+    let epoch = unsafe { jl_gc_total_hrtime() };
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        if registry.len() >= MAX_REGISTERED {
+            registry.clear();
+        }
+
+        registry.insert(ptr.as_ptr() as usize, epoch);
+    })
+}
+
+// Check that `ptr` hasn't been returned through an `Unrooted` target before the GC last ran
+// unless it's rooted, report a diagnostic if it has. `ptr` is never dereferenced.
+//
+// Safety: must be called from a thread that can call into Julia.
+pub(crate) unsafe fn check<T>(ptr: NonNull<T>, location: &'static Location<'static>) {
+    // Don't check pointers while a diagnostic is being reported.
+    if REPORTING.get() {
+        return;
+    }
+
+    let ptr = ptr.cast::<c_void>().as_ptr();
+    let Some(epoch) = REGISTRY.with(|registry| registry.borrow().get(&(ptr as usize)).copied())
+    else {
+        return;
+    };
+
+    if epoch == jl_gc_total_hrtime() || is_rooted(ptr) {
+        return;
+    }
+
+    // Every unrooted use is reported only once.
+    REGISTRY.with(|registry| registry.borrow_mut().remove(&(ptr as usize)));
+    report(DiagnosticKind::UnrootedAfterGc, ptr, location)
+}
+
+#[cold]
+#[inline(never)]
+fn report(kind: DiagnosticKind, ptr: *mut c_void, location: &'static Location<'static>) {
+    REPORTING.set(true);
+    let diagnostic = RootingDiagnostic {
+        kind,
+        ptr,
+        location,
+        backtrace: Backtrace::force_capture(),
+    };
+
+    let handler = *HANDLER.read().expect("poisoned");
+    handler(&diagnostic);
+    REPORTING.set(false);
+}
+
+// Mirrors the layout of `jl_gcframe_t`, its fields are private.
+#[repr(C)]
+struct RawFrame {
+    n_roots: usize,
+    prev: *mut RawFrame,
+}
+
+// Returns true if `ptr` is rooted in a GC frame of the current thread, or in a stack of roots
+// used by jlrs that is rooted in such a frame. Only the roots are dereferenced, not `ptr`.
+unsafe fn is_rooted(ptr: *mut c_void) -> bool {
+    let pgcstack = jlrs_ppgcstack();
+    if pgcstack.is_null() {
+        return false;
+    }
+
+    let mut frame = (*pgcstack).cast::<RawFrame>();
+    while !frame.is_null() {
+        let n_roots = (*frame).n_roots >> 2;
+        let indirect = (*frame).n_roots & 1 == 1;
+        let roots = frame.add(1).cast::<*mut c_void>();
+
+        for i in 0..n_roots {
+            let mut root = roots.add(i).read();
+            if indirect && !root.is_null() {
+                root = root.cast::<*mut c_void>().read();
+            }
+
+            if root.is_null() {
+                continue;
+            }
+
+            if root == ptr || stack_contains(root, ptr) {
+                return true;
+            }
+        }
+
+        frame = (*frame).prev;
+    }
+
+    false
+}
+
+unsafe fn stack_contains(root: *mut c_void, ptr: *mut c_void) -> bool {
+    let value = Value::wrap_non_null(NonNull::new_unchecked(root.cast::<jl_value_t>()), Private);
+    if !value.is::<Stack>() {
+        return false;
+    }
+
+    value.data_ptr().cast::<Stack>().as_ref().contains(ptr)
+}
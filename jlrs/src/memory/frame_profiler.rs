@@ -0,0 +1,211 @@
+//! Profile the number of roots used by scopes.
+//!
+//! The size of a [`LocalGcFrame`] must be chosen when the local scope is created, while a
+//! [`GcFrame`] can grow dynamically at the cost of some overhead. To choose these sizes, the
+//! frame profiler can record the maximum number of roots that are used by the frame of every
+//! `local_scope` and `scope` call site:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::memory::frame_profiler::{enable_frame_profiler, frame_profile, Provisioning};
+//! # fn main() {
+//! # let mut julia = Builder::new().start_local().unwrap();
+//! enable_frame_profiler(true);
+//!
+//! julia.local_scope::<_, 3>(|mut frame| {
+//!     let _ = Value::new(&mut frame, 1usize);
+//! });
+//!
+//! enable_frame_profiler(false);
+//!
+//! # #[cfg(debug_assertions)]
+//! # {
+//! let profile = frame_profile();
+//! assert_eq!(profile[0].max_roots(), 1);
+//! assert_eq!(profile[0].suggested_size(), 1);
+//! assert_eq!(profile[0].provisioning(), Provisioning::Over { unused: 2 });
+//! # }
+//! # }
+//! ```
+//!
+//! The number of roots is recorded when the scope is left. For local frames this is the number
+//! of slots that have been reserved, e.g. a `LocalOutput` is counted even if it's never used.
+//! Roots used by nested scopes are attributed to those scopes. [`report_frame_profile`] prints a
+//! warning for every local scope whose frame has more slots than it needed or used all of them,
+//! and suggests a size for every dynamically-sized scope.
+//!
+//! The profiler is only available in debug builds, in release builds nothing is recorded.
+//!
+//! [`LocalGcFrame`]: crate::memory::target::frame::LocalGcFrame
+//! [`GcFrame`]: crate::memory::target::frame::GcFrame
+
+use std::{
+    fmt,
+    panic::Location,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use fnv::FnvHashMap;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static PROFILE: Lazy<Mutex<FnvHashMap<&'static Location<'static>, ScopeProfile>>> =
+    Lazy::new(|| Mutex::new(FnvHashMap::default()));
+
+/// The kind of frame used by a scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameKind {
+    /// A `LocalGcFrame` with the given capacity.
+    Local(usize),
+    /// A dynamically-sized `GcFrame`.
+    Dynamic,
+}
+
+/// Whether the frame of a scope has the right size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provisioning {
+    /// The local frame had exactly as many slots as it needed. Every slot was used, so adding
+    /// another root to this scope will overflow the frame.
+    Exact,
+    /// The local frame had `unused` slots that were never used.
+    Over { unused: usize },
+    /// The frame is dynamically-sized, `suggested` is the size of a `LocalGcFrame` that could
+    /// replace it.
+    Dynamic { suggested: usize },
+}
+
+/// The number of roots used by the frames of a single scope call site.
+#[derive(Debug, Clone)]
+pub struct ScopeProfile {
+    location: &'static Location<'static>,
+    kind: FrameKind,
+    n_calls: u64,
+    max_roots: usize,
+}
+
+impl ScopeProfile {
+    /// The location of the call that created the scope.
+    #[inline]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// The kind of frame used by the scope.
+    #[inline]
+    pub fn kind(&self) -> FrameKind {
+        self.kind
+    }
+
+    /// The number of times the scope has been created.
+    #[inline]
+    pub fn n_calls(&self) -> u64 {
+        self.n_calls
+    }
+
+    /// The maximum number of roots used by the frame.
+    #[inline]
+    pub fn max_roots(&self) -> usize {
+        self.max_roots
+    }
+
+    /// The suggested size of a `LocalGcFrame` for this scope, i.e. the maximum number of roots
+    /// that have been used.
+    #[inline]
+    pub fn suggested_size(&self) -> usize {
+        self.max_roots
+    }
+
+    /// Whether the frame of this scope has the right size.
+    pub fn provisioning(&self) -> Provisioning {
+        match self.kind {
+            FrameKind::Local(n) if self.max_roots < n => Provisioning::Over {
+                unused: n - self.max_roots,
+            },
+            FrameKind::Local(_) => Provisioning::Exact,
+            FrameKind::Dynamic => Provisioning::Dynamic {
+                suggested: self.suggested_size(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for ScopeProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.provisioning() {
+            Provisioning::Exact => write!(
+                f,
+                "{}: local scope uses all {} slots of its frame, adding a root will overflow it",
+                self.location, self.max_roots
+            ),
+            Provisioning::Over { unused } => write!(
+                f,
+                "{}: local scope never used {} of its {} slots, consider using local_scope::<_, {}>",
+                self.location,
+                unused,
+                self.max_roots + unused,
+                self.suggested_size()
+            ),
+            Provisioning::Dynamic { suggested } => write!(
+                f,
+                "{}: scope used at most {} roots, consider using local_scope::<_, {}>",
+                self.location, self.max_roots, suggested
+            ),
+        }
+    }
+}
+
+/// Enable or disable the frame profiler.
+pub fn enable_frame_profiler(on: bool) {
+    ENABLED.store(on, Ordering::Relaxed);
+}
+
+/// Returns the profiles of all scopes that have been recorded, sorted by location.
+pub fn frame_profile() -> Vec<ScopeProfile> {
+    let mut profile = PROFILE.lock().values().cloned().collect::<Vec<_>>();
+    profile.sort_by_key(|p| (p.location.file(), p.location.line(), p.location.column()));
+    profile
+}
+
+/// Remove all recorded profiles.
+pub fn reset_frame_profile() {
+    PROFILE.lock().clear()
+}
+
+/// Print every recorded scope to stderr, with a warning if its local frame has unused slots or
+/// is full, or a suggested frame size if it's dynamically-sized.
+pub fn report_frame_profile() {
+    for profile in frame_profile() {
+        eprintln!("jlrs frame profiler: {profile}");
+    }
+}
+
+// Returns `true` if the profiler is enabled.
+#[cfg(debug_assertions)]
+#[inline]
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+// Record that the scope created at `location` used `n_roots` roots.
+#[cfg(debug_assertions)]
+#[inline]
+pub(crate) fn record(location: &'static Location<'static>, kind: FrameKind, n_roots: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    PROFILE
+        .lock()
+        .entry(location)
+        .and_modify(|profile| {
+            profile.n_calls += 1;
+            profile.max_roots = profile.max_roots.max(n_roots);
+        })
+        .or_insert(ScopeProfile {
+            location,
+            kind,
+            n_calls: 1,
+            max_roots: n_roots,
+        });
+}
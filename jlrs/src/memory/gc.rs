@@ -1,10 +1,16 @@
 //! Manage the garbage collector.
 
+use std::ffi::c_void;
+
+#[julia_version(since = "1.9")]
+use jl_sys::jl_gc_set_max_memory;
 pub use jl_sys::GcCollection;
 use jl_sys::{
-    jl_gc_collect, jl_gc_collection_t, jl_gc_enable, jl_gc_is_enabled, jl_gc_mark_queue_obj,
-    jl_gc_mark_queue_objarray, jl_gc_safepoint, jlrs_gc_safe_enter, jlrs_gc_safe_leave,
-    jlrs_gc_unsafe_enter, jlrs_gc_unsafe_leave, jlrs_gc_wb, jlrs_ppgcstack,
+    jl_gc_collect, jl_gc_collection_t, jl_gc_counted_free_with_size, jl_gc_counted_malloc,
+    jl_gc_counted_realloc_with_old_size, jl_gc_enable, jl_gc_is_enabled, jl_gc_live_bytes,
+    jl_gc_mark_queue_obj, jl_gc_mark_queue_objarray, jl_gc_safepoint, jl_gc_total_hrtime,
+    jlrs_gc_safe_enter, jlrs_gc_safe_leave, jlrs_gc_unsafe_enter, jlrs_gc_unsafe_leave, jlrs_gc_wb,
+    jlrs_ppgcstack,
 };
 use jlrs_macros::julia_version;
 
@@ -15,10 +21,11 @@ use super::{
 };
 #[cfg(feature = "local-rt")]
 use crate::runtime::sync_rt::Julia;
-#[julia_version(since = "1.7")]
-use crate::{call::Call, data::managed::module::Module};
 use crate::{
+    call::Call,
+    convert::into_jlrs_result::IntoJlrsResult,
     data::managed::{
+        module::Module,
         private::ManagedPriv,
         value::{Value, ValueRef},
     },
@@ -98,6 +105,24 @@ pub trait Gc: private::GcPriv {
         }
     }
 
+    /// Returns a snapshot of the GC's statistics.
+    fn gc_stats(&self) -> GcStats {
+        // Safety: this function can only be called while Julia is active from a thread known to
+        // Julia.
+        unsafe { GcStats::new() }
+    }
+
+    #[julia_version(since = "1.9")]
+    /// Set a hint for the maximum size of the heap in bytes, the GC collects more aggressively
+    /// when the heap grows beyond this size. This is equivalent to starting Julia with the
+    /// `--heap-size-hint` option.
+    #[inline]
+    fn gc_set_heap_size_hint(&self, bytes: u64) {
+        // Safety: this function can only be called while Julia is active from a thread known to
+        // Julia.
+        unsafe { jl_gc_set_max_memory(bytes) }
+    }
+
     /// Insert a safepoint, a point where the garbage collector may run.
     #[inline]
     fn gc_safepoint(&self) {
@@ -283,6 +308,120 @@ pub(crate) unsafe fn gc_unsafe_with<F: for<'scope> FnOnce(Unrooted<'scope>) -> T
     res
 }
 
+/// A snapshot of the GC's statistics, returned by [`Gc::gc_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GcStats {
+    /// The number of bytes that have been allocated since the last collection.
+    pub allocated_bytes: i64,
+    /// The number of bytes that have been freed.
+    pub freed_bytes: i64,
+    /// The total number of bytes that have been allocated.
+    pub total_allocated_bytes: u64,
+    /// The number of calls to `malloc`.
+    pub malloc_calls: u64,
+    /// The number of calls to `realloc`.
+    pub realloc_calls: u64,
+    /// The number of objects that have been allocated in pools.
+    pub pool_allocs: u64,
+    /// The number of objects that are too large to be allocated in a pool.
+    pub big_allocs: u64,
+    /// The number of calls to `free`.
+    pub free_calls: u64,
+    /// The number of collections.
+    pub collections: u64,
+    /// The number of full collections.
+    pub full_collections: u64,
+    /// The longest pause in nanoseconds, `None` if it isn't tracked by this version of Julia.
+    pub max_pause_ns: Option<u64>,
+    /// The size of the heap the GC tries to stay below, `None` if it isn't tracked by this
+    /// version of Julia.
+    pub max_memory: Option<u64>,
+    /// The number of bytes that are currently live.
+    pub live_bytes: i64,
+    /// The total time spent in the GC in nanoseconds.
+    pub total_time_ns: u64,
+}
+
+impl GcStats {
+    // Reads the fields of `Base.gc_num()` by name, its layout depends on the version of Julia.
+    unsafe fn new() -> Self {
+        let unrooted = Unrooted::new();
+        let mut stats = GcStats {
+            live_bytes: jl_gc_live_bytes(),
+            total_time_ns: jl_gc_total_hrtime(),
+            ..Default::default()
+        };
+
+        unrooted.with_local_scope::<_, _, 1>(|target, mut frame| {
+            let Ok(num) = Module::typed_global_cached::<Value, _, _>(&target, "Base.gc_num")
+                .and_then(|f| f.call0(&mut frame).into_jlrs_result())
+            else {
+                return;
+            };
+
+            let field = |name: &str| gc_num_field(num, name);
+            stats.allocated_bytes = field("allocd").unwrap_or_default() as i64;
+            stats.freed_bytes = field("freed").unwrap_or_default() as i64;
+            stats.total_allocated_bytes = field("total_allocd").unwrap_or_default();
+            stats.malloc_calls = field("malloc").unwrap_or_default();
+            stats.realloc_calls = field("realloc").unwrap_or_default();
+            stats.pool_allocs = field("poolalloc").unwrap_or_default();
+            stats.big_allocs = field("bigalloc").unwrap_or_default();
+            stats.free_calls = field("freecall").unwrap_or_default();
+            stats.collections = field("pause").unwrap_or_default();
+            stats.full_collections = field("full_sweep").unwrap_or_default();
+            stats.max_pause_ns = field("max_pause");
+            stats.max_memory = field("max_memory");
+        });
+
+        stats
+    }
+}
+
+fn gc_num_field(num: Value, name: &str) -> Option<u64> {
+    let field = || num.field_accessor().field(name).ok();
+    if let Ok(v) = field()?.access::<i64>() {
+        return Some(v as u64);
+    }
+    if let Ok(v) = field()?.access::<u64>() {
+        return Some(v);
+    }
+
+    field()?.access::<i32>().ok().map(|v| v as u64)
+}
+
+/// Allocate `size` bytes with `malloc` and report the allocation to the GC.
+///
+/// Memory that is owned by a foreign type is invisible to the GC, which can cause it to
+/// underestimate the memory pressure if foreign objects own large buffers. By allocating these
+/// buffers with this function, the GC takes them into account when deciding if it should
+/// collect. A null pointer is returned if the allocation fails.
+///
+/// Safety: must be called from a thread known to Julia. The allocation must be freed with
+/// [`counted_free`] with the same size.
+#[inline]
+pub unsafe fn counted_malloc(size: usize) -> *mut c_void {
+    jl_gc_counted_malloc(size)
+}
+
+/// Resize an allocation made with [`counted_malloc`] and report the change in size to the GC.
+///
+/// Safety: must be called from a thread known to Julia. `ptr` must have been allocated with
+/// [`counted_malloc`] or [`counted_realloc`], `old_size` must be its current size.
+#[inline]
+pub unsafe fn counted_realloc(ptr: *mut c_void, old_size: usize, new_size: usize) -> *mut c_void {
+    jl_gc_counted_realloc_with_old_size(ptr, old_size, new_size)
+}
+
+/// Free an allocation made with [`counted_malloc`] and report it to the GC.
+///
+/// Safety: must be called from a thread known to Julia. `ptr` must have been allocated with
+/// [`counted_malloc`] or [`counted_realloc`], `size` must be its current size.
+#[inline]
+pub unsafe fn counted_free(ptr: *mut c_void, size: usize) {
+    jl_gc_counted_free_with_size(ptr, size)
+}
+
 #[cfg(feature = "local-rt")]
 impl Gc for Julia<'_> {}
 impl<'frame, Tgt: Target<'frame>> Gc for Tgt {}
@@ -0,0 +1,261 @@
+//! Long-lived roots that aren't tied to a scope.
+//!
+//! Data rooted in a frame can't outlive the scope of that frame. A [`GlobalRoot`] roots managed
+//! data until it's dropped, which makes it possible to store Julia data in Rust data structures
+//! that outlive any scope, e.g. a cache owned by the application:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::memory::global_root::GlobalRoot;
+//! # fn main() {
+//! # let mut julia = Builder::new().start_local().unwrap();
+//! let root = julia.local_scope::<_, 1>(|mut frame| {
+//!     let value = Value::new(&mut frame, 1usize);
+//!     GlobalRoot::new(value)
+//! });
+//!
+//! julia.local_scope::<_, 0>(|_| {
+//!     let value = root.get().unwrap();
+//!     assert_eq!(value.unbox::<usize>().unwrap(), 1);
+//! });
+//! # }
+//! ```
+//!
+//! All global roots are stored in a single root set that is referenced by a constant in the
+//! `JlrsCore` module, and marked by the GC like any other foreign type. A `GlobalRoot` can be sent
+//! to and dropped on any thread, but the data it roots can only be accessed from a thread that can
+//! call into Julia.
+
+use std::{
+    ffi::c_void,
+    fmt,
+    marker::PhantomData,
+    ptr::{null_mut, NonNull},
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use jl_sys::{jl_tagged_gensym, jlrs_gc_wb, jlrs_task_gc_state};
+use parking_lot::Mutex;
+
+use crate::{
+    data::{
+        managed::{
+            module::Module,
+            private::ManagedPriv,
+            symbol::Symbol,
+            value::{Value, ValueRef},
+            Managed,
+        },
+        types::foreign_type::{create_foreign_type_nostack, ForeignType},
+    },
+    error::{JlrsResult, RuntimeError},
+    memory::{
+        scope::LocalScope,
+        target::{unrooted::Unrooted, Target},
+        PTls,
+    },
+    private::Private,
+    runtime::state::GC_UNSAFE,
+};
+
+static ROOT_SET: AtomicPtr<RootSet> = AtomicPtr::new(null_mut());
+
+/// Managed data that is rooted until this root is dropped.
+///
+/// See the [module-level docs] for more information.
+///
+/// [module-level docs]: self
+pub struct GlobalRoot<T> {
+    ptr: NonNull<c_void>,
+    slot: usize,
+    _marker: PhantomData<T>,
+}
+
+// Safety: the rooted data can only be accessed from a thread that can call into Julia, dropping
+// a root only updates the root set while holding its lock.
+unsafe impl<T> Send for GlobalRoot<T> {}
+unsafe impl<T> Sync for GlobalRoot<T> {}
+
+impl<T> GlobalRoot<T>
+where
+    T: Managed<'static, 'static>,
+{
+    /// Root `data` until the returned root is dropped.
+    pub fn new<'scope, M>(data: M) -> Self
+    where
+        M: Managed<'scope, 'static, InScope<'static> = T>,
+    {
+        let ptr = data.unwrap_non_null(Private).cast();
+        // Safety: the root set has been initialized when jlrs was initialized, `data` is valid
+        // managed data so this method is called from a thread that can call into Julia.
+        let slot = unsafe { RootSet::get().push_root(ptr) };
+
+        GlobalRoot {
+            ptr,
+            slot,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Access the rooted data.
+    ///
+    /// Returns `RuntimeError::InvalidThread` if the current thread is unknown to Julia, and
+    /// `RuntimeError::IncorrectState` if it can't call into Julia.
+    #[inline]
+    pub fn get(&self) -> JlrsResult<T::InScope<'_>> {
+        match unsafe { jlrs_task_gc_state() } {
+            GC_UNSAFE => Ok(unsafe { self.get_unchecked() }),
+            -1 => Err(RuntimeError::InvalidThread)?,
+            _ => Err(RuntimeError::IncorrectState)?,
+        }
+    }
+
+    /// Access the rooted data without checking if the current thread can call into Julia.
+    ///
+    /// Safety: this method must only be called from a thread that can call into Julia.
+    #[inline]
+    pub unsafe fn get_unchecked(&self) -> T::InScope<'_> {
+        <T::InScope<'_> as ManagedPriv>::wrap_non_null(self.ptr.cast(), Private)
+    }
+
+    /// Root the data in `target`.
+    #[inline]
+    pub fn root<'target, Tgt>(&self, target: Tgt) -> Tgt::Data<'static, T::InScope<'target>>
+    where
+        Tgt: Target<'target>,
+    {
+        // Safety: the data is rooted, a target only exists on threads that can call into Julia.
+        unsafe { target.data_from_ptr(self.ptr.cast(), Private) }
+    }
+
+    #[inline]
+    pub(crate) fn ptr(&self) -> NonNull<c_void> {
+        self.ptr
+    }
+}
+
+impl<T> fmt::Debug for GlobalRoot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalRoot")
+            .field("ptr", &self.ptr)
+            .field("slot", &self.slot)
+            .finish()
+    }
+}
+
+impl<T> Drop for GlobalRoot<T> {
+    fn drop(&mut self) {
+        // Safety: the root set has been initialized when jlrs was initialized.
+        unsafe { RootSet::get().pop_root(self.slot) }
+    }
+}
+
+#[derive(Default)]
+struct RootSlots {
+    roots: Vec<*mut c_void>,
+    free: Vec<usize>,
+}
+
+// The set of all global roots. It's a foreign type so the GC calls its mark function, and a
+// single instance is referenced by a constant in the JlrsCore module.
+#[derive(Default)]
+pub(crate) struct RootSet {
+    slots: Mutex<RootSlots>,
+}
+
+// The slots are only accessed while holding the lock.
+unsafe impl Send for RootSet {}
+unsafe impl Sync for RootSet {}
+
+unsafe impl ForeignType for RootSet {
+    fn mark(ptls: PTls, data: &Self) -> usize {
+        // Threads that can call into Julia can't hold the lock while the GC is running, other
+        // threads can only hold it briefly while a root is dropped.
+        let slots = data.slots.lock();
+        let roots_ptr = slots.roots.as_ptr() as *const Option<ValueRef>;
+        let n_roots = slots.roots.len();
+        let roots = unsafe { std::slice::from_raw_parts(roots_ptr, n_roots) };
+
+        unsafe {
+            crate::memory::gc::mark_queue_objarray(ptls, data.as_value_ref(), roots);
+        }
+
+        0
+    }
+}
+
+impl RootSet {
+    // Create the foreign type RootSet and an instance of it, both are stored as constants with
+    // unique names in the JlrsCore module.
+    #[cfg_attr(
+        not(any(
+            feature = "local-rt",
+            feature = "async-rt",
+            feature = "multi-rt",
+            feature = "ccall"
+        )),
+        allow(unused)
+    )]
+    pub(crate) unsafe fn init() {
+        if !ROOT_SET.load(Ordering::Acquire).is_null() {
+            return;
+        }
+
+        let unrooted = Unrooted::new();
+        unrooted.local_scope::<_, 2>(|mut frame| {
+            let module = Module::jlrs_core(&unrooted);
+
+            let ty_name = gensym("RootSet");
+            let dt = create_foreign_type_nostack::<Self, _>(&mut frame, ty_name, module);
+            module.set_const_unchecked(ty_name, dt.as_value());
+
+            let root_set = Value::new(&mut frame, RootSet::default());
+            module.set_const_unchecked(gensym("root_set"), root_set);
+
+            ROOT_SET.store(root_set.unwrap(Private).cast(), Ordering::Release);
+        })
+    }
+
+    // Safety: must only be called after the root set has been initialized.
+    #[inline]
+    unsafe fn get() -> &'static RootSet {
+        let ptr = ROOT_SET.load(Ordering::Acquire);
+        debug_assert!(!ptr.is_null(), "root set has not been initialized");
+        &*ptr
+    }
+
+    // Add a new root to the set and return its slot.
+    //
+    // Safety: `root` must point to data that hasn't been freed yet, this method must be called
+    // from a thread that can call into Julia.
+    unsafe fn push_root(&self, root: NonNull<c_void>) -> usize {
+        let slot = {
+            let mut slots = self.slots.lock();
+            match slots.free.pop() {
+                Some(slot) => {
+                    slots.roots[slot] = root.as_ptr();
+                    slot
+                }
+                None => {
+                    slots.roots.push(root.as_ptr());
+                    slots.roots.len() - 1
+                }
+            }
+        };
+
+        jlrs_gc_wb(self as *const _ as *mut _, root.as_ptr().cast());
+        slot
+    }
+
+    // Remove the root in `slot` from the set. This method can be called from any thread.
+    fn pop_root(&self, slot: usize) {
+        let mut slots = self.slots.lock();
+        slots.roots[slot] = null_mut();
+        slots.free.push(slot);
+    }
+}
+
+unsafe fn gensym(name: &str) -> Symbol<'static> {
+    let sym = jl_tagged_gensym(name.as_ptr().cast(), name.len());
+    Symbol::wrap_non_null(NonNull::new_unchecked(sym), Private)
+}
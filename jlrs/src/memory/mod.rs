@@ -175,7 +175,11 @@
 //! [`ExtendedTarget`]: crate::memory::target::ExtendedTarget
 
 pub(crate) mod context;
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+pub mod frame_profiler;
 pub mod gc;
+pub mod global_root;
 pub mod scope;
 pub mod stack_frame;
 pub mod target;
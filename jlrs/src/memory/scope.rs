@@ -1,5 +1,7 @@
 use jl_sys::unsized_local_scope;
 
+#[cfg(debug_assertions)]
+use super::frame_profiler::{self, FrameKind};
 use super::target::frame::{GcFrame, LocalFrame, LocalGcFrame, UnsizedLocalGcFrame};
 
 pub trait LocalReturning<'ctx> {
@@ -12,6 +14,7 @@ pub trait Returning<'ctx> {
 
 pub trait LocalScope<'a, T> {
     #[inline]
+    #[cfg_attr(debug_assertions, track_caller)]
     fn local_scope<F, const N: usize>(&self, func: F) -> T
     where
         for<'scope> F: FnOnce(LocalGcFrame<'scope, N>) -> T,
@@ -20,6 +23,14 @@ pub trait LocalScope<'a, T> {
             let mut local_frame = LocalFrame::new();
             let pinned = local_frame.pin();
             let res = func(LocalGcFrame::new(&pinned));
+            #[cfg(debug_assertions)]
+            if frame_profiler::is_enabled() {
+                frame_profiler::record(
+                    std::panic::Location::caller(),
+                    FrameKind::Local(N),
+                    pinned.n_reserved(),
+                );
+            }
             pinned.pop();
             res
         }
@@ -41,6 +52,7 @@ pub trait LocalScope<'a, T> {
 }
 
 pub trait Scope<'a, T>: LocalScope<'a, T> {
+    #[cfg_attr(debug_assertions, track_caller)]
     fn scope<F>(&mut self, func: F) -> T
     where
         for<'scope> F: FnOnce(GcFrame<'scope>) -> T;
@@ -0,0 +1,142 @@
+//! Root rings
+//!
+//! A root ring reserves a fixed number of slots in some frame and targets them in turn. There are
+//! two variations, [`RootRing`] and [`LocalRootRing`], both behave the same way, they only target
+//! different kinds of frame.
+//!
+//! Root rings are useful when many short-lived values are created, e.g. in a loop. Rooting them
+//! in a `GcFrame` grows its stack until the scope is left, and a reusable slot can only hold one
+//! value. When a mutable reference to a root ring is used as a target, the data is rooted in the
+//! next slot of the ring, after the last slot has been used the first one is reused. The data
+//! remains rooted until `N` more values have been rooted in the ring, the ring is cleared, or the
+//! scope of the frame that roots it is left. Because the data can become unrooted while it is
+//! usable, a `Ref` is returned as if an unrooting target has been used.
+//!
+//! Examples:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # fn main() {
+//! let mut julia = Builder::new().start_local().unwrap();
+//!
+//! julia.local_scope::<_, 4>(|mut frame| {
+//!     let mut ring = frame.local_root_ring::<4>();
+//!
+//!     for i in 0..1000usize {
+//!         let a = Value::new(&mut ring, i);
+//!         let b = Value::new(&mut ring, i + 1);
+//!
+//!         // Safety: `a` and `b` are the last two values rooted in the ring.
+//!         let (a, b) = unsafe { (a.as_value(), b.as_value()) };
+//!         assert_eq!(a.unbox::<usize>().unwrap() + 1, b.unbox::<usize>().unwrap());
+//!     }
+//! });
+//! # }
+//! ```
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # fn main() {
+//! let mut julia = Builder::new().start_local().unwrap();
+//!
+//! julia.returning::<JlrsResult<_>>().scope(|mut frame| {
+//!     let mut ring = frame.root_ring::<16>();
+//!
+//!     for i in 0..1000usize {
+//!         let _v = Value::new(&mut ring, i);
+//!     }
+//!
+//!     // Unroot all values in the ring.
+//!     ring.clear();
+//!     Ok(())
+//! }).unwrap();
+//! # }
+//! ```
+
+use std::{cell::Cell, ffi::c_void, ptr::NonNull};
+
+use crate::{
+    data::managed::{Managed, Ref},
+    memory::context::stack::Stack,
+};
+
+/// A ring of `N` slots that targets a [`GcFrame`].
+///
+/// See the [module-level docs] for more information.
+///
+/// [module-level docs]: crate::memory::target::root_ring
+/// [`GcFrame`]: crate::memory::target::frame::GcFrame
+pub struct RootRing<'target, const N: usize> {
+    pub(crate) stack: &'target Stack,
+    pub(crate) offset: usize,
+    pub(crate) cursor: usize,
+}
+
+impl<'target, const N: usize> RootRing<'target, N> {
+    /// Returns the number of slots in this ring.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Unroot all data rooted in this ring.
+    #[inline]
+    pub fn clear(&mut self) {
+        // Safety: the slots have been reserved in the stack when this ring was created.
+        unsafe { self.stack.clear_roots(self.offset, N) };
+        self.cursor = 0;
+    }
+
+    #[inline]
+    pub(crate) unsafe fn temporary<'data, T: Managed<'target, 'data>>(
+        &mut self,
+        ptr: NonNull<T::Wraps>,
+    ) -> Ref<'target, 'data, T> {
+        self.stack.set_root(self.offset + self.cursor, ptr.cast());
+        self.cursor = (self.cursor + 1) % N;
+        Ref::<T>::wrap(ptr)
+    }
+}
+
+/// A ring of `N` slots that targets a [`LocalGcFrame`].
+///
+/// See the [module-level docs] for more information.
+///
+/// [module-level docs]: crate::memory::target::root_ring
+/// [`LocalGcFrame`]: crate::memory::target::frame::LocalGcFrame
+pub struct LocalRootRing<'target, const N: usize> {
+    slots: &'target [Cell<*mut c_void>; N],
+    cursor: usize,
+}
+
+impl<'target, const N: usize> LocalRootRing<'target, N> {
+    /// Returns the number of slots in this ring.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Unroot all data rooted in this ring.
+    #[inline]
+    pub fn clear(&mut self) {
+        for slot in self.slots {
+            slot.set(std::ptr::null_mut());
+        }
+        self.cursor = 0;
+    }
+
+    #[inline]
+    pub(crate) fn new(slots: &'target [Cell<*mut c_void>; N]) -> Self {
+        LocalRootRing { slots, cursor: 0 }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn temporary<'data, T: Managed<'target, 'data>>(
+        &mut self,
+        ptr: NonNull<T::Wraps>,
+    ) -> Ref<'target, 'data, T> {
+        self.slots[self.cursor].set(ptr.as_ptr().cast());
+        self.cursor = (self.cursor + 1) % N;
+        Ref::<T>::wrap(ptr)
+    }
+}
@@ -7,8 +7,8 @@ pub use jlrs_macros::julia_module;
 pub use jlrs_macros::{encode_as_constant_bytes, julia_version};
 #[cfg(feature = "jlrs-derive")]
 pub use jlrs_macros::{
-    CCallArg, CCallReturn, ConstructType, Enum, HasLayout, IntoJulia, IsBits, Typecheck, Unbox,
-    ValidField, ValidLayout,
+    CCallArg, CCallReturn, ConstructType, Enum, ExposedFields, ForeignType, HasLayout, IntoJulia,
+    IsBits, Mark, Typecheck, Unbox, ValidField, ValidLayout,
 };
 
 #[cfg(any(feature = "local-rt", feature = "async-rt", feature = "ccall"))]
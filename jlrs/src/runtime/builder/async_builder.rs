@@ -1,4 +1,4 @@
-use std::{path::Path, thread, thread::JoinHandle};
+use std::{path::Path, sync::mpsc::sync_channel, thread, thread::JoinHandle};
 
 use async_channel::{bounded, unbounded};
 use jl_sys::jlrs_gc_safe_enter;
@@ -16,6 +16,7 @@ use crate::{
         handle::async_handle::{
             cancellation_token::CancellationToken, on_main_thread, AsyncHandle,
         },
+        pkg::Environment,
         state::{can_init, set_exit},
     },
     InstallJlrsCore,
@@ -124,6 +125,7 @@ impl<E: Executor<N>, const N: usize> AsyncBuilder<E, N> {
     ///
     /// [`PackageCompiler`]: https://julialang.github.io/PackageCompiler.jl
     #[inline]
+    #[allow(clippy::result_large_err)]
     pub unsafe fn image<P, Q>(mut self, julia_bindir: P, image_path: Q) -> Result<Self, Self>
     where
         P: AsRef<Path> + Send + 'static,
@@ -154,6 +156,17 @@ impl<E: Executor<N>, const N: usize> AsyncBuilder<E, N> {
         self.builder.install_jlrs_core = install;
         self
     }
+
+    /// Set up the Julia environment after Julia and JlrsCore have been initialized.
+    ///
+    /// See [`Builder::environment`] for more information.
+    ///
+    /// Safety: adding and instantiating packages can cause arbitrary Julia code to be executed.
+    #[inline]
+    pub unsafe fn environment(mut self, environment: Environment) -> Self {
+        self.builder.environment = Some(Box::new(environment));
+        self
+    }
 }
 
 pub(crate) fn spawn_main<R: Executor<N>, const N: usize>(
@@ -173,8 +186,14 @@ pub(crate) fn spawn_main<R: Executor<N>, const N: usize>(
         bounded(channel_capacity)
     };
 
+    let (init_sender, init_receiver) = sync_channel(1);
     let thread_handle = std::thread::spawn(move || unsafe {
-        init_runtime(&builder);
+        let res = init_runtime(&builder);
+        let failed = res.is_err();
+        init_sender.send(res).ok();
+        if failed {
+            return;
+        }
 
         let ptls = get_tls();
         jlrs_gc_safe_enter(ptls);
@@ -185,6 +204,10 @@ pub(crate) fn spawn_main<R: Executor<N>, const N: usize>(
         set_exit();
     });
 
+    init_receiver
+        .recv()
+        .map_err(|_| JlrsError::exception("failed to initialize Julia"))??;
+
     unsafe {
         let handle = AsyncHandle::new_main(sender, t2);
         Ok((handle, thread_handle))
@@ -202,7 +225,7 @@ pub(crate) fn run_main<T: 'static + Send, R: Executor<N>, const N: usize>(
     }
 
     unsafe {
-        init_runtime(&builder);
+        init_runtime(&builder)?;
 
         let token = CancellationToken::new();
         let t2 = token.clone();
@@ -233,11 +256,9 @@ pub(crate) fn run_main<T: 'static + Send, R: Executor<N>, const N: usize>(
 #[cfg(feature = "multi-rt")]
 #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8")))]
 mod mt_impl {
-    use parking_lot::{Condvar, Mutex};
-    static INIT_LOCK: (Mutex<bool>, Condvar) = (Mutex::new(false), Condvar::new());
-
     use std::{
         panic::{catch_unwind, AssertUnwindSafe},
+        sync::mpsc::sync_channel,
         thread::{self, JoinHandle},
     };
 
@@ -255,7 +276,7 @@ mod mt_impl {
                     cancellation_token::CancellationToken, channel::channel, on_main_thread,
                 },
                 mt_handle::{wait_loop, MtHandle, EXIT_LOCK},
-                notify, wait,
+                wait,
             },
             state::{can_init, set_exit},
         },
@@ -274,12 +295,17 @@ mod mt_impl {
         let t2 = token.clone();
         let (sender, receiver) = channel(channel_capacity);
 
+        let (init_sender, init_receiver) = sync_channel(1);
         let handle = thread::spawn(move || {
             unsafe {
-                init_runtime(&builder);
+                let res = init_runtime(&builder);
+                let failed = res.is_err();
 
                 // Notify that initialization is finished
-                notify(&INIT_LOCK);
+                init_sender.send(res).ok();
+                if failed {
+                    return;
+                }
 
                 let mut base_frame = StackFrame::<N>::new_n();
                 let res = catch_unwind(AssertUnwindSafe(|| {
@@ -302,7 +328,10 @@ mod mt_impl {
             }
         });
 
-        wait(&INIT_LOCK);
+        init_receiver
+            .recv()
+            .map_err(|_| JlrsError::exception("failed to initialize Julia"))??;
+
         let mt_handle = unsafe { MtHandle::new() };
         let async_handle = unsafe { AsyncHandle::new_main(sender, t2) };
         Ok((mt_handle, async_handle, handle))
@@ -327,7 +356,7 @@ mod mt_impl {
         let (sender, receiver) = channel(channel_capacity);
 
         unsafe {
-            init_runtime(&options);
+            init_runtime(&options)?;
         }
 
         let async_handle = unsafe { AsyncHandle::new_main(sender, t2) };
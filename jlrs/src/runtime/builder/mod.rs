@@ -18,7 +18,7 @@ use std::{
 
 #[cfg(feature = "async-rt")]
 pub use async_builder::*;
-use jl_sys::{jl_init, jl_init_with_image, jlrs_set_nthreads};
+use jl_sys::{jl_atexit_hook, jl_init, jl_init_with_image, jlrs_set_nthreads};
 #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8")))]
 use jl_sys::{jlrs_set_nthreadpools, jlrs_set_nthreads_per_pool};
 
@@ -31,7 +31,11 @@ use crate::runtime::executor::Executor;
 use crate::runtime::handle::mt_handle::MtHandle;
 #[cfg(feature = "local-rt")]
 use crate::runtime::{handle::local_handle::LocalHandle, sync_rt::PendingJulia};
-use crate::{init_jlrs, InstallJlrsCore};
+use crate::{
+    init_jlrs,
+    runtime::{pkg::Environment, state::set_exit},
+    weak_handle_unchecked, InstallJlrsCore,
+};
 
 /// Build a runtime.
 ///
@@ -42,6 +46,7 @@ use crate::{init_jlrs, InstallJlrsCore};
 pub struct Builder {
     pub(crate) image: Option<(PathBuf, PathBuf)>,
     pub(crate) install_jlrs_core: InstallJlrsCore,
+    pub(crate) environment: Option<Box<Environment>>,
     pub(crate) n_threads: usize,
     #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8")))]
     pub(crate) n_threadsi: usize,
@@ -56,6 +61,7 @@ impl Builder {
         Builder {
             image: None,
             install_jlrs_core: InstallJlrsCore::Default,
+            environment: None,
             n_threads: 0,
             #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8")))]
             n_threadsi: 0,
@@ -83,7 +89,7 @@ impl Builder {
         }
 
         unsafe {
-            init_runtime(&self);
+            init_runtime(&self)?;
             Ok(LocalHandle::new())
         }
     }
@@ -174,6 +180,19 @@ impl Builder {
         self
     }
 
+    /// Set up the Julia environment after Julia and JlrsCore have been initialized.
+    ///
+    /// The environment can activate a project, add packages and instantiate the project, see
+    /// [`Environment`] for more information. If the environment can't be applied, Julia is shut
+    /// down and the error is returned when the runtime is started.
+    ///
+    /// Safety: adding and instantiating packages can cause arbitrary Julia code to be executed.
+    #[inline]
+    pub unsafe fn environment(mut self, environment: Environment) -> Self {
+        self.environment = Some(Box::new(environment));
+        self
+    }
+
     /// Upgrade this builder to an [`AsyncBuilder`].
     ///
     /// You must provide an executor, jlrs supports using tokio if the `tokio-rt` feature is
@@ -205,15 +224,14 @@ impl Builder {
 #[cfg(feature = "multi-rt")]
 #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8")))]
 mod mt_impl {
-    use parking_lot::{Condvar, Mutex};
-    static INIT_LOCK: (Mutex<bool>, Condvar) = (Mutex::new(false), Condvar::new());
-
     pub(super) mod sync_impl {
-        use std::thread::{self, JoinHandle};
+        use std::{
+            sync::mpsc::sync_channel,
+            thread::{self, JoinHandle},
+        };
 
         use jl_sys::jl_atexit_hook;
 
-        use super::INIT_LOCK;
         use crate::{
             error::{JlrsError, RuntimeError},
             memory::gc::gc_safe,
@@ -222,7 +240,7 @@ mod mt_impl {
                 builder::{init_runtime, Builder},
                 handle::{
                     mt_handle::{wait_loop, MtHandle, EXIT_LOCK},
-                    notify, wait,
+                    wait,
                 },
                 state::{can_init, set_exit},
             },
@@ -233,10 +251,16 @@ mod mt_impl {
                 Err(RuntimeError::AlreadyInitialized)?;
             }
 
+            let (init_sender, init_receiver) = sync_channel(1);
             let handle = thread::spawn(move || {
                 unsafe {
-                    init_runtime(&options);
-                    notify(&INIT_LOCK);
+                    let res = init_runtime(&options);
+                    let failed = res.is_err();
+                    init_sender.send(res).ok();
+                    if failed {
+                        return;
+                    }
+
                     wait_loop();
 
                     // Returned from wait_main, so we're about to exit Julia because all handles have
@@ -247,7 +271,10 @@ mod mt_impl {
                 }
             });
 
-            wait(&INIT_LOCK);
+            init_receiver
+                .recv()
+                .map_err(|_| JlrsError::exception("failed to initialize Julia"))??;
+
             let mt_handle = unsafe { MtHandle::new() };
             Ok((mt_handle, handle))
         }
@@ -264,7 +291,7 @@ mod mt_impl {
             }
 
             unsafe {
-                init_runtime(&options);
+                init_runtime(&options)?;
             }
 
             let handle = thread::spawn(|| unsafe {
@@ -292,10 +319,23 @@ mod mt_impl {
     }
 }
 
-unsafe fn init_runtime(options: &Builder) {
+// Initializes Julia and jlrs, and applies the environment set with `Builder::environment`. If the
+// environment can't be applied, Julia is shut down and the error is returned.
+unsafe fn init_runtime(options: &Builder) -> crate::error::JlrsResult<()> {
     set_n_threads(options);
     init_julia(options);
     init_jlrs(&options.install_jlrs_core);
+
+    if let Some(environment) = options.environment.as_ref() {
+        let handle = weak_handle_unchecked!();
+        if let Err(err) = environment.apply(&handle) {
+            set_exit();
+            jl_atexit_hook(1);
+            return Err(err);
+        }
+    }
+
+    Ok(())
 }
 
 unsafe fn init_julia(options: &Builder) {
@@ -4,7 +4,7 @@ use std::{
     cell::RefCell,
     collections::VecDeque,
     ffi::{c_void, CStr},
-    path::Path,
+    path::{Path, PathBuf},
     ptr::NonNull,
     rc::Rc,
     sync::{
@@ -39,10 +39,17 @@ use crate::{
         task::{sleep, AsyncTask, PersistentTask, Register},
     },
     convert::into_jlrs_result::IntoJlrsResult,
+    data::managed::{expr::Expr, Managed},
     error::IOError,
-    memory::{gc::gc_unsafe_with, get_tls, stack_frame::JlrsStackFrame, target::frame::GcFrame},
+    memory::{
+        gc::gc_unsafe_with, get_tls, global_root::GlobalRoot, stack_frame::JlrsStackFrame,
+        target::frame::GcFrame,
+    },
     prelude::{JlrsResult, LocalScope, Module, StackFrame, Value},
-    runtime::executor::{Executor, IsFinished},
+    runtime::{
+        executor::{Executor, IsFinished},
+        pkg::{self, Environment},
+    },
     util::RequireSendSync,
     weak_handle_unchecked,
 };
@@ -165,6 +172,124 @@ impl AsyncHandle {
         Dispatch::new(msg, &self.sender, receiver)
     }
 
+    /// Parse `code` without evaluating it and return the first syntax error if there is one.
+    ///
+    /// See [`Value::parse_all`] for more information.
+    pub fn check_syntax(&self, code: String) -> Dispatch<Message, JlrsResult<()>> {
+        let (sender, receiver) = oneshot_channel();
+        let pending_task = BlockingTask::new(
+            move |mut frame| Value::parse_all(&mut frame, code, None).map(|_| ()),
+            sender,
+        );
+
+        let msg = MessageInner::BlockingTask(Box::new(pending_task)).wrap();
+        Dispatch::new(msg, &self.sender, receiver)
+    }
+
+    /// Parse `code` and evaluate it in the module at `module`, e.g. `"Main"` or
+    /// `"Main.MyModule"`.
+    ///
+    /// Syntax errors are returned as `JlrsError::ParseError` and nothing is evaluated in that
+    /// case.
+    ///
+    /// Safety: The code can't be checked for correctness, nothing prevents you from causing a
+    /// segmentation fault with code like `unsafe_load(Ptr{Float64}(C_NULL))`.
+    pub unsafe fn eval_in(
+        &self,
+        module: String,
+        code: String,
+    ) -> Dispatch<Message, JlrsResult<()>> {
+        let (sender, receiver) = oneshot_channel();
+        let pending_task = BlockingTask::new(
+            move |mut frame| unsafe {
+                let module = Module::from_path(&mut frame, module)?;
+                let expr = Value::parse_all(&mut frame, code, None)?;
+                Value::eval_expr(&mut frame, module, expr)
+                    .map(|_| ())
+                    .into_jlrs_result()
+            },
+            sender,
+        );
+
+        let msg = MessageInner::BlockingTask(Box::new(pending_task)).wrap();
+        Dispatch::new(msg, &self.sender, receiver)
+    }
+
+    /// Prepare to parse all expressions in `code` without evaluating them.
+    ///
+    /// The result is an `Expr` with the head `:toplevel` that is rooted until the returned root
+    /// is dropped, it can be evaluated with [`AsyncHandle::eval_expr_in`]. See
+    /// [`Value::parse_all`] for more information.
+    pub fn parse_all(
+        &self,
+        code: String,
+    ) -> Dispatch<Message, JlrsResult<GlobalRoot<Expr<'static>>>> {
+        let (sender, receiver) = oneshot_channel();
+        let pending_task = BlockingTask::new(
+            move |mut frame| {
+                let expr = Value::parse_all(&mut frame, code, None)?.cast::<Expr>()?;
+                Ok(GlobalRoot::new(expr))
+            },
+            sender,
+        );
+
+        let msg = MessageInner::BlockingTask(Box::new(pending_task)).wrap();
+        Dispatch::new(msg, &self.sender, receiver)
+    }
+
+    /// Prepare to evaluate the expression `expr` in the module at `module`, e.g. `"Main"` or
+    /// `"Main.MyModule"`.
+    ///
+    /// Safety: The expression can't be checked for correctness, nothing prevents you from
+    /// causing a segmentation fault by evaluating an expression like
+    /// `unsafe_load(Ptr{Float64}(C_NULL))`.
+    pub unsafe fn eval_expr_in(
+        &self,
+        module: String,
+        expr: Arc<GlobalRoot<Expr<'static>>>,
+    ) -> Dispatch<Message, JlrsResult<()>> {
+        let (sender, receiver) = oneshot_channel();
+        let pending_task = BlockingTask::new(
+            move |mut frame| unsafe {
+                let module = Module::from_path(&mut frame, module)?;
+                let expr = expr.get()?.as_value();
+                Value::eval_expr(&mut frame, module, expr)
+                    .map(|_| ())
+                    .into_jlrs_result()
+            },
+            sender,
+        );
+
+        let msg = MessageInner::BlockingTask(Box::new(pending_task)).wrap();
+        Dispatch::new(msg, &self.sender, receiver)
+    }
+
+    /// Prepare to apply `environment`, see [`Environment`] for more information.
+    ///
+    /// Safety: adding and instantiating packages can cause arbitrary Julia code to be executed.
+    pub unsafe fn environment(
+        &self,
+        environment: Environment,
+    ) -> Dispatch<Message, JlrsResult<()>> {
+        let (sender, receiver) = oneshot_channel();
+        let pending_task =
+            BlockingTask::new(move |frame| unsafe { environment.apply(&frame) }, sender);
+
+        let msg = MessageInner::BlockingTask(Box::new(pending_task)).wrap();
+        Dispatch::new(msg, &self.sender, receiver)
+    }
+
+    /// Prepare to return the paths in `DEPOT_PATH`.
+    ///
+    /// See [`pkg::depot_path`] for more information.
+    pub fn depot_path(&self) -> Dispatch<Message, JlrsResult<Vec<PathBuf>>> {
+        let (sender, receiver) = oneshot_channel();
+        let pending_task = BlockingTask::new(move |frame| pkg::depot_path(&frame), sender);
+
+        let msg = MessageInner::BlockingTask(Box::new(pending_task)).wrap();
+        Dispatch::new(msg, &self.sender, receiver)
+    }
+
     /// Prepare to enable or disable colored error messages originating from Julia.
     ///
     /// This feature is disabled by default and is a global property.
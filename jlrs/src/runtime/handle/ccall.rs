@@ -45,6 +45,7 @@ impl<'context> CCall<'context> {
     }
 
     /// Create a [`GcFrame`], call the given closure, and return its result.
+    #[cfg_attr(debug_assertions, track_caller)]
     pub fn scope<T, F>(&mut self, func: F) -> JlrsResult<T>
     where
         for<'scope> F: FnOnce(GcFrame<'scope>) -> JlrsResult<T>,
@@ -53,6 +54,12 @@ impl<'context> CCall<'context> {
             let stack = self.frame.stack_frame().sync_stack();
             let frame = GcFrame::base(stack);
             let ret = func(frame);
+            #[cfg(debug_assertions)]
+            crate::memory::frame_profiler::record(
+                std::panic::Location::caller(),
+                crate::memory::frame_profiler::FrameKind::Dynamic,
+                stack.size(),
+            );
             stack.pop_roots(0);
             ret
         }
@@ -60,6 +67,7 @@ impl<'context> CCall<'context> {
 
     /// Create a [`LocalGcFrame`], call the given closure, and return its result.
     #[inline]
+    #[cfg_attr(debug_assertions, track_caller)]
     pub unsafe fn local_scope<T, F, const N: usize>(func: F) -> JlrsResult<T>
     where
         for<'scope> F: FnOnce(LocalGcFrame<'scope, N>) -> JlrsResult<T>,
@@ -68,6 +76,14 @@ impl<'context> CCall<'context> {
 
         let pinned = local_frame.pin();
         let res = func(LocalGcFrame::new(&pinned));
+        #[cfg(debug_assertions)]
+        if crate::memory::frame_profiler::is_enabled() {
+            crate::memory::frame_profiler::record(
+                std::panic::Location::caller(),
+                crate::memory::frame_profiler::FrameKind::Local(N),
+                pinned.n_reserved(),
+            );
+        }
         pinned.pop();
         res
     }
@@ -1,6 +1,10 @@
 //! A handle that lets you call into Julia from the current thread.
 
-use std::{fmt, marker::PhantomData, path::Path};
+use std::{
+    fmt,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
 
 use jl_sys::jl_atexit_hook;
 
@@ -8,11 +12,20 @@ use super::IsActive;
 use crate::{
     call::Call,
     convert::into_jlrs_result::IntoJlrsResult,
-    data::managed::module::Main,
+    data::managed::{
+        expr::Expr,
+        module::{Main, Module},
+    },
     error::{IOError, JlrsResult},
-    memory::scope::{LocalReturning, LocalScope},
+    memory::{
+        global_root::GlobalRoot,
+        scope::{LocalReturning, LocalScope},
+    },
     prelude::{JuliaString, Managed, Value},
-    runtime::state::set_exit,
+    runtime::{
+        pkg::{self, Environment},
+        state::set_exit,
+    },
 };
 
 /// A handle that lets you call into Julia from the current thread.
@@ -70,6 +83,81 @@ impl LocalHandle {
         });
     }
 
+    /// Parse `code` without evaluating it and return the first syntax error if there is one.
+    ///
+    /// See [`Value::parse_all`] for more information.
+    pub fn check_syntax<S: AsRef<str>>(&self, code: S) -> JlrsResult<()> {
+        self.local_scope::<_, 1>(|mut frame| Value::parse_all(&mut frame, code, None).map(|_| ()))
+    }
+
+    /// Parse `code` and evaluate it in the module at `module`, e.g. `"Main"` or
+    /// `"Main.MyModule"`.
+    ///
+    /// Syntax errors are returned as `JlrsError::ParseError` and nothing is evaluated in that
+    /// case.
+    ///
+    /// Safety: The code can't be checked for correctness, nothing prevents you from causing a
+    /// segmentation fault with code like `unsafe_load(Ptr{Float64}(C_NULL))`.
+    pub unsafe fn eval_in<M: AsRef<str>, S: AsRef<str>>(
+        &self,
+        module: M,
+        code: S,
+    ) -> JlrsResult<()> {
+        self.local_scope::<_, 3>(|mut frame| {
+            let module = Module::from_path(&mut frame, module)?;
+            let expr = Value::parse_all(&mut frame, code, None)?;
+            Value::eval_expr(&mut frame, module, expr)
+                .map(|_| ())
+                .into_jlrs_result()
+        })
+    }
+
+    /// Parse all expressions in `code` without evaluating them.
+    ///
+    /// The result is an `Expr` with the head `:toplevel` that is rooted until the returned root
+    /// is dropped, it can be evaluated with [`LocalHandle::eval_expr_in`]. See [`Value::parse_all`] for
+    /// more information.
+    pub fn parse_all<S: AsRef<str>>(&self, code: S) -> JlrsResult<GlobalRoot<Expr<'static>>> {
+        self.local_scope::<_, 1>(|mut frame| {
+            let expr = Value::parse_all(&mut frame, code, None)?.cast::<Expr>()?;
+            Ok(GlobalRoot::new(expr))
+        })
+    }
+
+    /// Evaluate the expression `expr` in the module at `module`, e.g. `"Main"` or
+    /// `"Main.MyModule"`.
+    ///
+    /// Safety: The expression can't be checked for correctness, nothing prevents you from
+    /// causing a segmentation fault by evaluating an expression like
+    /// `unsafe_load(Ptr{Float64}(C_NULL))`.
+    pub unsafe fn eval_expr_in<M: AsRef<str>>(
+        &self,
+        module: M,
+        expr: &GlobalRoot<Expr<'static>>,
+    ) -> JlrsResult<()> {
+        self.local_scope::<_, 2>(|mut frame| {
+            let module = Module::from_path(&mut frame, module)?;
+            let expr = expr.get()?.as_value();
+            Value::eval_expr(&mut frame, module, expr)
+                .map(|_| ())
+                .into_jlrs_result()
+        })
+    }
+
+    /// Apply `environment`, see [`Environment`] for more information.
+    ///
+    /// Safety: adding and instantiating packages can cause arbitrary Julia code to be executed.
+    pub unsafe fn environment(&self, environment: &Environment) -> JlrsResult<()> {
+        self.local_scope::<_, 0>(|frame| environment.apply(&frame))
+    }
+
+    /// Returns the paths in `DEPOT_PATH`.
+    ///
+    /// See [`pkg::depot_path`] for more information.
+    pub fn depot_path(&self) -> JlrsResult<Vec<PathBuf>> {
+        self.local_scope::<_, 0>(|frame| pkg::depot_path(&frame))
+    }
+
     pub(crate) unsafe fn new() -> Self {
         LocalHandle {
             _marker: PhantomData,
@@ -2,7 +2,13 @@
 
 #[cfg(feature = "async")]
 use std::num::NonZeroUsize;
-use std::{cell::Cell, marker::PhantomData, path::Path, pin::Pin, sync::atomic::AtomicUsize};
+use std::{
+    cell::Cell,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::atomic::AtomicUsize,
+};
 
 use atomic::Ordering;
 use jl_sys::{jl_adopt_thread, jl_atexit_hook, jlrs_gc_safe_enter, jlrs_ptls_from_gcstack};
@@ -18,11 +24,17 @@ use crate::runtime::executor::Executor;
 use crate::{
     call::Call,
     convert::into_jlrs_result::IntoJlrsResult,
-    data::managed::module::{JlrsCore, Main},
+    data::managed::{
+        expr::Expr,
+        module::{JlrsCore, Main, Module},
+    },
     error::{IOError, CANNOT_DISPLAY_VALUE},
-    memory::{gc::gc_unsafe, get_tls, scope::LocalReturning},
+    memory::{gc::gc_unsafe, get_tls, global_root::GlobalRoot, scope::LocalReturning},
     prelude::{JlrsResult, JuliaString, LocalScope, Managed, Value},
-    runtime::state::{set_exit, set_pending_exit},
+    runtime::{
+        pkg::{self, Environment},
+        state::{set_exit, set_pending_exit},
+    },
     weak_handle_unchecked,
 };
 
@@ -171,6 +183,81 @@ impl<'ctx> ActiveHandle<'ctx> {
                 .into_jlrs_result()
         });
     }
+
+    /// Parse `code` without evaluating it and return the first syntax error if there is one.
+    ///
+    /// See [`Value::parse_all`] for more information.
+    pub fn check_syntax<S: AsRef<str>>(&self, code: S) -> JlrsResult<()> {
+        self.local_scope::<_, 1>(|mut frame| Value::parse_all(&mut frame, code, None).map(|_| ()))
+    }
+
+    /// Parse `code` and evaluate it in the module at `module`, e.g. `"Main"` or
+    /// `"Main.MyModule"`.
+    ///
+    /// Syntax errors are returned as `JlrsError::ParseError` and nothing is evaluated in that
+    /// case.
+    ///
+    /// Safety: The code can't be checked for correctness, nothing prevents you from causing a
+    /// segmentation fault with code like `unsafe_load(Ptr{Float64}(C_NULL))`.
+    pub unsafe fn eval_in<M: AsRef<str>, S: AsRef<str>>(
+        &self,
+        module: M,
+        code: S,
+    ) -> JlrsResult<()> {
+        self.local_scope::<_, 3>(|mut frame| {
+            let module = Module::from_path(&mut frame, module)?;
+            let expr = Value::parse_all(&mut frame, code, None)?;
+            Value::eval_expr(&mut frame, module, expr)
+                .map(|_| ())
+                .into_jlrs_result()
+        })
+    }
+
+    /// Parse all expressions in `code` without evaluating them.
+    ///
+    /// The result is an `Expr` with the head `:toplevel` that is rooted until the returned root
+    /// is dropped, it can be evaluated with [`ActiveHandle::eval_expr_in`]. See [`Value::parse_all`] for
+    /// more information.
+    pub fn parse_all<S: AsRef<str>>(&self, code: S) -> JlrsResult<GlobalRoot<Expr<'static>>> {
+        self.local_scope::<_, 1>(|mut frame| {
+            let expr = Value::parse_all(&mut frame, code, None)?.cast::<Expr>()?;
+            Ok(GlobalRoot::new(expr))
+        })
+    }
+
+    /// Evaluate the expression `expr` in the module at `module`, e.g. `"Main"` or
+    /// `"Main.MyModule"`.
+    ///
+    /// Safety: The expression can't be checked for correctness, nothing prevents you from
+    /// causing a segmentation fault by evaluating an expression like
+    /// `unsafe_load(Ptr{Float64}(C_NULL))`.
+    pub unsafe fn eval_expr_in<M: AsRef<str>>(
+        &self,
+        module: M,
+        expr: &GlobalRoot<Expr<'static>>,
+    ) -> JlrsResult<()> {
+        self.local_scope::<_, 2>(|mut frame| {
+            let module = Module::from_path(&mut frame, module)?;
+            let expr = expr.get()?.as_value();
+            Value::eval_expr(&mut frame, module, expr)
+                .map(|_| ())
+                .into_jlrs_result()
+        })
+    }
+
+    /// Apply `environment`, see [`Environment`] for more information.
+    ///
+    /// Safety: adding and instantiating packages can cause arbitrary Julia code to be executed.
+    pub unsafe fn environment(&self, environment: &Environment) -> JlrsResult<()> {
+        self.local_scope::<_, 0>(|frame| environment.apply(&frame))
+    }
+
+    /// Returns the paths in `DEPOT_PATH`.
+    ///
+    /// See [`pkg::depot_path`] for more information.
+    pub fn depot_path(&self) -> JlrsResult<Vec<PathBuf>> {
+        self.local_scope::<_, 0>(|frame| pkg::depot_path(&frame))
+    }
 }
 
 impl IsActive for ActiveHandle<'_> {}
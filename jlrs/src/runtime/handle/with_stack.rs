@@ -46,6 +46,7 @@ impl<'ctx> IsActive for StackHandle<'ctx> {}
 
 impl<'ctx, T> Scope<'ctx, T> for StackHandle<'ctx> {
     #[inline]
+    #[cfg_attr(debug_assertions, track_caller)]
     fn scope<F>(&mut self, func: F) -> T
     where
         for<'scope> F: FnOnce(GcFrame<'scope>) -> T,
@@ -53,6 +54,12 @@ impl<'ctx, T> Scope<'ctx, T> for StackHandle<'ctx> {
         unsafe {
             let frame = GcFrame::base(&self.stack);
             let ret = func(frame);
+            #[cfg(debug_assertions)]
+            crate::memory::frame_profiler::record(
+                std::panic::Location::caller(),
+                crate::memory::frame_profiler::FrameKind::Dynamic,
+                self.stack.size(),
+            );
             self.stack.pop_roots(0);
             ret
         }
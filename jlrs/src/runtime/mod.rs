@@ -11,6 +11,7 @@ pub mod builder;
 #[cfg(feature = "async")]
 pub mod executor;
 pub mod handle;
+pub mod pkg;
 pub mod state;
 #[cfg(feature = "local-rt")]
 pub mod sync_rt;
@@ -0,0 +1,385 @@
+//! Manage the Julia environment of an embedding application.
+//!
+//! jlrs only installs the JlrsCore package by itself, see [`InstallJlrsCore`]. Applications that
+//! depend on other Julia packages can use an [`Environment`] to activate a project, add
+//! packages and instantiate the project when Julia is initialized by providing it to
+//! [`Builder::environment`], or later by calling `environment` on a handle.
+//!
+//! The functions in this module call the corresponding functions from the `Pkg` standard
+//! library. Exceptions thrown by `Pkg` are converted to [`PkgError::Failed`].
+//!
+//! If an environment is used offline, `Pkg` doesn't access the network. Packages can only be
+//! installed from registries that are already available in the depot and from local paths.
+//!
+//! [`InstallJlrsCore`]: crate::InstallJlrsCore
+//! [`Builder::environment`]: crate::runtime::builder::Builder::environment
+
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::Ordering,
+};
+
+use crate::{
+    call::{Call, ProvideKeywords},
+    data::managed::{
+        array::Vector,
+        module::{Module, ModuleData},
+        string::JuliaString,
+        value::Value,
+        Managed,
+    },
+    error::{JlrsResult, PkgError, CANNOT_DISPLAY_VALUE},
+    memory::target::Target,
+    named_tuple,
+};
+
+/// A package that must be added to the active project.
+#[derive(Clone, Debug)]
+pub enum Package {
+    /// A package from a registry that is available in the depot.
+    Registered {
+        /// The name of the package.
+        name: String,
+        /// The version of the package, e.g. `"1.2.3"`. The latest compatible version is used if
+        /// no version is provided.
+        version: Option<String>,
+    },
+    /// A package at a local path, this package is tracked with `Pkg.develop`.
+    Path(PathBuf),
+    /// A revision of some git repository.
+    Git {
+        /// URL of the repository.
+        repo: String,
+        /// Revision to be installed.
+        revision: String,
+    },
+}
+
+impl Package {
+    /// A registered package, the latest compatible version is used.
+    #[inline]
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Package::Registered {
+            name: name.into(),
+            version: None,
+        }
+    }
+
+    /// A specific version of a registered package.
+    #[inline]
+    pub fn with_version<S: Into<String>, V: Into<String>>(name: S, version: V) -> Self {
+        Package::Registered {
+            name: name.into(),
+            version: Some(version.into()),
+        }
+    }
+
+    /// A package at a local path.
+    #[inline]
+    pub fn path<P: AsRef<Path>>(path: P) -> Self {
+        Package::Path(path.as_ref().to_path_buf())
+    }
+}
+
+/// The Julia environment of an application.
+///
+/// When an environment is applied, the following steps are taken in order: the depots are
+/// prepended to `DEPOT_PATH`, offline mode is enabled or disabled if it has been set, the project
+/// is activated, the packages are added, and finally the project is instantiated.
+#[derive(Clone, Debug)]
+pub struct Environment {
+    project: Option<PathBuf>,
+    depots: Vec<PathBuf>,
+    packages: Vec<Package>,
+    offline: Option<bool>,
+    instantiate: bool,
+}
+
+impl Environment {
+    /// Create a new environment.
+    ///
+    /// The default environment doesn't activate a project, add any depots or packages, doesn't
+    /// change whether `Pkg` is used offline, and doesn't instantiate the project.
+    #[inline]
+    pub const fn new() -> Self {
+        Environment {
+            project: None,
+            depots: Vec::new(),
+            packages: Vec::new(),
+            offline: None,
+            instantiate: false,
+        }
+    }
+
+    /// Activate the project at `path`.
+    ///
+    /// The path can either be the path of a `Project.toml` file or of the directory that
+    /// contains it.
+    #[inline]
+    pub fn project<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.project = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Prepend `path` to `DEPOT_PATH`.
+    ///
+    /// If this method is called multiple times, the depot added last is used first.
+    #[inline]
+    pub fn depot<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.depots.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Add `package` to the active project.
+    #[inline]
+    pub fn package(mut self, package: Package) -> Self {
+        self.packages.push(package);
+        self
+    }
+
+    /// Enable or disable offline mode.
+    ///
+    /// If this method isn't called, offline mode is left unchanged when the environment is
+    /// applied.
+    #[inline]
+    pub const fn offline(mut self, offline: bool) -> Self {
+        self.offline = Some(offline);
+        self
+    }
+
+    /// Instantiate the project after the packages have been added.
+    #[inline]
+    pub const fn instantiate(mut self, instantiate: bool) -> Self {
+        self.instantiate = instantiate;
+        self
+    }
+
+    /// Apply this environment.
+    ///
+    /// Safety: adding and instantiating packages can cause arbitrary Julia code to be executed,
+    /// e.g. by build scripts.
+    pub unsafe fn apply<'target, Tgt>(&self, target: &Tgt) -> JlrsResult<()>
+    where
+        Tgt: Target<'target>,
+    {
+        for depot in self.depots.iter() {
+            add_depot(target, depot)?;
+        }
+
+        if let Some(offline) = self.offline {
+            set_offline(target, offline)?;
+        }
+
+        if let Some(project) = self.project.as_ref() {
+            activate(target, project)?;
+        }
+
+        for package in self.packages.iter() {
+            add(target, package)?;
+        }
+
+        if self.instantiate {
+            instantiate(target)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Environment {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Activate the project at `path` by calling `Pkg.activate`.
+///
+/// The path can either be the path of a `Project.toml` file or of the directory that contains
+/// it. Returns `PkgError::ProjectNotFound` if the path doesn't exist.
+///
+/// Safety: activating a project changes which code is loaded by later calls to `using` and
+/// `import`.
+pub unsafe fn activate<'target, P, Tgt>(target: &Tgt, path: P) -> JlrsResult<()>
+where
+    P: AsRef<Path>,
+    Tgt: Target<'target>,
+{
+    let path = path.as_ref();
+    if !path.exists() {
+        Err(PkgError::ProjectNotFound {
+            path: path.to_string_lossy().into(),
+        })?
+    }
+
+    let dir = if path.is_file() {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+
+    target.with_local_scope::<_, _, 4>(|_, mut frame| {
+        let pkg = pkg_module(&mut frame)?;
+        let dir = JuliaString::new(&mut frame, dir.to_string_lossy()).as_value();
+        let func = pkg.global(&mut frame, "activate")?;
+        check("activate", func.call1(&mut frame, dir)).map(|_| ())
+    })
+}
+
+/// Instantiate the active project by calling `Pkg.instantiate`.
+///
+/// Safety: instantiating a project can cause arbitrary Julia code to be executed.
+pub unsafe fn instantiate<'target, Tgt>(target: &Tgt) -> JlrsResult<()>
+where
+    Tgt: Target<'target>,
+{
+    target.with_local_scope::<_, _, 3>(|_, mut frame| {
+        let pkg = pkg_module(&mut frame)?;
+        let func = pkg.global(&mut frame, "instantiate")?;
+        check("instantiate", func.call0(&mut frame)).map(|_| ())
+    })
+}
+
+/// Add `package` to the active project.
+///
+/// Registered packages and git repositories are added with `Pkg.add`, packages at a local path
+/// with `Pkg.develop`.
+///
+/// Safety: adding a package can cause arbitrary Julia code to be executed.
+pub unsafe fn add<'target, Tgt>(target: &Tgt, package: &Package) -> JlrsResult<()>
+where
+    Tgt: Target<'target>,
+{
+    target.with_local_scope::<_, _, 6>(|_, mut frame| {
+        let pkg = pkg_module(&mut frame)?;
+
+        match package {
+            Package::Registered { name, version } => {
+                let func = pkg.global(&mut frame, "add")?;
+                let name = JuliaString::new(&mut frame, name).as_value();
+                let kws = match version {
+                    Some(version) => {
+                        let version = JuliaString::new(&mut frame, version).as_value();
+                        named_tuple!(&mut frame, "name" => name, "version" => version)
+                    }
+                    None => named_tuple!(&mut frame, "name" => name),
+                };
+
+                check("add", func.provide_keywords(kws)?.call0(&mut frame)).map(|_| ())
+            }
+            Package::Path(path) => {
+                if !path.exists() {
+                    Err(PkgError::PackageNotFound {
+                        path: path.to_string_lossy().into(),
+                    })?
+                }
+
+                let func = pkg.global(&mut frame, "develop")?;
+                let path = JuliaString::new(&mut frame, path.to_string_lossy()).as_value();
+                let kws = named_tuple!(&mut frame, "path" => path);
+                check("develop", func.provide_keywords(kws)?.call0(&mut frame)).map(|_| ())
+            }
+            Package::Git { repo, revision } => {
+                let func = pkg.global(&mut frame, "add")?;
+                let repo = JuliaString::new(&mut frame, repo).as_value();
+                let revision = JuliaString::new(&mut frame, revision).as_value();
+                let kws = named_tuple!(&mut frame, "url" => repo, "rev" => revision);
+                check("add", func.provide_keywords(kws)?.call0(&mut frame)).map(|_| ())
+            }
+        }
+    })
+}
+
+/// Enable or disable offline mode by calling `Pkg.offline`.
+///
+/// While offline mode is enabled `Pkg` doesn't access the network.
+pub fn set_offline<'target, Tgt>(target: &Tgt, offline: bool) -> JlrsResult<()>
+where
+    Tgt: Target<'target>,
+{
+    // Safety: Pkg is a standard library, Pkg.offline only sets a flag.
+    target.with_local_scope::<_, _, 3>(|target, mut frame| unsafe {
+        let pkg = pkg_module(&mut frame)?;
+        let func = pkg.global(&mut frame, "offline")?;
+        let offline = if offline {
+            Value::true_v(&target)
+        } else {
+            Value::false_v(&target)
+        };
+
+        check("offline", func.call1(&mut frame, offline)).map(|_| ())
+    })
+}
+
+/// Prepend `path` to `DEPOT_PATH`.
+///
+/// Returns `PkgError::DepotNotFound` if the path doesn't exist.
+pub fn add_depot<'target, P, Tgt>(target: &Tgt, path: P) -> JlrsResult<()>
+where
+    P: AsRef<Path>,
+    Tgt: Target<'target>,
+{
+    let path = path.as_ref();
+    if !path.is_dir() {
+        Err(PkgError::DepotNotFound {
+            path: path.to_string_lossy().into(),
+        })?
+    }
+
+    target.with_local_scope::<_, _, 2>(|target, mut frame| unsafe {
+        let depot_path = Module::typed_global_cached::<Value, _, _>(&target, "Base.DEPOT_PATH")?;
+        let path = JuliaString::new(&mut frame, path.to_string_lossy()).as_value();
+
+        // Safety: DEPOT_PATH is a Vector{String}.
+        let res = Module::typed_global_cached::<Value, _, _>(&target, "Base.pushfirst!")?
+            .call2(&mut frame, depot_path, path);
+        check("pushfirst!", res).map(|_| ())
+    })
+}
+
+/// Returns the paths in `DEPOT_PATH`, the depot at the first path is used to install packages.
+pub fn depot_path<'target, Tgt>(target: &Tgt) -> JlrsResult<Vec<PathBuf>>
+where
+    Tgt: Target<'target>,
+{
+    // Safety: DEPOT_PATH is a Vector{String} which is only read.
+    unsafe {
+        let depot_path = Module::typed_global_cached::<Vector, _, _>(target, "Base.DEPOT_PATH")?;
+        let paths = depot_path
+            .try_managed_data::<JuliaString>()?
+            .as_slice()
+            .iter()
+            .filter_map(|path| path.load(Ordering::Relaxed))
+            .map(|path| {
+                PathBuf::from(String::from_utf8_lossy(path.as_managed().as_bytes()).into_owned())
+            })
+            .collect();
+
+        Ok(paths)
+    }
+}
+
+// Loads the Pkg standard library.
+unsafe fn pkg_module<'target, Tgt>(target: Tgt) -> JlrsResult<ModuleData<'target, Tgt>>
+where
+    Tgt: Target<'target>,
+{
+    target.with_local_scope::<_, _, 1>(|target, mut frame| {
+        let pkg = Module::main(&frame).require(&mut frame, "Pkg");
+        let pkg = check("require", pkg)?.cast::<Module>()?;
+        Ok(pkg.root(target))
+    })
+}
+
+fn check<'target>(
+    operation: &'static str,
+    res: Result<Value<'target, 'static>, Value<'target, 'static>>,
+) -> JlrsResult<Value<'target, 'static>> {
+    match res {
+        Ok(v) => Ok(v),
+        Err(e) => Err(PkgError::Failed {
+            operation,
+            msg: e.error_string_or(CANNOT_DISPLAY_VALUE),
+        })?,
+    }
+}
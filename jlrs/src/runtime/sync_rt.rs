@@ -9,7 +9,7 @@
 use std::{
     ffi::{c_void, CString},
     marker::PhantomData,
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 use jl_sys::{jl_atexit_hook, jl_init, jl_init_with_image, jl_is_initialized};
@@ -17,16 +17,21 @@ use jl_sys::{jl_atexit_hook, jl_init, jl_init_with_image, jl_is_initialized};
 use crate::{
     call::Call,
     convert::into_jlrs_result::IntoJlrsResult,
-    data::managed::{module::Module, string::JuliaString, value::Value, Managed},
+    data::managed::{expr::Expr, module::Module, string::JuliaString, value::Value, Managed},
     error::{IOError, JlrsResult, RuntimeError},
     init_jlrs,
     memory::{
         context::stack::Stack,
+        global_root::GlobalRoot,
         scope::{LocalScope, Scope},
         stack_frame::{PinnedFrame, StackFrame},
         target::{frame::GcFrame, unrooted::Unrooted},
     },
-    runtime::{builder::Builder, state::can_init},
+    runtime::{
+        builder::Builder,
+        pkg::{self, Environment},
+        state::can_init,
+    },
     INSTALL_METHOD,
 };
 
@@ -51,12 +56,10 @@ impl PendingJulia {
 
             let frame = pinned.stack_frame();
             let context = frame.sync_stack();
-            let wrapped: Julia<'ctx> = Julia {
+            Julia {
                 stack: context,
                 _frame: pinned,
-            };
-
-            wrapped
+            }
         }
     }
 
@@ -82,6 +85,14 @@ impl PendingJulia {
         let install_method = builder.install_jlrs_core.clone();
         INSTALL_METHOD.get_or_init(|| install_method);
 
+        if let Some(environment) = builder.environment.as_ref() {
+            init_jlrs(INSTALL_METHOD.get().unwrap());
+            if let Err(err) = environment.apply(&Unrooted::new()) {
+                jl_atexit_hook(1);
+                return Err(err);
+            }
+        }
+
         Ok(PendingJulia {
             _not_send_sync: PhantomData,
         })
@@ -178,12 +189,88 @@ impl<'a> Julia<'a> {
         });
     }
 
+    /// Parse `code` without evaluating it and return the first syntax error if there is one.
+    ///
+    /// See [`Value::parse_all`] for more information.
+    pub fn check_syntax<S: AsRef<str>>(&self, code: S) -> JlrsResult<()> {
+        self.local_scope::<_, 1>(|mut frame| Value::parse_all(&mut frame, code, None).map(|_| ()))
+    }
+
+    /// Parse `code` and evaluate it in the module at `module`, e.g. `"Main"` or
+    /// `"Main.MyModule"`.
+    ///
+    /// Syntax errors are returned as `JlrsError::ParseError` and nothing is evaluated in that
+    /// case.
+    ///
+    /// Safety: The code can't be checked for correctness, nothing prevents you from causing a
+    /// segmentation fault with code like `unsafe_load(Ptr{Float64}(C_NULL))`.
+    pub unsafe fn eval_in<M: AsRef<str>, S: AsRef<str>>(
+        &self,
+        module: M,
+        code: S,
+    ) -> JlrsResult<()> {
+        self.local_scope::<_, 3>(|mut frame| {
+            let module = Module::from_path(&mut frame, module)?;
+            let expr = Value::parse_all(&mut frame, code, None)?;
+            Value::eval_expr(&mut frame, module, expr)
+                .map(|_| ())
+                .into_jlrs_result()
+        })
+    }
+
+    /// Parse all expressions in `code` without evaluating them.
+    ///
+    /// The result is an `Expr` with the head `:toplevel` that is rooted until the returned root
+    /// is dropped, it can be evaluated with [`Julia::eval_expr_in`]. See [`Value::parse_all`] for
+    /// more information.
+    pub fn parse_all<S: AsRef<str>>(&self, code: S) -> JlrsResult<GlobalRoot<Expr<'static>>> {
+        self.local_scope::<_, 1>(|mut frame| {
+            let expr = Value::parse_all(&mut frame, code, None)?.cast::<Expr>()?;
+            Ok(GlobalRoot::new(expr))
+        })
+    }
+
+    /// Evaluate the expression `expr` in the module at `module`, e.g. `"Main"` or
+    /// `"Main.MyModule"`.
+    ///
+    /// Safety: The expression can't be checked for correctness, nothing prevents you from
+    /// causing a segmentation fault by evaluating an expression like
+    /// `unsafe_load(Ptr{Float64}(C_NULL))`.
+    pub unsafe fn eval_expr_in<M: AsRef<str>>(
+        &self,
+        module: M,
+        expr: &GlobalRoot<Expr<'static>>,
+    ) -> JlrsResult<()> {
+        self.local_scope::<_, 2>(|mut frame| {
+            let module = Module::from_path(&mut frame, module)?;
+            let expr = expr.get()?.as_value();
+            Value::eval_expr(&mut frame, module, expr)
+                .map(|_| ())
+                .into_jlrs_result()
+        })
+    }
+
+    /// Apply `environment`, see [`Environment`] for more information.
+    ///
+    /// Safety: adding and instantiating packages can cause arbitrary Julia code to be executed.
+    pub unsafe fn environment(&self, environment: &Environment) -> JlrsResult<()> {
+        self.local_scope::<_, 0>(|frame| environment.apply(&frame))
+    }
+
+    /// Returns the paths in `DEPOT_PATH`.
+    ///
+    /// See [`pkg::depot_path`] for more information.
+    pub fn depot_path(&self) -> JlrsResult<Vec<PathBuf>> {
+        self.local_scope::<_, 0>(|frame| pkg::depot_path(&frame))
+    }
+
     pub fn returning<T>(&mut self) -> &mut impl Scope<'a, T> {
         self
     }
 }
 
 impl<'ctx, T> Scope<'ctx, T> for Julia<'ctx> {
+    #[cfg_attr(debug_assertions, track_caller)]
     fn scope<F>(&mut self, func: F) -> T
     where
         for<'scope> F: FnOnce(GcFrame<'scope>) -> T,
@@ -192,6 +279,12 @@ impl<'ctx, T> Scope<'ctx, T> for Julia<'ctx> {
             let frame = GcFrame::base(&self.stack);
 
             let ret = func(frame);
+            #[cfg(debug_assertions)]
+            crate::memory::frame_profiler::record(
+                std::panic::Location::caller(),
+                crate::memory::frame_profiler::FrameKind::Dynamic,
+                self.stack.size(),
+            );
             self.stack.pop_roots(0);
             ret
         }
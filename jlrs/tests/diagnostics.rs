@@ -0,0 +1,100 @@
+mod util;
+
+#[cfg(all(feature = "local-rt", feature = "diagnostics"))]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use jlrs::{
+        memory::{
+            diagnostics::{set_diagnostics_handler, DiagnosticKind, RootingDiagnostic},
+            gc::{Gc, GcCollection},
+        },
+        prelude::*,
+    };
+
+    use crate::util::JULIA;
+
+    static N_UNROOTED: AtomicUsize = AtomicUsize::new(0);
+
+    fn count_diagnostics(diagnostic: &RootingDiagnostic) {
+        match diagnostic.kind() {
+            DiagnosticKind::UnrootedAfterGc => N_UNROOTED.fetch_add(1, Ordering::SeqCst),
+        };
+    }
+
+    fn unrooted_use_after_gc_is_reported() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|frame| {
+                    let before = N_UNROOTED.load(Ordering::SeqCst);
+
+                    let unrooted = frame.unrooted();
+                    let value = Value::new(unrooted, 0x1234_5678_9abcu64);
+                    frame.gc_collect(GcCollection::Full);
+                    frame.gc_collect(GcCollection::Full);
+
+                    let _ = unsafe { value.as_value() };
+                    assert_eq!(N_UNROOTED.load(Ordering::SeqCst), before + 1);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn rooted_use_after_gc_is_not_reported() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let before = N_UNROOTED.load(Ordering::SeqCst);
+
+                    let unrooted = frame.unrooted();
+                    let value = Value::new(unrooted, 0x1234_5678_9abdu64);
+                    let rooted = unsafe { value.root(&mut frame) };
+                    frame.gc_collect(GcCollection::Full);
+
+                    let _ = unsafe { value.as_value() };
+                    assert_eq!(rooted.unbox::<u64>()?, 0x1234_5678_9abd);
+                    assert_eq!(N_UNROOTED.load(Ordering::SeqCst), before);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn use_before_gc_is_not_reported() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|frame| {
+                    let before = N_UNROOTED.load(Ordering::SeqCst);
+
+                    let unrooted = frame.unrooted();
+                    let value = Value::new(unrooted, 0x1234_5678_9abeu64);
+                    let value = unsafe { value.as_value() };
+                    assert_eq!(value.unbox::<u64>()?, 0x1234_5678_9abe);
+                    assert_eq!(N_UNROOTED.load(Ordering::SeqCst), before);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn diagnostics_tests() {
+        set_diagnostics_handler(count_diagnostics);
+        use_before_gc_is_not_reported();
+        rooted_use_after_gc_is_not_reported();
+        unrooted_use_after_gc_is_reported();
+    }
+}
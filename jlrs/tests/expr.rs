@@ -0,0 +1,130 @@
+mod util;
+
+#[cfg(feature = "local-rt")]
+mod tests {
+    use jlrs::{data::managed::expr::Expr, prelude::*};
+
+    use crate::util::JULIA;
+
+    fn build_and_eval_call() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let plus = Symbol::new(&frame, "+").as_value();
+                    let a = Value::new(&mut frame, 1isize);
+                    let b = Value::new(&mut frame, 2isize);
+                    let expr = Expr::call(&mut frame, plus, [a, b]).into_jlrs_result()?;
+
+                    assert_eq!(expr.head().unwrap().as_str()?, "call");
+                    assert_eq!(expr.n_args(), 3);
+
+                    let main = Module::main(&frame);
+                    let res = unsafe { Value::eval_expr(&mut frame, main, expr.as_value()) }
+                        .into_jlrs_result()?
+                        .unbox::<isize>()?;
+                    assert_eq!(res, 3);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn build_and_eval_function() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let name = Symbol::new(&frame, "expr_generated_fn").as_value();
+                    let x = Symbol::new(&frame, "x").as_value();
+                    let times = Symbol::new(&frame, "*").as_value();
+                    let two = Value::new(&mut frame, 2isize);
+
+                    let sig = Expr::call(&mut frame, name, [x]).into_jlrs_result()?;
+                    let body = Expr::call(&mut frame, times, [x, two]).into_jlrs_result()?;
+                    let line = Expr::line_number_node(&mut frame, 1, None).into_jlrs_result()?;
+                    let block =
+                        Expr::block(&mut frame, [line, body.as_value()]).into_jlrs_result()?;
+                    let func = Expr::function(&mut frame, sig.as_value(), block.as_value())
+                        .into_jlrs_result()?;
+
+                    let main = Module::main(&frame);
+                    unsafe { Value::eval_expr(&mut frame, main, func.as_value()) }
+                        .into_jlrs_result()?;
+
+                    let three = Value::new(&mut frame, 3isize);
+                    let call = Expr::call(&mut frame, name, [three]).into_jlrs_result()?;
+                    let res = unsafe { Value::eval_expr(&mut frame, main, call.as_value()) }
+                        .into_jlrs_result()?
+                        .unbox::<isize>()?;
+                    assert_eq!(res, 6);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn build_other_exprs() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let x = Symbol::new(&frame, "x").as_value();
+                    let one = Value::new(&mut frame, 1isize);
+
+                    let quoted = Expr::quote(&mut frame, x).into_jlrs_result()?;
+                    assert_eq!(quoted.head().unwrap().as_str()?, "quote");
+                    assert_eq!(quoted.n_args(), 1);
+
+                    let index = Expr::index(&mut frame, x, [one]).into_jlrs_result()?;
+                    assert_eq!(index.head().unwrap().as_str()?, "ref");
+                    assert_eq!(index.n_args(), 2);
+
+                    let macrocall =
+                        Expr::macrocall(&mut frame, "@show", None, [x]).into_jlrs_result()?;
+                    assert_eq!(macrocall.head().unwrap().as_str()?, "macrocall");
+                    assert_eq!(macrocall.n_args(), 3);
+
+                    let custom = Expr::new(&mut frame, "tuple", [one, x]).into_jlrs_result()?;
+                    assert_eq!(custom.head().unwrap().as_str()?, "tuple");
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn eval_expr_catches_exception() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let undefined = Symbol::new(&frame, "this_variable_is_undefined").as_value();
+                    let main = Module::main(&frame);
+                    let res = unsafe { Value::eval_expr(&mut frame, main, undefined) };
+                    assert!(res.is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn expr_tests() {
+        build_and_eval_call();
+        build_and_eval_function();
+        build_other_exprs();
+        eval_expr_catches_exception();
+    }
+}
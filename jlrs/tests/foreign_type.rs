@@ -0,0 +1,289 @@
+mod util;
+
+#[cfg(all(feature = "local-rt", feature = "jlrs-derive"))]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use jlrs::{
+        data::{
+            managed::{module::ModuleRef, value::typed::TypedValue},
+            types::{
+                exposed_fields::ExposedFields,
+                foreign_type::{destroy, is_destroyed, OpaqueType},
+            },
+        },
+        gc_safe::{GcSafeMutex, GcSafeRwLock},
+        memory::{gc::Gc, target::unrooted::Unrooted},
+        prelude::*,
+    };
+
+    use crate::util::JULIA;
+
+    #[derive(Mark)]
+    struct Inner {
+        values: Vec<Option<ValueRef<'static, 'static>>>,
+        #[allow(dead_code)]
+        count: usize,
+    }
+
+    #[derive(ForeignType)]
+    struct Marked {
+        value: ValueRef<'static, 'static>,
+        array: [Option<ValueRef<'static, 'static>>; 2],
+        locked: GcSafeMutex<Option<ModuleRef<'static>>>,
+        shared: GcSafeRwLock<Vec<ValueRef<'static, 'static>>>,
+        inner: Box<Inner>,
+        #[jlrs(skip)]
+        #[allow(dead_code)]
+        unmarked: Option<ValueRef<'static, 'static>>,
+    }
+
+    unsafe impl Send for Marked {}
+
+    #[derive(ForeignType)]
+    struct MarkedTuple(ValueRef<'static, 'static>, #[allow(dead_code)] u32);
+
+    unsafe impl Send for MarkedTuple {}
+
+    static N_FINALIZED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Closable(#[allow(dead_code)] u32);
+
+    unsafe impl OpaqueType for Closable {
+        fn finalize(&mut self, target: Option<Unrooted<'_>>) {
+            assert!(target.is_some());
+            N_FINALIZED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[derive(ExposedFields)]
+    struct Particle {
+        #[jlrs(expose)]
+        mass: f64,
+        #[jlrs(expose)]
+        charge: i32,
+        #[allow(dead_code)]
+        history: Vec<f64>,
+    }
+
+    unsafe impl OpaqueType for Particle {}
+
+    fn derived_foreign_type_is_marked() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    unsafe {
+                        let name = Symbol::new(&frame, "DerivedMarked");
+                        let main = Module::main(&frame);
+                        Marked::create_type(&mut frame, name, main);
+                    }
+
+                    let marked = frame.scope(|mut frame| {
+                        let value = Value::new(&mut frame, 1usize).leak();
+                        let first = Value::new(&mut frame, 2usize).leak();
+                        let shared = Value::new(&mut frame, 3usize).leak();
+                        let inner = Value::new(&mut frame, 4usize).leak();
+
+                        let data = Marked {
+                            value,
+                            array: [Some(first), None],
+                            locked: GcSafeMutex::new(Some(Module::main(&frame).leak())),
+                            shared: GcSafeRwLock::new(vec![shared]),
+                            inner: Box::new(Inner {
+                                values: vec![None, Some(inner)],
+                                count: 1,
+                            }),
+                            unmarked: None,
+                        };
+
+                        TypedValue::new(&mut frame, data).leak()
+                    });
+
+                    let marked = unsafe { marked.root(&mut frame) };
+                    frame.gc_collect(jlrs::memory::gc::GcCollection::Full);
+
+                    let tracked = unsafe { marked.track_shared()? };
+                    unsafe {
+                        assert_eq!(tracked.value.as_value().unbox::<usize>()?, 1);
+                        let first = tracked.array[0].unwrap().as_value();
+                        assert_eq!(first.unbox::<usize>()?, 2);
+                        let shared = tracked.shared.read()[0].as_value();
+                        assert_eq!(shared.unbox::<usize>()?, 3);
+                        let inner = tracked.inner.values[1].unwrap().as_value();
+                        assert_eq!(inner.unbox::<usize>()?, 4);
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn derived_tuple_struct_is_marked() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    unsafe {
+                        let name = Symbol::new(&frame, "DerivedMarkedTuple");
+                        let main = Module::main(&frame);
+                        MarkedTuple::create_type(&mut frame, name, main);
+                    }
+
+                    let marked = frame.scope(|mut frame| {
+                        let value = Value::new(&mut frame, 5usize).leak();
+                        TypedValue::new(&mut frame, MarkedTuple(value, 0)).leak()
+                    });
+
+                    let marked = unsafe { marked.root(&mut frame) };
+                    frame.gc_collect(jlrs::memory::gc::GcCollection::Full);
+
+                    let tracked = unsafe { marked.track_shared()? };
+                    let value = unsafe { tracked.0.as_value() };
+                    assert_eq!(value.unbox::<usize>()?, 5);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn destroy_foreign_value() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    unsafe {
+                        let name = Symbol::new(&frame, "DerivedMarked");
+                        let main = Module::main(&frame);
+                        Marked::create_type(&mut frame, name, main);
+                    }
+
+                    let marked = frame.scope(|mut frame| {
+                        let value = Value::new(&mut frame, 6usize).leak();
+                        let shared = Value::new(&mut frame, 7usize).leak();
+                        let inner = Value::new(&mut frame, 8usize).leak();
+
+                        let data = Marked {
+                            value,
+                            array: [None, Some(value)],
+                            locked: GcSafeMutex::new(None),
+                            shared: GcSafeRwLock::new(vec![shared]),
+                            inner: Box::new(Inner {
+                                values: vec![Some(inner)],
+                                count: 1,
+                            }),
+                            unmarked: None,
+                        };
+
+                        TypedValue::new(&mut frame, data).leak()
+                    });
+
+                    let marked = unsafe { marked.root(&mut frame) }.as_value();
+                    assert!(unsafe { destroy::<Marked>(marked)? });
+
+                    // The data has been dropped, so the destroyed instance must not be marked.
+                    frame.gc_collect(jlrs::memory::gc::GcCollection::Full);
+                    frame.gc_collect(jlrs::memory::gc::GcCollection::Full);
+
+                    assert!(is_destroyed(marked));
+                    assert!(marked.track_shared::<Marked>().is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn destroy_opaque_value() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    unsafe {
+                        let name = Symbol::new(&frame, "Closable");
+                        let main = Module::main(&frame);
+                        <Closable as OpaqueType>::create_type(&mut frame, name, main);
+                    }
+
+                    let value = Value::new(&mut frame, Closable(1));
+                    assert!(!is_destroyed(value));
+
+                    {
+                        let _tracked = value.track_shared::<Closable>()?;
+                        assert!(unsafe { destroy::<Closable>(value) }.is_err());
+                        assert!(!is_destroyed(value));
+                    }
+
+                    assert!(unsafe { destroy::<Closable>(value)? });
+                    assert!(is_destroyed(value));
+                    assert_eq!(N_FINALIZED.load(Ordering::Relaxed), 1);
+                    assert!(value.track_shared::<Closable>().is_err());
+
+                    assert!(!unsafe { destroy::<Closable>(value)? });
+                    assert_eq!(N_FINALIZED.load(Ordering::Relaxed), 1);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn derived_exposed_fields() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    assert_eq!(Particle::FIELD_NAMES, &["mass", "charge"]);
+                    assert_eq!(Particle::field_index("charge"), Some(1));
+                    assert_eq!(Particle::field_index("history"), None);
+
+                    let mut particle = Particle {
+                        mass: 1.0,
+                        charge: -1,
+                        history: vec![],
+                    };
+
+                    let mass = particle.get_field(&mut frame, 0);
+                    assert_eq!(mass.unbox::<f64>()?, 1.0);
+
+                    let charge = Value::new(&mut frame, 2i32);
+                    particle.set_field(1, charge)?;
+                    assert_eq!(particle.charge, 2);
+
+                    let wrong_type = Value::new(&mut frame, 2.0f32);
+                    assert!(particle.set_field(0, wrong_type).is_err());
+                    assert_eq!(particle.mass, 1.0);
+
+                    let charge_ty = Particle::field_type(&mut frame, 1);
+                    assert_eq!(
+                        charge_ty.as_value(),
+                        DataType::int32_type(&frame).as_value()
+                    );
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn foreign_type_tests() {
+        derived_foreign_type_is_marked();
+        derived_tuple_struct_is_marked();
+        destroy_opaque_value();
+        destroy_foreign_value();
+        derived_exposed_fields();
+    }
+}
@@ -0,0 +1,131 @@
+mod util;
+
+#[cfg(all(feature = "local-rt", debug_assertions))]
+mod tests {
+    use jlrs::{
+        memory::frame_profiler::{
+            enable_frame_profiler, frame_profile, reset_frame_profile, FrameKind, Provisioning,
+        },
+        prelude::*,
+    };
+
+    use crate::util::JULIA;
+
+    fn profile_local_scopes() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|frame| {
+                    reset_frame_profile();
+                    enable_frame_profiler(true);
+
+                    for i in 0..3 {
+                        frame.local_scope::<_, 4>(|mut frame| {
+                            for j in 0..=i {
+                                Value::new(&mut frame, j as usize);
+                            }
+                        });
+                    }
+
+                    frame.local_scope::<_, 2>(|mut frame| {
+                        Value::new(&mut frame, 1usize);
+                        Value::new(&mut frame, 2usize);
+                    });
+
+                    enable_frame_profiler(false);
+
+                    let profile = frame_profile();
+                    assert_eq!(profile.len(), 2);
+
+                    let over = &profile[0];
+                    assert_eq!(over.kind(), FrameKind::Local(4));
+                    assert_eq!(over.n_calls(), 3);
+                    assert_eq!(over.max_roots(), 3);
+                    assert_eq!(over.suggested_size(), 3);
+                    assert_eq!(over.provisioning(), Provisioning::Over { unused: 1 });
+
+                    let exact = &profile[1];
+                    assert_eq!(exact.kind(), FrameKind::Local(2));
+                    assert_eq!(exact.provisioning(), Provisioning::Exact);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn reserved_slots_are_counted() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|frame| {
+                    reset_frame_profile();
+                    enable_frame_profiler(true);
+
+                    frame.local_scope::<_, 2>(|mut frame| {
+                        let _output = frame.local_output();
+                        Value::new(&mut frame, 1usize);
+                    });
+
+                    enable_frame_profiler(false);
+
+                    let profile = frame_profile();
+                    assert_eq!(profile.len(), 1);
+                    assert_eq!(profile[0].max_roots(), 2);
+                    assert_eq!(profile[0].provisioning(), Provisioning::Exact);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn profile_dynamic_scopes() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    reset_frame_profile();
+                    enable_frame_profiler(true);
+
+                    frame.scope(|mut frame| {
+                        Value::new(&mut frame, 1usize);
+                        frame.scope(|mut frame| {
+                            Value::new(&mut frame, 2usize);
+                            Value::new(&mut frame, 3usize);
+                        });
+                    });
+
+                    enable_frame_profiler(false);
+
+                    let profile = frame_profile();
+                    assert_eq!(profile.len(), 2);
+                    assert_eq!(profile[0].kind(), FrameKind::Dynamic);
+                    assert_eq!(
+                        profile[0].provisioning(),
+                        Provisioning::Dynamic { suggested: 1 }
+                    );
+                    assert_eq!(
+                        profile[1].provisioning(),
+                        Provisioning::Dynamic { suggested: 2 }
+                    );
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn frame_profiler_tests() {
+        profile_local_scopes();
+        reserved_slots_are_counted();
+        profile_dynamic_scopes();
+    }
+}
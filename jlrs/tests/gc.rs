@@ -2,7 +2,7 @@ mod util;
 #[cfg(feature = "local-rt")]
 mod tests {
     use jlrs::{
-        memory::gc::{Gc, GcCollection},
+        memory::gc::{counted_free, counted_malloc, counted_realloc, Gc, GcCollection},
         prelude::*,
     };
 
@@ -68,10 +68,72 @@ mod tests {
         })
     }
 
+    fn read_gc_stats() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let mut jlrs = jlrs.instance(&mut frame);
+
+            jlrs.gc_collect(GcCollection::Full);
+            let before = jlrs.gc_stats();
+            assert!(before.live_bytes > 0);
+            assert!(before.collections > 0);
+
+            jlrs.gc_collect(GcCollection::Full);
+            let after = jlrs.gc_stats();
+            assert!(after.collections > before.collections);
+            assert!(after.full_collections > before.full_collections);
+            assert!(after.total_time_ns >= before.total_time_ns);
+
+            jlrs.returning::<JlrsResult<_>>()
+                .scope(|frame| {
+                    let stats = frame.gc_stats();
+                    assert!(stats.total_allocated_bytes > 0);
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn account_external_memory() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let jlrs = jlrs.instance(&mut frame);
+
+            let before = jlrs.gc_stats();
+            unsafe {
+                let ptr = counted_malloc(1 << 20);
+                assert!(!ptr.is_null());
+                let ptr = counted_realloc(ptr, 1 << 20, 2 << 20);
+                assert!(!ptr.is_null());
+                counted_free(ptr, 2 << 20);
+            }
+
+            let after = jlrs.gc_stats();
+            assert!(after.malloc_calls > before.malloc_calls);
+            assert!(after.free_calls > before.free_calls);
+        })
+    }
+
+    #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8")))]
+    fn set_heap_size_hint() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let jlrs = jlrs.instance(&mut frame);
+            jlrs.gc_set_heap_size_hint(1 << 32);
+        })
+    }
+
     #[test]
     fn gc_tests() {
         disable_enable_gc();
         collect_garbage();
         insert_safepoint();
+        read_gc_stats();
+        account_external_memory();
+        #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8")))]
+        set_heap_size_hint();
     }
 }
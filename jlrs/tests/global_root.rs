@@ -0,0 +1,136 @@
+mod util;
+
+#[cfg(feature = "local-rt")]
+mod tests {
+    use jlrs::{
+        error::{JlrsError, RuntimeError},
+        memory::{
+            gc::{Gc, GcCollection},
+            global_root::GlobalRoot,
+        },
+        prelude::*,
+    };
+
+    use crate::util::JULIA;
+
+    fn global_root_outlives_scope() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let mut jlrs = jlrs.instance(&mut frame);
+
+            let root = jlrs
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let value = Value::new(&mut frame, 0x1234_5678_9abcu64);
+                    Ok(GlobalRoot::new(value))
+                })
+                .unwrap();
+
+            jlrs.returning::<JlrsResult<_>>()
+                .scope(|frame| {
+                    frame.gc_collect(GcCollection::Full);
+                    let value = root.get()?;
+                    assert_eq!(value.unbox::<u64>()?, 0x1234_5678_9abc);
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn root_global_root_in_frame() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let s = JuliaString::new(&mut frame, "global root");
+                    let root = GlobalRoot::new(s);
+                    let rooted = root.root(&mut frame);
+                    std::mem::drop(root);
+
+                    frame.gc_collect(GcCollection::Full);
+                    assert_eq!(rooted.as_str()?, "global root");
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn many_global_roots() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|frame| {
+                    let mut roots = Vec::new();
+                    for i in 0..64u64 {
+                        let root = frame.local_scope::<_, 1>(|mut frame| {
+                            let value = Value::new(&mut frame, 0x1000_0000_0000u64 + i);
+                            GlobalRoot::new(value)
+                        });
+                        roots.push(root);
+                    }
+
+                    roots.retain(|root| {
+                        let value = root.get().unwrap().unbox::<u64>().unwrap();
+                        value % 2 == 0
+                    });
+
+                    frame.gc_collect(GcCollection::Full);
+
+                    for (i, root) in roots.iter().enumerate() {
+                        let value = root.get()?.unbox::<u64>()?;
+                        assert_eq!(value, 0x1000_0000_0000u64 + 2 * i as u64);
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn global_root_is_checked_on_other_threads() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let value = Value::new(&mut frame, 1usize);
+                    let root = GlobalRoot::new(value);
+
+                    let root = std::thread::spawn(move || {
+                        let err = root.get().unwrap_err();
+                        assert!(matches!(
+                            err.as_ref(),
+                            JlrsError::RuntimeError(RuntimeError::InvalidThread)
+                        ));
+                        root
+                    })
+                    .join()
+                    .unwrap();
+
+                    assert_eq!(root.get()?.unbox::<usize>()?, 1);
+
+                    // Global roots can be dropped on any thread.
+                    std::thread::spawn(move || std::mem::drop(root))
+                        .join()
+                        .unwrap();
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn global_root_tests() {
+        global_root_outlives_scope();
+        root_global_root_in_frame();
+        many_global_roots();
+        global_root_is_checked_on_other_threads();
+    }
+}
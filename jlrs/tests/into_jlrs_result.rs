@@ -1,7 +1,7 @@
 mod util;
 #[cfg(feature = "local-rt")]
 mod tests {
-    use jlrs::prelude::*;
+    use jlrs::{error::JlrsError, prelude::*};
 
     use super::util::JULIA;
 
@@ -37,9 +37,70 @@ mod tests {
         });
     }
 
+    fn exc_to_structured_exception() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame).scope(|mut frame| unsafe {
+                let func = Value::eval_string(
+                    &mut frame,
+                    "function jlrs_throws_bounds_error() [1, 2][3] end",
+                )
+                .unwrap();
+                let err = func.call0(&mut frame).into_jlrs_result().unwrap_err();
+
+                let JlrsError::Exception(exc) = *err else {
+                    panic!("expected an exception")
+                };
+
+                // The fields and backtrace are only captured on request.
+                assert_eq!(exc.type_name(), Some("BoundsError"));
+                assert!(exc.fields().is_empty());
+                assert!(exc.backtrace().is_empty());
+
+                let err = func
+                    .call0(&mut frame)
+                    .into_jlrs_result_with_details()
+                    .unwrap_err();
+
+                let JlrsError::Exception(exc) = *err else {
+                    panic!("expected an exception")
+                };
+
+                assert_eq!(exc.type_name(), Some("BoundsError"));
+                assert!(exc.get_message().contains("BoundsError"));
+
+                let names: Vec<_> = exc.fields().iter().map(|f| f.name.as_str()).collect();
+                assert_eq!(names, ["a", "i"]);
+                assert_eq!(exc.field("a").unwrap().value, "[1, 2]");
+                assert_eq!(exc.field("i").unwrap().value, "(3,)");
+                assert!(exc.field("b").is_none());
+
+                assert!(exc
+                    .backtrace()
+                    .iter()
+                    .any(|f| f.function == "jlrs_throws_bounds_error"));
+            });
+        });
+    }
+
+    fn message_to_exception() {
+        let JlrsError::Exception(exc) = JlrsError::exception("msg") else {
+            panic!("expected an exception")
+        };
+
+        assert_eq!(exc.get_message(), "msg");
+        assert!(exc.type_name().is_none());
+        assert!(exc.fields().is_empty());
+        assert!(exc.backtrace().is_empty());
+    }
+
     #[test]
     fn test_into_jlrs_result() {
         ok_to_jlrs_result();
         exc_to_jlrs_result();
+        exc_to_structured_exception();
+        message_to_exception();
     }
 }
@@ -0,0 +1,155 @@
+mod util;
+#[cfg(feature = "local-rt")]
+mod tests {
+    use jlrs::{
+        error::{JuliaException, JuliaResultExt},
+        prelude::*,
+    };
+
+    use super::util::JULIA;
+
+    fn classify_method_error() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame).scope(|mut frame| unsafe {
+                let func = Module::base(&frame)
+                    .global(&frame, "+")
+                    .unwrap()
+                    .as_managed();
+                let arg = JuliaString::new(&mut frame, "a").as_value();
+                let exc = func
+                    .call2(&mut frame, arg, arg)
+                    .classify_exception()
+                    .unwrap_err();
+
+                let JuliaException::MethodError { f, args } = exc else {
+                    panic!("expected a MethodError, got {:?}", exc.type_name())
+                };
+                assert_eq!(f, func);
+                assert_eq!(args.n_fields(), 2);
+                assert_eq!(exc.type_name(), Some("MethodError"));
+            });
+        });
+    }
+
+    fn classify_bounds_error() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame).scope(|mut frame| unsafe {
+                let exc = Value::eval_string(&mut frame, "[1, 2][3]")
+                    .classify_exception()
+                    .unwrap_err();
+
+                let JuliaException::BoundsError { a, i } = exc else {
+                    panic!("expected a BoundsError, got {:?}", exc.type_name())
+                };
+                assert!(a.unwrap().is::<Array>());
+                assert_eq!(i.unwrap().display_string().unwrap(), "(3,)");
+            });
+        });
+    }
+
+    fn classify_messages() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame).scope(|mut frame| unsafe {
+                let exc = Value::eval_string(&mut frame, "throw(ArgumentError(\"bad arg\"))")
+                    .classify_exception()
+                    .unwrap_err();
+                assert!(
+                    matches!(exc, JuliaException::ArgumentError { ref msg } if msg == "bad arg")
+                );
+
+                let exc = Value::eval_string(&mut frame, "error(\"oops\")")
+                    .classify_exception()
+                    .unwrap_err();
+                assert!(matches!(exc, JuliaException::ErrorException { ref msg } if msg == "oops"));
+
+                let exc = Value::eval_string(&mut frame, "sqrt(-1.0)")
+                    .classify_exception()
+                    .unwrap_err();
+                let JuliaException::DomainError { val, msg } = exc else {
+                    panic!("expected a DomainError, got {:?}", exc.type_name())
+                };
+                assert_eq!(val.unbox::<f64>().unwrap(), -1.0);
+                assert!(msg.is_some());
+
+                let exc = Value::eval_string(&mut frame, "throw(DomainError(-1.0))")
+                    .classify_exception()
+                    .unwrap_err();
+                let JuliaException::DomainError { val, msg } = exc else {
+                    panic!("expected a DomainError, got {:?}", exc.type_name())
+                };
+                assert_eq!(val.unbox::<f64>().unwrap(), -1.0);
+                assert!(msg.is_none());
+            });
+        });
+    }
+
+    fn classify_payloads() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame).scope(|mut frame| unsafe {
+                let exc = Value::eval_string(&mut frame, "Dict(1 => 2)[3]")
+                    .classify_exception()
+                    .unwrap_err();
+                let JuliaException::KeyError { key } = exc else {
+                    panic!("expected a KeyError, got {:?}", exc.type_name())
+                };
+                assert_eq!(key.unbox::<i64>().unwrap(), 3);
+
+                let exc = Value::eval_string(&mut frame, "jlrs_undefined_variable")
+                    .classify_exception()
+                    .unwrap_err();
+                let JuliaException::UndefVarError { var } = exc else {
+                    panic!("expected an UndefVarError, got {:?}", exc.type_name())
+                };
+                assert_eq!(var.as_str().unwrap(), "jlrs_undefined_variable");
+
+                let exc = Value::eval_string(&mut frame, "throw(InterruptException())")
+                    .classify_exception()
+                    .unwrap_err();
+                assert!(matches!(exc, JuliaException::InterruptException));
+            });
+        });
+    }
+
+    fn classify_other() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame).scope(|mut frame| unsafe {
+                let exc = Value::eval_string(
+                    &mut frame,
+                    "struct JlrsCustomException <: Exception end; throw(JlrsCustomException())",
+                )
+                .classify_exception()
+                .unwrap_err();
+
+                let JuliaException::Other(value) = exc else {
+                    panic!("expected an unknown exception, got {:?}", exc.type_name())
+                };
+                assert_eq!(value.datatype().name(), "JlrsCustomException");
+                assert!(exc.type_name().is_none());
+            });
+        });
+    }
+
+    #[test]
+    fn julia_exception_tests() {
+        classify_method_error();
+        classify_bounds_error();
+        classify_messages();
+        classify_payloads();
+        classify_other();
+    }
+}
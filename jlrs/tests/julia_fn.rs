@@ -0,0 +1,109 @@
+mod util;
+
+#[cfg(feature = "local-rt")]
+mod tests {
+    use jlrs::{data::managed::julia_fn::JuliaFn, prelude::*};
+
+    use crate::util::JULIA;
+
+    fn call_typed_function() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|frame| {
+                    let func = unsafe { Module::base(&frame).function(&frame, "+")?.as_managed() };
+                    let add = func.as_julia_fn::<(f64, f64), f64>();
+                    let res = unsafe { add.call(&frame, (1.0, 2.0))? };
+                    assert_eq!(res, 3.0);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn call_typed_function_no_args() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let func =
+                        unsafe { Value::eval_string(&mut frame, "() -> 3").into_jlrs_result()? };
+                    let three = JuliaFn::<(), isize>::from_value(func)?;
+                    let res = unsafe { three.call(&frame, ())? };
+                    assert_eq!(res, 3);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn typed_function_throws() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let func = unsafe {
+                        Value::eval_string(&mut frame, "x -> throw(ArgumentError(\"x\"))")
+                            .into_jlrs_result()?
+                    };
+                    let throws = JuliaFn::<(u32,), u32>::from_value(func)?;
+                    let res = unsafe { throws.call(&frame, (1,)) };
+                    assert!(res.is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn typed_function_wrong_return_type() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|frame| {
+                    let func = unsafe { Module::base(&frame).function(&frame, "+")?.as_managed() };
+                    let add = func.as_julia_fn::<(i32, i32), f64>();
+                    let res = unsafe { add.call(&frame, (1, 2)) };
+                    assert!(res.is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn not_a_function() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let v = Value::new(&mut frame, 1usize);
+                    assert!(JuliaFn::<(usize,), usize>::from_value(v).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn julia_fn_tests() {
+        call_typed_function();
+        call_typed_function_no_args();
+        typed_function_throws();
+        typed_function_wrong_return_type();
+        not_a_function();
+    }
+}
@@ -0,0 +1,160 @@
+mod util;
+
+#[cfg(feature = "local-rt")]
+mod tests {
+    use jlrs::{
+        data::managed::{function::Function, method::Method},
+        prelude::*,
+    };
+
+    use crate::util::JULIA;
+
+    const DEFINE_FUNC: &str = "
+        method_intro(a::Int, b::Float64) = a + b
+        method_intro(x::String) = x
+        method_intro";
+
+    fn list_methods() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let func = unsafe { Value::eval_string(&mut frame, DEFINE_FUNC) }
+                        .into_jlrs_result()?
+                        .cast::<Function>()?;
+
+                    let methods = func.methods(&frame)?;
+                    assert_eq!(methods.len(), 2);
+
+                    let mut names = methods
+                        .iter()
+                        .map(|m| unsafe { m.as_managed() }.argument_names(&frame))
+                        .map(|names| {
+                            names.map(|names| {
+                                names
+                                    .iter()
+                                    .map(|n| n.as_string().unwrap())
+                                    .collect::<Vec<_>>()
+                            })
+                        })
+                        .collect::<JlrsResult<Vec<_>>>()?;
+                    names.sort();
+                    assert_eq!(names, [vec!["a", "b"], vec!["x"]]);
+
+                    for method in methods {
+                        let method = unsafe { method.as_managed() };
+                        assert_eq!(method.name().unwrap().as_str()?, "method_intro");
+                        assert_eq!(method.module().unwrap(), Module::main(&frame));
+                        assert!(method.signature().is_some());
+                        assert!(!method.is_vararg().unwrap());
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn check_has_method() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let func = unsafe { Value::eval_string(&mut frame, DEFINE_FUNC) }
+                        .into_jlrs_result()?
+                        .cast::<Function>()?;
+
+                    let int_ty = DataType::int64_type(&frame).as_value();
+                    let float_ty = DataType::float64_type(&frame).as_value();
+                    let string_ty = DataType::string_type(&frame).as_value();
+
+                    assert!(func.has_method(&frame, &[int_ty, float_ty])?);
+                    assert!(func.has_method(&frame, &[string_ty])?);
+                    assert!(!func.has_method(&frame, &[float_ty, int_ty])?);
+
+                    let a = Value::new(&mut frame, 1i64);
+                    let b = Value::new(&mut frame, 2.0f64);
+                    assert!(func.is_applicable(&frame, &[a, b])?);
+                    assert!(!func.is_applicable(&frame, &[b, a])?);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn find_most_specific_method() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let func = unsafe { Value::eval_string(&mut frame, DEFINE_FUNC) }
+                        .into_jlrs_result()?
+                        .cast::<Function>()?;
+
+                    let string_ty = DataType::string_type(&frame).as_value();
+                    let int_ty = DataType::int64_type(&frame).as_value();
+
+                    let method = func.lookup_method(&frame, &[string_ty])?.unwrap();
+                    let method: Method = unsafe { method.as_managed() };
+                    assert_eq!(method.n_args().unwrap(), 2);
+
+                    assert!(func.lookup_method(&frame, &[int_ty])?.is_none());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn find_method_instance() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let func = unsafe { Value::eval_string(&mut frame, DEFINE_FUNC) }
+                        .into_jlrs_result()?
+                        .cast::<Function>()?;
+
+                    let s = JuliaString::new(&mut frame, "foo").as_value();
+                    unsafe { func.call1(&mut frame, s) }.into_jlrs_result()?;
+
+                    let string_ty = DataType::string_type(&frame).as_value();
+                    let mi = func.method_instance(&frame, &[string_ty])?.unwrap();
+                    let mi = unsafe { mi.as_managed() };
+                    let method = unsafe { mi.method().unwrap().as_managed() };
+                    assert_eq!(method.name().unwrap().as_str()?, "method_intro");
+
+                    if let Some(ci) = mi.cache() {
+                        let ci = unsafe { ci.as_managed() };
+                        assert!(ci.min_world().unwrap() <= ci.max_world().unwrap());
+                    }
+
+                    let mt = func.method_table();
+                    if let Some(mt) = mt {
+                        let mt = unsafe { mt.as_managed() };
+                        assert!(mt.name().is_some());
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn method_tests() {
+        list_methods();
+        check_has_method();
+        find_most_specific_method();
+        find_method_instance();
+    }
+}
@@ -529,6 +529,368 @@ mod tests {
         })
     }
 
+    fn list_names() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let res = jlrs
+                .instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    unsafe {
+                        Value::eval_string(
+                            &mut frame,
+                            "module ListNames
+                                export exported_fn
+                                exported_fn() = 1
+                                hidden_fn() = 2
+                                const HIDDEN_CONST = 3
+                            end",
+                        )
+                        .into_jlrs_result()?;
+                    }
+
+                    let module = Module::main(&frame).submodule(&frame, "ListNames")?;
+                    let module = unsafe { module.as_managed() };
+
+                    let exported = module.exported_names(&frame)?;
+                    let exported: Vec<_> =
+                        exported.iter().map(|s| s.as_string().unwrap()).collect();
+                    assert!(exported.contains(&"exported_fn".to_string()));
+                    assert!(!exported.contains(&"hidden_fn".to_string()));
+
+                    let all = module.names(&frame, true, false)?;
+                    let all: Vec<_> = all.iter().map(|s| s.as_string().unwrap()).collect();
+                    assert!(all.contains(&"exported_fn".to_string()));
+                    assert!(all.contains(&"hidden_fn".to_string()));
+                    assert!(all.contains(&"HIDDEN_CONST".to_string()));
+
+                    Ok(())
+                });
+
+            assert!(res.is_ok());
+        })
+    }
+
+    fn list_bindings() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let res = jlrs
+                .instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    unsafe {
+                        Value::eval_string(
+                            &mut frame,
+                            "module ListBindings
+                                import Base: sin
+                                export VISIBLE
+                                const VISIBLE = 1
+                                mutable_global = 2
+                            end",
+                        )
+                        .into_jlrs_result()?;
+                    }
+
+                    let module = Module::main(&frame).submodule(&frame, "ListBindings")?;
+                    let module = unsafe { module.as_managed() };
+                    let bindings = unsafe { module.bindings(&frame, true, true)? };
+                    let find = |name: &str| {
+                        bindings
+                            .iter()
+                            .find(|b| b.name().as_str().unwrap() == name)
+                            .copied()
+                            .unwrap()
+                    };
+
+                    let visible = find("VISIBLE");
+                    assert!(visible.is_const());
+                    assert!(visible.is_exported());
+                    assert!(!visible.is_imported());
+                    let owner = unsafe { visible.owner().unwrap().as_managed() };
+                    assert_eq!(owner, module);
+
+                    let mutable_global = find("mutable_global");
+                    assert!(!mutable_global.is_const());
+                    assert!(!mutable_global.is_exported());
+
+                    let sin = find("sin");
+                    assert!(sin.is_imported());
+                    let owner = unsafe { sin.owner().unwrap().as_managed() };
+                    assert_eq!(owner, Module::base(&frame));
+
+                    Ok(())
+                });
+
+            assert!(res.is_ok());
+        })
+    }
+
+    fn list_submodules() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let res = jlrs
+                .instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    unsafe {
+                        Value::eval_string(
+                            &mut frame,
+                            "module ListSubmodules
+                                module A
+                                    module B end
+                                end
+                                module C end
+                                const AliasOfA = A
+                                import Base: Iterators
+                            end",
+                        )
+                        .into_jlrs_result()?;
+                    }
+
+                    let module = Module::main(&frame).submodule(&frame, "ListSubmodules")?;
+                    let module = unsafe { module.as_managed() };
+
+                    let direct = module.submodules(&frame, false)?;
+                    let mut direct: Vec<_> = direct
+                        .iter()
+                        .map(|m| unsafe { m.as_managed() }.name().as_string().unwrap())
+                        .collect();
+                    direct.sort();
+                    assert_eq!(direct, ["A", "C"]);
+
+                    let recursive = module.submodules(&frame, true)?;
+                    let mut recursive: Vec<_> = recursive
+                        .iter()
+                        .map(|m| unsafe { m.as_managed() }.name().as_string().unwrap())
+                        .collect();
+                    recursive.sort();
+                    assert_eq!(recursive, ["A", "B", "C"]);
+
+                    Ok(())
+                });
+
+            assert!(res.is_ok());
+        })
+    }
+
+    fn sandbox_module() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let res = jlrs
+                .instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let main = Module::main(&frame);
+                    let sandbox = Module::new(&mut frame, "Sandbox", main)?;
+                    assert_eq!(sandbox.name().as_str()?, "Sandbox");
+                    assert_eq!(sandbox.parent(), main);
+                    assert!(main.global(&frame, "Sandbox").is_err());
+
+                    let res = unsafe { sandbox.eval_string(&mut frame, "sandboxed = 1 + 2") }
+                        .into_jlrs_result()?
+                        .unbox::<i64>()?;
+                    assert_eq!(res, 3);
+                    assert!(sandbox.global(&frame, "sandboxed").is_ok());
+                    assert!(main.global(&frame, "sandboxed").is_err());
+
+                    let sym = Symbol::new(&frame, "sandboxed").as_value();
+                    let res = unsafe { sandbox.eval(&mut frame, sym) }
+                        .into_jlrs_result()?
+                        .unbox::<i64>()?;
+                    assert_eq!(res, 3);
+
+                    let res = unsafe { sandbox.eval_string(&mut frame, "eval(:sandboxed)") }
+                        .into_jlrs_result()?
+                        .unbox::<i64>()?;
+                    assert_eq!(res, 3);
+                    assert!(unsafe { sandbox.eval_string(&mut frame, "include") }.is_ok());
+
+                    let other = Module::new(&mut frame, "Sandbox", main)?;
+                    assert_ne!(other, sandbox);
+                    assert!(other.global(&frame, "sandboxed").is_err());
+
+                    assert!(unsafe { sandbox.include(&mut frame, "does_not_exist.jl") }.is_err());
+
+                    Ok(())
+                });
+
+            assert!(res.is_ok());
+        })
+    }
+
+    fn bare_sandbox_module() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let res = jlrs
+                .instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let main = Module::main(&frame);
+                    let sandbox = Module::new_bare(&mut frame, "BareSandbox", main);
+
+                    let res = unsafe { sandbox.eval_string(&mut frame, "x = 1") };
+                    assert!(res.is_ok());
+
+                    let res = unsafe { sandbox.eval_string(&mut frame, "y = x + 1") };
+                    assert!(res.is_err());
+
+                    Ok(())
+                });
+
+            assert!(res.is_ok());
+        })
+    }
+
+    #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8")))]
+    fn declare_typed_global() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let res = jlrs
+                .instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let main = Module::main(&frame);
+                    let module = Module::new(&mut frame, "TypedGlobals", main)?;
+                    let ty = DataType::float64_type(&frame).as_value();
+                    let value = Value::new(&mut frame, 1i64);
+
+                    unsafe { module.declare_global(&frame, "typed", ty, Some(value))? };
+                    let typed = module.global(&frame, "typed")?;
+                    assert_eq!(unsafe { typed.as_value() }.unbox::<f64>()?, 1.0);
+
+                    let string = JuliaString::new(&mut frame, "foo").as_value();
+                    let res = unsafe { module.set_global(&frame, "typed", string) };
+                    assert!(res.is_err());
+
+                    Ok(())
+                });
+
+            assert!(res.is_ok());
+        })
+    }
+
+    #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8")))]
+    fn set_global_atomically() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let res = jlrs
+                .instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    use jlrs::data::managed::module::MemoryOrder;
+
+                    let main = Module::main(&frame);
+                    let module = Module::new(&mut frame, "AtomicGlobals", main)?;
+                    let ty = DataType::int64_type(&frame).as_value();
+                    unsafe { module.declare_global(&frame, "counter", ty, None)? };
+
+                    let one = Value::new(&mut frame, 1i64);
+                    unsafe {
+                        module.set_global_atomic(
+                            &mut frame,
+                            "counter",
+                            one,
+                            MemoryOrder::SequentiallyConsistent,
+                        )
+                    }
+                    .into_jlrs_result()?;
+
+                    let counter = module.global(&frame, "counter")?;
+                    assert_eq!(unsafe { counter.as_value() }.unbox::<i64>()?, 1);
+
+                    #[cfg(not(any(feature = "julia-1-9", feature = "julia-1-10")))]
+                    {
+                        let two = Value::new(&mut frame, 2i64);
+                        let old = unsafe {
+                            module.swap_global(
+                                &mut frame,
+                                "counter",
+                                two,
+                                MemoryOrder::SequentiallyConsistent,
+                            )
+                        }
+                        .into_jlrs_result()?;
+                        assert_eq!(old.unbox::<i64>()?, 1);
+                    }
+
+                    Ok(())
+                });
+
+            assert!(res.is_ok());
+        })
+    }
+
+    fn watch_global() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let res = jlrs
+                .instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    use std::sync::{
+                        atomic::{AtomicI64, Ordering},
+                        Arc,
+                    };
+
+                    let main = Module::main(&frame);
+                    let module = Module::new(&mut frame, "WatchedGlobals", main)?;
+
+                    let seen = Arc::new(AtomicI64::new(0));
+                    let seen2 = seen.clone();
+                    let watcher = unsafe {
+                        module.watch_global(&frame, "config", move |v| {
+                            seen2.store(v.unbox::<i64>().unwrap(), Ordering::Relaxed)
+                        })?
+                    };
+
+                    unsafe { module.eval_string(&mut frame, "set_config!(3)") }
+                        .into_jlrs_result()?;
+                    assert_eq!(seen.load(Ordering::Relaxed), 3);
+
+                    watcher.unwatch();
+                    unsafe { module.eval_string(&mut frame, "set_config!(4)") }
+                        .into_jlrs_result()?;
+                    assert_eq!(seen.load(Ordering::Relaxed), 3);
+
+                    // Dropping a watcher unregisters its callback.
+                    let seen3 = seen.clone();
+                    let watcher = unsafe {
+                        module.watch_global(&frame, "config", move |v| {
+                            seen3.store(v.unbox::<i64>().unwrap(), Ordering::Relaxed)
+                        })?
+                    };
+                    std::mem::drop(watcher);
+                    unsafe { module.eval_string(&mut frame, "set_config!(4)") }
+                        .into_jlrs_result()?;
+                    assert_eq!(seen.load(Ordering::Relaxed), 3);
+
+                    let config = module.global(&frame, "config")?;
+                    assert_eq!(unsafe { config.as_value() }.unbox::<i64>()?, 4);
+
+                    // A panicking callback is rethrown as a JlrsError.
+                    let watcher =
+                        unsafe { module.watch_global(&frame, "config", |_| panic!("bad config"))? };
+                    let res = unsafe { module.eval_string(&mut frame, "set_config!(5)") };
+                    assert!(res.is_err());
+                    let exc = res.unwrap_err();
+                    assert_eq!(exc.datatype().name(), "JlrsError");
+                    std::mem::drop(watcher);
+
+                    Ok(())
+                });
+
+            assert!(res.is_ok());
+        })
+    }
+
     #[test]
     fn module_tests() {
         core_module();
@@ -574,5 +936,15 @@ mod tests {
         set_global_unchecked();
         set_const_unchecked();
         function_must_be_function();
+        list_names();
+        list_bindings();
+        list_submodules();
+        sandbox_module();
+        bare_sandbox_module();
+        #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8")))]
+        declare_typed_global();
+        #[cfg(not(any(feature = "julia-1-6", feature = "julia-1-7", feature = "julia-1-8")))]
+        set_global_atomically();
+        watch_global();
     }
 }
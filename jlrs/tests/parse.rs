@@ -0,0 +1,153 @@
+mod util;
+
+#[cfg(feature = "local-rt")]
+mod tests {
+    use jlrs::{data::managed::expr::Expr, error::JlrsError, prelude::*};
+
+    use crate::util::JULIA;
+
+    fn parse_single_expression() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let parsed = Value::parse(&mut frame, "1 + 2")?;
+                    let expr = parsed.cast::<Expr>()?;
+                    assert_eq!(expr.head().unwrap().as_str()?, "call");
+                    assert_eq!(expr.n_args(), 3);
+
+                    let literal = Value::parse(&mut frame, "42")?;
+                    assert_eq!(literal.unbox::<i64>()?, 42);
+
+                    let main = Module::main(&frame);
+                    let res = unsafe { Value::eval_expr(&mut frame, main, parsed) }
+                        .into_jlrs_result()?
+                        .unbox::<i64>()?;
+                    assert_eq!(res, 3);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn parse_all_expressions() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let parsed = Value::parse_all(&mut frame, "a = 1\nb = 2\n", Some("test.jl"))?;
+                    let expr = parsed.cast::<Expr>()?;
+                    assert_eq!(expr.head().unwrap().as_str()?, "toplevel");
+                    assert_eq!(expr.n_args(), 4);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn parse_error_has_location() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let res = Value::parse_all(&mut frame, "a = 1\nb = )\n", None);
+                    match res.map(|_| ()).unwrap_err().as_ref() {
+                        JlrsError::ParseError(e) => {
+                            assert!(!e.get_message().is_empty());
+                            assert_eq!(e.line(), Some(2));
+                        }
+                        e => panic!("unexpected error: {e}"),
+                    }
+
+                    let res = Value::parse(&mut frame, "f(");
+                    assert!(matches!(
+                        res.map(|_| ()).unwrap_err().as_ref(),
+                        JlrsError::ParseError(_)
+                    ));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn check_syntax_and_eval_in() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let mut julia = jlrs.instance(&mut frame);
+
+            assert!(julia.check_syntax("x = 1").is_ok());
+            assert!(julia.check_syntax("x = ").is_err());
+
+            unsafe {
+                julia.eval_in("Main", "module EvalInTarget end").unwrap();
+                julia
+                    .eval_in("Main.EvalInTarget", "evaluated_here = 3")
+                    .unwrap();
+            }
+
+            julia
+                .returning::<JlrsResult<_>>()
+                .scope(|frame| unsafe {
+                    let module = Module::main(&frame)
+                        .submodule(&frame, "EvalInTarget")?
+                        .as_managed();
+                    let value = module.global(&frame, "evaluated_here")?.as_managed();
+                    assert_eq!(value.unbox::<i64>()?, 3);
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn parse_all_and_eval_expr_in() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let mut julia = jlrs.instance(&mut frame);
+
+            let expr = julia.parse_all("parsed_then_evaluated = 4").unwrap();
+            unsafe {
+                julia.eval_in("Main", "module EvalExprTarget end").unwrap();
+                julia.eval_expr_in("Main.EvalExprTarget", &expr).unwrap();
+
+                // The module is replaced, the path must resolve to the new module.
+                julia.eval_in("Main", "module EvalExprTarget end").unwrap();
+                julia.eval_expr_in("Main.EvalExprTarget", &expr).unwrap();
+            }
+
+            julia
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| unsafe {
+                    let module = Module::from_path(&mut frame, "Main.EvalExprTarget")?;
+                    let main = Module::main(&frame)
+                        .submodule(&frame, "EvalExprTarget")?
+                        .as_managed();
+                    assert_eq!(module, main);
+
+                    let value = module.global(&frame, "parsed_then_evaluated")?.as_managed();
+                    assert_eq!(value.unbox::<i64>()?, 4);
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn parse_tests() {
+        parse_single_expression();
+        parse_all_expressions();
+        parse_error_has_location();
+        check_syntax_and_eval_in();
+        parse_all_and_eval_expr_in();
+    }
+}
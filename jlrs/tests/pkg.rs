@@ -0,0 +1,126 @@
+mod util;
+
+#[cfg(feature = "local-rt")]
+mod tests {
+    use std::fs;
+
+    use jlrs::{
+        error::{JlrsError, PkgError},
+        prelude::*,
+        runtime::pkg::{self, Environment, Package},
+    };
+
+    use crate::util::JULIA;
+
+    fn read_depot_path() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let julia = jlrs.instance(&mut frame);
+
+            let depots = julia.depot_path().unwrap();
+            assert!(!depots.is_empty());
+        })
+    }
+
+    fn activate_and_instantiate_offline() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let mut julia = jlrs.instance(&mut frame);
+
+            let dir = std::env::temp_dir().join("jlrs_pkg_test_project");
+            fs::create_dir_all(&dir).unwrap();
+            let project = dir.join("Project.toml");
+            fs::write(&project, "").unwrap();
+
+            let env = Environment::new()
+                .project(&project)
+                .offline(true)
+                .instantiate(true);
+            unsafe { julia.environment(&env).unwrap() };
+
+            let depot = dir.join("depot");
+            fs::create_dir_all(&depot).unwrap();
+            let env = Environment::new().depot(&depot);
+            unsafe { julia.environment(&env).unwrap() };
+            assert_eq!(julia.depot_path().unwrap()[0], depot);
+
+            // The second environment doesn't set offline mode, so it's still enabled.
+            let offline = julia
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| unsafe {
+                    Value::eval_string(
+                        &mut frame,
+                        "Base.loaded_modules[Base.PkgId(Base.UUID(\"44cfe95a-1eb2-52ea-b672-e2afdf69b78f\"), \"Pkg\")].OFFLINE_MODE[]",
+                    )
+                    .into_jlrs_result()?
+                    .unbox::<bool>()
+                })
+                .unwrap();
+            assert!(offline.as_bool());
+        })
+    }
+
+    fn missing_paths_are_reported() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|frame| unsafe {
+                    let missing = std::env::temp_dir().join("jlrs_pkg_test_missing");
+
+                    let res = pkg::activate(&frame, &missing);
+                    assert!(matches!(
+                        res.unwrap_err().as_ref(),
+                        JlrsError::PkgError(PkgError::ProjectNotFound { .. })
+                    ));
+
+                    let res = pkg::add(&frame, &Package::path(&missing));
+                    assert!(matches!(
+                        res.unwrap_err().as_ref(),
+                        JlrsError::PkgError(PkgError::PackageNotFound { .. })
+                    ));
+
+                    let res = pkg::add_depot(&frame, &missing);
+                    assert!(matches!(
+                        res.unwrap_err().as_ref(),
+                        JlrsError::PkgError(PkgError::DepotNotFound { .. })
+                    ));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn unknown_package_fails_offline() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|frame| unsafe {
+                    pkg::set_offline(&frame, true)?;
+                    let package = Package::with_version("ThisPackageDoesNotExist", "1.0.0");
+                    let res = pkg::add(&frame, &package);
+                    assert!(matches!(
+                        res.unwrap_err().as_ref(),
+                        JlrsError::PkgError(PkgError::Failed { .. })
+                    ));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn pkg_tests() {
+        read_depot_path();
+        activate_and_instantiate_offline();
+        missing_paths_are_reported();
+        unknown_package_fails_offline();
+    }
+}
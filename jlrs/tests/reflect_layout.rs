@@ -0,0 +1,179 @@
+#[cfg(all(test, feature = "jlrs-derive", feature = "local-rt"))]
+#[allow(dead_code)]
+mod derive_util;
+
+#[cfg(all(test, feature = "jlrs-derive", feature = "local-rt"))]
+mod tests {
+    use jlrs::{data::layout::reflect::Reflector, prelude::*};
+
+    use super::derive_util::JULIA_DERIVE;
+
+    fn reflect_bits_type() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia.instance(&mut frame).scope(|frame| {
+                let mut reflector = Reflector::new();
+                reflector
+                    .reflect(&frame, "Main.BitsCharFloat32Float64")
+                    .unwrap();
+
+                let layout = reflector.layouts().next().unwrap();
+                assert_eq!(layout.julia_type(), "Main.BitsCharFloat32Float64");
+                assert_eq!(layout.rust_name(), "BitsCharFloat32Float64");
+                assert_eq!(
+                    layout.source(),
+                    "#[repr(C)]\n\
+                     #[derive(Clone, Debug, Unbox, ValidLayout, Typecheck, IntoJulia, ValidField, IsBits, ConstructType, CCallArg, CCallReturn)]\n\
+                     #[jlrs(julia_type = \"Main.BitsCharFloat32Float64\")]\n\
+                     pub struct BitsCharFloat32Float64 {\n    \
+                     pub a: ::jlrs::data::layout::char::Char,\n    \
+                     pub b: f32,\n    \
+                     pub c: f64,\n\
+                     }\n"
+                );
+            });
+        })
+    }
+
+    fn reflect_bits_union() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia.instance(&mut frame).scope(|frame| {
+                let mut reflector = Reflector::new();
+                reflector.reflect(&frame, "Main.DoubleVariant").unwrap();
+
+                let layout = reflector.layouts().next().unwrap();
+                assert_eq!(
+                    layout.source(),
+                    "#[repr(C)]\n\
+                     #[derive(Clone, Debug, Unbox, ValidLayout, Typecheck, ValidField, ConstructType, CCallArg, CCallReturn)]\n\
+                     #[jlrs(julia_type = \"Main.DoubleVariant\")]\n\
+                     pub struct DoubleVariant {\n    \
+                     #[jlrs(bits_union_align)]\n    \
+                     _a_align: ::jlrs::data::layout::union::Align4,\n    \
+                     #[jlrs(bits_union)]\n    \
+                     pub a: ::jlrs::data::layout::union::BitsUnion<4>,\n    \
+                     #[jlrs(bits_union_flag)]\n    \
+                     pub a_flag: u8,\n\
+                     }\n"
+                );
+            });
+        })
+    }
+
+    fn reflect_inline_dependencies() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia.instance(&mut frame).scope(|frame| {
+                let mut reflector = Reflector::new();
+                reflector
+                    .reflect(&frame, "Main.WithPropagatedLifetimes")
+                    .unwrap();
+
+                let names: Vec<_> = reflector.layouts().map(|l| l.rust_name()).collect();
+                assert_eq!(names, ["WithGenericT", "WithPropagatedLifetimes"]);
+
+                let generic = reflector.layouts().next().unwrap();
+                assert!(generic.source().contains("pub struct WithGenericT<T> {\n    pub a: T,\n}"));
+
+                let layout = reflector.layouts().nth(1).unwrap();
+                assert!(layout.source().contains(
+                    "pub struct WithPropagatedLifetimes<'scope, 'data> {\n    \
+                     pub a: WithGenericT<::jlrs::data::layout::tuple::Tuple2<i32, WithGenericT<::std::option::Option<::jlrs::data::managed::array::ArrayRef<'scope, 'data>>>>>,\n\
+                     }"
+                ));
+
+                let code = reflector.to_string();
+                assert!(code.starts_with("use jlrs::prelude::*;\n\n#[repr(C)]"));
+            });
+        })
+    }
+
+    fn reflect_elided_param() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia.instance(&mut frame).scope(|frame| {
+                let mut reflector = Reflector::new();
+                reflector.reflect(&frame, "Main.HasGenericImmut").unwrap();
+
+                let layout = reflector.layouts().next().unwrap();
+                assert_eq!(
+                    layout.source(),
+                    "#[repr(C)]\n\
+                     #[derive(Clone, Debug, Unbox, ValidLayout, Typecheck, ValidField)]\n\
+                     #[jlrs(julia_type = \"Main.HasGenericImmut\")]\n\
+                     pub struct HasGenericImmut<'scope, 'data> {\n    \
+                     pub a: ::std::option::Option<::jlrs::data::managed::value::ValueRef<'scope, 'data>>,\n\
+                     }\n\
+                     \n\
+                     #[derive(ConstructType, HasLayout)]\n\
+                     #[jlrs(julia_type = \"Main.HasGenericImmut\", constructor_for = \"HasGenericImmut\", scope_lifetime = true, data_lifetime = true, layout_params = [], elided_params = [\"T\"], all_params = [\"T\"])]\n\
+                     pub struct HasGenericImmutTypeConstructor<T> {\n    \
+                     _t: ::std::marker::PhantomData<T>,\n\
+                     }\n"
+                );
+            });
+        })
+    }
+
+    fn reflect_invalid_types() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia.instance(&mut frame).scope(|frame| {
+                let mut reflector = Reflector::new();
+                assert!(reflector.reflect(&frame, "Main.DoesNotExist").is_err());
+                assert!(reflector.reflect(&frame, "Core.Int32").is_err());
+                assert!(reflector.reflect(&frame, "Core.Number").is_err());
+                assert_eq!(reflector.layouts().count(), 0);
+            });
+        })
+    }
+
+    fn reflect_name_collision() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia.instance(&mut frame).scope(|frame| {
+                unsafe {
+                    Value::eval_string(
+                        &frame,
+                        "module ReflectA struct Collision a::Int end end
+                         module ReflectB struct Collision b::Int end end",
+                    )
+                    .unwrap();
+                }
+
+                let mut reflector = Reflector::new();
+                reflector
+                    .reflect(&frame, "Main.ReflectA.Collision")
+                    .unwrap();
+                let err = reflector
+                    .reflect(&frame, "Main.ReflectB.Collision")
+                    .unwrap_err();
+                assert!(err.to_string().contains("Main.ReflectA.Collision"));
+                assert_eq!(reflector.layouts().count(), 1);
+            });
+        })
+    }
+
+    #[test]
+    fn reflect_layout_tests() {
+        reflect_bits_type();
+        reflect_bits_union();
+        reflect_inline_dependencies();
+        reflect_elided_param();
+        reflect_invalid_types();
+        reflect_name_collision();
+    }
+}
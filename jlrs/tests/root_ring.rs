@@ -0,0 +1,109 @@
+mod util;
+
+#[cfg(feature = "local-rt")]
+mod tests {
+    use jlrs::{
+        memory::gc::{Gc, GcCollection},
+        prelude::*,
+    };
+
+    use crate::util::JULIA;
+
+    fn root_ring_roots_last_n_values() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let mut ring = frame.root_ring::<4>();
+                    assert_eq!(ring.capacity(), 4);
+
+                    let stack_size = frame.stack_size();
+                    let mut values = Vec::new();
+                    for i in 0..1000usize {
+                        values.push(Value::new(&mut ring, i));
+                    }
+
+                    // The ring doesn't grow the stack.
+                    assert_eq!(frame.stack_size(), stack_size);
+
+                    frame.gc_collect(GcCollection::Full);
+                    for (i, value) in values[996..].iter().enumerate() {
+                        let value = unsafe { value.as_value() };
+                        assert_eq!(value.unbox::<usize>()?, 996 + i);
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn root_ring_call() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let func = Module::base(&frame).global(&mut frame, "+")?;
+                    let mut ring = frame.root_ring::<3>();
+
+                    let mut sum = Value::new(&mut ring, 0usize);
+                    for i in 1..=100usize {
+                        let arg = Value::new(&mut ring, i);
+                        sum = unsafe {
+                            func.call2(&mut ring, sum.as_value(), arg.as_value())
+                                .unwrap()
+                        };
+                    }
+
+                    assert_eq!(unsafe { sum.as_value() }.unbox::<usize>()?, 5050);
+                    ring.clear();
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn local_root_ring() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|frame| {
+                    frame.local_scope::<_, 3>(|mut frame| {
+                        let output = frame.local_output();
+                        let mut ring = frame.local_root_ring::<2>();
+                        assert_eq!(frame.n_roots(), 3);
+
+                        let value = frame.local_scope::<_, 0>(|_| {
+                            let mut last = Value::new(&mut ring, 0u32);
+                            for i in 1..100u32 {
+                                last = Value::new(&mut ring, i);
+                            }
+
+                            unsafe { last.as_value() }.root(output)
+                        });
+
+                        ring.clear();
+                        frame.gc_collect(GcCollection::Full);
+                        assert_eq!(value.unbox::<u32>().unwrap(), 99);
+                    });
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn root_ring_tests() {
+        root_ring_roots_last_n_values();
+        root_ring_call();
+        local_root_ring();
+    }
+}
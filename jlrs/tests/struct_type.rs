@@ -0,0 +1,167 @@
+mod util;
+
+#[cfg(feature = "local-rt")]
+mod tests {
+    use jlrs::{
+        data::{managed::type_var::TypeVar, types::struct_type::StructType},
+        error::{JlrsError, TypeError},
+        prelude::*,
+    };
+
+    use crate::util::JULIA;
+
+    fn define_struct_type() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let main = Module::main(&frame);
+                    let f64_ty = DataType::float64_type(&frame).as_value();
+                    let i64_ty = DataType::int64_type(&frame).as_value();
+
+                    let ty = StructType::new("RuntimeMeasurement")
+                        .field("value", f64_ty)
+                        .field("count", i64_ty)
+                        .define(&mut frame, main)?;
+
+                    assert!(!ty.mutable());
+                    assert!(ty.is_concrete_type());
+                    assert_eq!(ty.n_fields(), Some(2));
+
+                    let value = Value::new(&mut frame, 1.5f64);
+                    let count = Value::new(&mut frame, 3i64);
+                    let measurement = ty
+                        .instantiate(&mut frame, [value, count])?
+                        .into_jlrs_result()?;
+
+                    let count = measurement.get_field(&mut frame, "count")?;
+                    assert_eq!(count.unbox::<i64>()?, 3);
+
+                    let global = main.global(&mut frame, "RuntimeMeasurement")?;
+                    assert_eq!(global, ty.as_value());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn define_mutable_subtype() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let main = Module::main(&frame);
+                    let i32_ty = DataType::int32_type(&frame).as_value();
+                    let super_type = Module::base(&frame)
+                        .global(&mut frame, "Number")?
+                        .cast::<DataType>()?;
+
+                    let ty = StructType::new("RuntimeCounter")
+                        .field("count", i32_ty)
+                        .super_type(super_type)
+                        .mutable(true)
+                        .define(&mut frame, main)?;
+
+                    assert!(ty.mutable());
+                    assert!(ty.as_value().subtype(super_type.as_value()));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn define_parametric_type() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let tvar = TypeVar::new(&mut frame, "T", None, None).into_jlrs_result()?;
+                    let main = Module::main(&frame);
+
+                    let ty = StructType::new("RuntimeWrapper")
+                        .parameter(tvar)
+                        .field("value", tvar.as_value())
+                        .define(&mut frame, main)?;
+
+                    assert!(!ty.is_concrete_type());
+
+                    let f32_ty = DataType::float32_type(&frame).as_value();
+                    let concrete = ty
+                        .type_name()
+                        .wrapper()
+                        .apply_type(&mut frame, [f32_ty])
+                        .into_jlrs_result()?
+                        .cast::<DataType>()?;
+
+                    let value = Value::new(&mut frame, 2.0f32);
+                    let wrapper = concrete
+                        .instantiate(&mut frame, [value])?
+                        .into_jlrs_result()?;
+
+                    let value = wrapper.get_field(&mut frame, "value")?;
+                    assert_eq!(value.unbox::<f32>()?, 2.0);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn invalid_definitions_are_rejected() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .returning::<JlrsResult<_>>()
+                .scope(|mut frame| {
+                    let main = Module::main(&frame);
+                    let i64_ty = DataType::int64_type(&frame);
+
+                    let res = StructType::new("RuntimeDuplicate")
+                        .field("a", i64_ty.as_value())
+                        .field("a", i64_ty.as_value())
+                        .define(&mut frame, main);
+                    assert!(matches!(
+                        res.unwrap_err().as_ref(),
+                        JlrsError::TypeError(TypeError::DuplicateField { .. })
+                    ));
+
+                    let res = StructType::new("RuntimeConcreteSuper")
+                        .super_type(i64_ty)
+                        .define(&mut frame, main);
+                    assert!(matches!(
+                        res.unwrap_err().as_ref(),
+                        JlrsError::TypeError(TypeError::NotAbstract { .. })
+                    ));
+
+                    let one = Value::new(&mut frame, 1usize);
+                    let res = StructType::new("RuntimeInvalidField")
+                        .field("a", one)
+                        .define(&mut frame, main);
+                    assert!(matches!(
+                        res.unwrap_err().as_ref(),
+                        JlrsError::TypeError(TypeError::InvalidFieldType { .. })
+                    ));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn struct_type_tests() {
+        define_struct_type();
+        define_mutable_subtype();
+        define_parametric_type();
+        invalid_definitions_are_rejected();
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(all(test, feature = "jlrs-derive", feature = "local-rt"))]
+#[allow(dead_code)]
+mod derive_util;
+
+#[cfg(all(test, feature = "jlrs-derive", feature = "local-rt"))]
+mod tests {
+    use jlrs::{
+        data::layout::{
+            bool::Bool,
+            verify::{LayoutVerifier, Mismatch},
+        },
+        prelude::*,
+    };
+
+    use super::derive_util::{derive_impls::*, JULIA_DERIVE};
+
+    #[repr(C)]
+    #[derive(Clone, Debug, ValidLayout)]
+    #[jlrs(julia_type = "Main.BitsTypeBool", verify)]
+    struct RegisteredBitsTypeBool {
+        a: Bool,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Debug, ValidLayout)]
+    #[jlrs(julia_type = "Main.BitsCharFloat32Float64", verify)]
+    struct ReorderedFields {
+        a: char,
+        c: f64,
+        b: f32,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Debug, ValidLayout)]
+    #[jlrs(julia_type = "Main.DoesNotExist", verify)]
+    struct MissingType {
+        a: i32,
+    }
+
+    fn verify_matching_layouts() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia.instance(&mut frame).scope(|frame| {
+                let report = LayoutVerifier::new()
+                    .layout::<BitsTypeBool>()
+                    .layout::<BitsCharFloat32Float64>()
+                    .layout::<BitsUInt8TupleInt32Int64>()
+                    .layout::<DoubleVariant>()
+                    .layout::<WithPropagatedLifetimes>()
+                    .verify(&frame);
+
+                assert_eq!(report.layouts().len(), 5);
+                report.assert_ok();
+            });
+        })
+    }
+
+    fn verify_reordered_fields() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia.instance(&mut frame).scope(|frame| {
+                let report = LayoutVerifier::new()
+                    .layout::<BitsTypeBool>()
+                    .layout::<ReorderedFields>()
+                    .verify(&frame);
+
+                assert!(!report.is_ok());
+                assert_eq!(report.failures().count(), 1);
+
+                let failure = report.failures().next().unwrap();
+                assert_eq!(failure.julia_type, "Main.BitsCharFloat32Float64");
+                assert!(failure.mismatches.contains(&Mismatch::FieldIndex {
+                    field: "c",
+                    rust: 1,
+                    julia: 2,
+                }));
+                assert!(failure.mismatches.contains(&Mismatch::FieldOffset {
+                    field: "b",
+                    rust: 16,
+                    julia: 4,
+                }));
+                assert!(!failure
+                    .mismatches
+                    .iter()
+                    .any(|m| matches!(m, Mismatch::FieldType { .. })));
+
+                let msg = report.to_string();
+                assert!(msg.starts_with("1 of 2 layouts"));
+                assert!(msg.contains("- b: offset 4"));
+                assert!(msg.contains("+ b: offset 16"));
+            });
+        })
+    }
+
+    fn verify_missing_type() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia.instance(&mut frame).scope(|frame| {
+                let report = LayoutVerifier::new().layout::<MissingType>().verify(&frame);
+
+                let failure = report.failures().next().unwrap();
+                assert!(matches!(
+                    failure.mismatches[0],
+                    Mismatch::TypeNotFound { .. }
+                ));
+            });
+        })
+    }
+
+    fn verify_registered_layouts() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia.instance(&mut frame).scope(|frame| {
+                let report = LayoutVerifier::new().registered_layouts().verify(&frame);
+
+                let bits_type_bool = report
+                    .layouts()
+                    .iter()
+                    .find(|l| l.rust_type.ends_with("::RegisteredBitsTypeBool"))
+                    .unwrap();
+                assert!(bits_type_bool.is_ok());
+
+                // Layouts without `#[jlrs(verify)]` aren't registered.
+                assert!(!report
+                    .layouts()
+                    .iter()
+                    .any(|l| l.rust_type.ends_with("::BitsTypeBool")));
+
+                let failures = report.failures().map(|l| l.rust_type).collect::<Vec<_>>();
+                assert!(failures.iter().any(|ty| ty.ends_with("::ReorderedFields")));
+                assert!(failures.iter().any(|ty| ty.ends_with("::MissingType")));
+            });
+        })
+    }
+
+    #[test]
+    fn verify_layout_tests() {
+        verify_matching_layouts();
+        verify_reordered_fields();
+        verify_missing_type();
+        verify_registered_layouts();
+    }
+}
@@ -1,7 +1,7 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TS2;
 use quote::{format_ident, quote};
-use syn::{self, punctuated::Punctuated, token::Comma, Token};
+use syn::{self, ext::IdentExt, punctuated::Punctuated, token::Comma, Token};
 
 #[derive(Default)]
 pub struct ClassifiedFields<'a> {
@@ -67,6 +67,7 @@ pub struct JlrsTypeAttrs {
     julia_type: Option<String>,
     constructor_for: Option<String>,
     zst: bool,
+    verify: bool,
     scope_lifetime: bool,
     data_lifetime: bool,
     layout_params: Vec<String>,
@@ -84,6 +85,7 @@ impl JlrsTypeAttrs {
         let mut elided_params = Vec::new();
         let mut all_params = Vec::new();
         let mut zst = false;
+        let mut verify = false;
 
         for attr in &ast.attrs {
             if attr.path().is_ident("jlrs") {
@@ -95,6 +97,9 @@ impl JlrsTypeAttrs {
                         syn::Meta::Path(path) if path.is_ident("zero_sized_type") => {
                             zst = true;
                         }
+                        syn::Meta::Path(path) if path.is_ident("verify") => {
+                            verify = true;
+                        }
                         syn::Meta::NameValue(mnv) if mnv.path.is_ident("julia_type") => {
                             if let syn::Expr::Lit(lit) = mnv.value {
                                 if let syn::Lit::Str(s) = lit.lit {
@@ -180,6 +185,7 @@ impl JlrsTypeAttrs {
         JlrsTypeAttrs {
             julia_type,
             zst,
+            verify,
             constructor_for,
             scope_lifetime,
             data_lifetime,
@@ -194,6 +200,8 @@ enum JlrsFieldAttr {
     BitsUnionAlign,
     BitsUnion,
     BitsUnionFlag,
+    Skip,
+    Expose,
 }
 
 impl JlrsFieldAttr {
@@ -213,6 +221,10 @@ impl JlrsFieldAttr {
                     return Some(JlrsFieldAttr::BitsUnionAlign);
                 } else if path.is_ident("bits_union_flag") {
                     return Some(JlrsFieldAttr::BitsUnionFlag);
+                } else if path.is_ident("skip") {
+                    return Some(JlrsFieldAttr::Skip);
+                } else if path.is_ident("expose") {
+                    return Some(JlrsFieldAttr::Expose);
                 }
             }
         }
@@ -762,6 +774,19 @@ pub fn impl_valid_layout(ast: &syn::DeriveInput) -> TokenStream {
         let n_fields = classified_fields.jl_union_field_idxs.len()
             + classified_fields.jl_non_union_field_idxs.len();
 
+        let described_layout_impl = if generics.type_params().next().is_none() {
+            impl_described_layout(
+                name,
+                generics,
+                fields,
+                &classified_fields,
+                &jl_type,
+                attrs.verify,
+            )
+        } else {
+            TS2::new()
+        };
+
         let valid_layout_impl = quote! {
             unsafe impl #generics ::jlrs::data::layout::valid_layout::ValidLayout for #name #generics #where_clause {
                 fn valid_layout(v: ::jlrs::data::managed::value::Value) -> bool {
@@ -818,6 +843,8 @@ pub fn impl_valid_layout(ast: &syn::DeriveInput) -> TokenStream {
 
                 const IS_REF: bool = false;
             }
+
+            #described_layout_impl
         };
 
         valid_layout_impl.into()
@@ -858,6 +885,92 @@ pub fn impl_valid_layout(ast: &syn::DeriveInput) -> TokenStream {
     }
 }
 
+fn impl_described_layout(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    fields: &syn::Fields,
+    classified_fields: &ClassifiedFields,
+    jl_type: &str,
+    verify: bool,
+) -> TS2 {
+    let mut union_idx = 0;
+    let field_layouts = fields.iter().filter_map(|field| {
+        let ident = field.ident.as_ref()?;
+        let field_name = ident.unraw().to_string();
+        let ty = &field.ty;
+        let rust_type = quote!(#ty).to_string().replace(' ', "");
+
+        let mut is_union = false;
+        for attr in &field.attrs {
+            match JlrsFieldAttr::parse(attr) {
+                Some(JlrsFieldAttr::BitsUnion) => is_union = true,
+                Some(_) => return None,
+                None => (),
+            }
+        }
+
+        if is_union {
+            let align_ty = classified_fields.rs_align_fields[union_idx];
+            let flag_ty = classified_fields.rs_flag_fields[union_idx];
+            union_idx += 1;
+
+            Some(quote! {
+                ::jlrs::data::layout::verify::FieldLayout {
+                    name: #field_name,
+                    rust_type: #rust_type,
+                    offset: ::std::mem::offset_of!(Self, #ident),
+                    size: None,
+                    valid_field: |ty: ::jlrs::data::managed::value::Value| unsafe {
+                        if ty.is::<::jlrs::data::managed::union::Union>() {
+                            let u = ty.cast_unchecked::<::jlrs::data::managed::union::Union>();
+                            ::jlrs::data::layout::union::correct_layout_for::<#align_ty, #ty, #flag_ty>(u)
+                        } else {
+                            false
+                        }
+                    },
+                }
+            })
+        } else {
+            Some(quote! {
+                ::jlrs::data::layout::verify::FieldLayout {
+                    name: #field_name,
+                    rust_type: #rust_type,
+                    offset: ::std::mem::offset_of!(Self, #ident),
+                    size: Some(::std::mem::size_of::<#ty>()),
+                    valid_field: <#ty as ::jlrs::data::layout::valid_layout::ValidField>::valid_field,
+                }
+            })
+        }
+    });
+
+    // Layouts are only registered if they're annotated with `#[jlrs(verify)]`. Layouts with const
+    // parameters can't be registered, every lifetime is replaced with `'static`.
+    let register = if verify && generics.const_params().next().is_none() {
+        let lifetimes = generics.lifetimes().map(|_| quote!('static));
+        quote! {
+            ::jlrs::data::layout::verify::inventory::submit! {
+                ::jlrs::data::layout::verify::RegisteredLayout::new::<#name<#(#lifetimes),*>>()
+            }
+        }
+    } else {
+        TS2::new()
+    };
+
+    let where_clause = &generics.where_clause;
+
+    quote! {
+        unsafe impl #generics ::jlrs::data::layout::verify::DescribedLayout for #name #generics #where_clause {
+            const JULIA_TYPE: &'static str = #jl_type;
+
+            fn field_layouts() -> ::std::vec::Vec<::jlrs::data::layout::verify::FieldLayout> {
+                ::std::vec![#(#field_layouts),*]
+            }
+        }
+
+        #register
+    }
+}
+
 pub fn impl_enum(ast: &syn::DeriveInput) -> TokenStream {
     let name = &ast.ident;
     let repr = get_repr_int(ast).expect("Enum can only be derived for enums with an integer repr.");
@@ -1083,6 +1196,151 @@ pub fn impl_ccall_return(ast: &syn::DeriveInput) -> TokenStream {
     ccall_arg_impl.into()
 }
 
+pub fn impl_foreign_type(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let marked = marked_fields(ast, "ForeignType");
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let foreign_type_impl = quote! {
+        unsafe impl #impl_generics ::jlrs::data::types::foreign_type::ForeignType for #name #ty_generics #where_clause {
+            fn mark(ptls: ::jlrs::memory::PTls, data: &Self) -> usize {
+                0 #(
+                    + unsafe { ::jlrs::data::types::foreign_type::Mark::mark(&data.#marked, ptls) }
+                )*
+            }
+        }
+    };
+
+    foreign_type_impl.into()
+}
+
+pub fn impl_mark(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let marked = marked_fields(ast, "Mark");
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let mark_impl = quote! {
+        unsafe impl #impl_generics ::jlrs::data::types::foreign_type::Mark for #name #ty_generics #where_clause {
+            unsafe fn mark(&self, ptls: ::jlrs::memory::PTls) -> usize {
+                0 #(+ ::jlrs::data::types::foreign_type::Mark::mark(&self.#marked, ptls))*
+            }
+        }
+    };
+
+    mark_impl.into()
+}
+
+pub fn impl_exposed_fields(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let syn::Data::Struct(ref data) = ast.data else {
+        panic!("ExposedFields can only be derived for structs.");
+    };
+
+    let syn::Fields::Named(ref fields) = data.fields else {
+        panic!("ExposedFields can only be derived for structs with named fields.");
+    };
+
+    let exposed = fields
+        .named
+        .iter()
+        .filter(|field| {
+            field
+                .attrs
+                .iter()
+                .any(|attr| matches!(JlrsFieldAttr::parse(attr), Some(JlrsFieldAttr::Expose)))
+        })
+        .collect::<Vec<_>>();
+
+    let idents = exposed
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let names = idents.iter().map(|ident| ident.to_string());
+    let tys = exposed.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let indices = (0..idents.len()).collect::<Vec<_>>();
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let exposed_fields_impl = quote! {
+        impl #impl_generics ::jlrs::data::types::exposed_fields::ExposedFields for #name #ty_generics #where_clause {
+            const FIELD_NAMES: &'static [&'static str] = &[#(#names),*];
+
+            fn get_field<'target, Tgt>(
+                &self,
+                target: Tgt,
+                idx: usize,
+            ) -> ::jlrs::data::managed::value::ValueData<'target, 'static, Tgt>
+            where
+                Tgt: ::jlrs::memory::target::Target<'target>,
+            {
+                match idx {
+                    #(
+                        #indices => ::jlrs::data::managed::value::Value::new(
+                            target,
+                            ::std::clone::Clone::clone(&self.#idents),
+                        ),
+                    )*
+                    _ => panic!("field index out of bounds"),
+                }
+            }
+
+            fn set_field(
+                &mut self,
+                idx: usize,
+                value: ::jlrs::data::managed::value::Value,
+            ) -> ::jlrs::error::JlrsResult<()> {
+                match idx {
+                    #(
+                        #indices => self.#idents = value.unbox::<#tys>()?,
+                    )*
+                    _ => panic!("field index out of bounds"),
+                }
+
+                Ok(())
+            }
+
+            fn field_type<'target, Tgt>(
+                target: Tgt,
+                idx: usize,
+            ) -> ::jlrs::data::managed::datatype::DataTypeData<'target, Tgt>
+            where
+                Tgt: ::jlrs::memory::target::Target<'target>,
+            {
+                match idx {
+                    #(
+                        #indices => <#tys as ::jlrs::convert::into_julia::IntoJulia>::julia_type(target),
+                    )*
+                    _ => panic!("field index out of bounds"),
+                }
+            }
+        }
+    };
+
+    exposed_fields_impl.into()
+}
+
+// Returns the members of all fields that must be marked, i.e. all fields that aren't annotated
+// with `#[jlrs(skip)]`.
+fn marked_fields(ast: &syn::DeriveInput, derived: &str) -> Vec<syn::Member> {
+    let syn::Data::Struct(ref data) = ast.data else {
+        panic!("{} can only be derived for structs.", derived);
+    };
+
+    data.fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| {
+            !field
+                .attrs
+                .iter()
+                .any(|attr| matches!(JlrsFieldAttr::parse(attr), Some(JlrsFieldAttr::Skip)))
+        })
+        .map(|(idx, field)| match field.ident.as_ref() {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(idx.into()),
+        })
+        .collect()
+}
+
 fn is_repr_c(ast: &syn::DeriveInput) -> bool {
     for attr in &ast.attrs {
         if attr.path().is_ident("repr") {
@@ -57,6 +57,49 @@ use self::{constant_bytes::*, version::emit_if_compatible};
 ///     // or `ForeignType`.
 ///     struct MyType as MyForeignType;
 ///
+///     // Exports `Point` and adds methods to `Base.show`, `Base.:(==)`, `Base.hash`, `Base.copy`
+///     // and `Base.deepcopy_internal` that call the implementations of `Display`, `PartialEq`,
+///     // `Hash` and `Clone` respectively. Use `#[show(Debug)]` to show it with its
+///     // implementation of `Debug` instead.
+///     #[show]
+///     #[eq]
+///     #[hash]
+///     #[copy]
+///     struct Point;
+///
+///     // Exports `Counter` and adds methods to `Serialization.serialize` and
+///     // `Serialization.deserialize`. `Counter` must implement `SerializableOpaque`.
+///     #[serialize]
+///     struct Counter;
+///
+///     // Exports `Connection` and adds methods to `Base.close` and `Base.isopen`. Closing an
+///     // instance drops its Rust data immediately, calling an exported method with a closed
+///     // instance as `self` throws an exception.
+///     #[close]
+///     struct Connection;
+///
+///     // Exports `Particle` and adds methods to `Base.getproperty`, `Base.setproperty!` and
+///     // `Base.propertynames` that access the fields annotated with `#[jlrs(expose)]`. `Particle`
+///     // must implement `ExposedFields`, which can be derived.
+///     #[properties]
+///     struct Particle;
+///
+///     // Exports `Squares`, an alias of `RustIterator<u64>`. Methods for `Base.iterate`,
+///     // `Base.IteratorSize` and `Base.eltype` are added automatically.
+///     struct Squares;
+///
+///     // Exports the trait `Shape` as an abstract type. Traits must be exported outside `for`
+///     // blocks.
+///     trait Shape;
+///
+///     // Exports the struct `Circle`, which implements `Shape`, as a subtype of `Shape`.
+///     struct Circle: Shape;
+///
+///     // Exports the trait method `Shape::area` as a single function `area(::Shape)` that
+///     // dispatches to the implementation of `Shape` for the type of its argument. Only methods
+///     // that take `&self` can be exported this way.
+///     in dyn Shape fn area(&self) -> f64;
+///
 ///     // Exports `MyType::new` as `MyForeignType`, turning it into a constructor for that type.
 ///     in MyType fn new(arg0: TypedValue<u32>) -> TypedValueRet<MyType> as MyForeignType;
 ///
@@ -163,7 +206,8 @@ pub fn julia_version(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Derive `IntoJulia`.
 ///
-/// Should only be used in combination with layouts generated by JlrsReflect.jl
+/// Should only be used in combination with layouts generated by JlrsReflect.jl or
+/// `jlrs::data::layout::reflect`.
 #[cfg(feature = "derive")]
 #[proc_macro_derive(IntoJulia, attributes(jlrs))]
 pub fn into_julia_derive(input: TokenStream) -> TokenStream {
@@ -173,7 +217,8 @@ pub fn into_julia_derive(input: TokenStream) -> TokenStream {
 
 /// Derive `IsBits`.
 ///
-/// Should only be used in combination with layouts generated by JlrsReflect.jl
+/// Should only be used in combination with layouts generated by JlrsReflect.jl or
+/// `jlrs::data::layout::reflect`.
 #[cfg(feature = "derive")]
 #[proc_macro_derive(IsBits, attributes(jlrs))]
 pub fn is_bits_derive(input: TokenStream) -> TokenStream {
@@ -183,7 +228,8 @@ pub fn is_bits_derive(input: TokenStream) -> TokenStream {
 
 /// Derive `HasLayout`.
 ///
-/// Should only be used in combination with layouts generated by JlrsReflect.jl
+/// Should only be used in combination with layouts generated by JlrsReflect.jl or
+/// `jlrs::data::layout::reflect`.
 #[cfg(feature = "derive")]
 #[proc_macro_derive(HasLayout, attributes(jlrs))]
 pub fn is_has_layout(input: TokenStream) -> TokenStream {
@@ -193,7 +239,8 @@ pub fn is_has_layout(input: TokenStream) -> TokenStream {
 
 /// Derive `Unbox`.
 ///
-/// Should only be used in combination with layouts generated by JlrsReflect.jl
+/// Should only be used in combination with layouts generated by JlrsReflect.jl or
+/// `jlrs::data::layout::reflect`.
 #[cfg(feature = "derive")]
 #[proc_macro_derive(Unbox, attributes(jlrs))]
 pub fn unbox_derive(input: TokenStream) -> TokenStream {
@@ -203,7 +250,8 @@ pub fn unbox_derive(input: TokenStream) -> TokenStream {
 
 /// Derive `Typecheck`.
 ///
-/// Should only be used in combination with layouts generated by JlrsReflect.jl
+/// Should only be used in combination with layouts generated by JlrsReflect.jl or
+/// `jlrs::data::layout::reflect`.
 #[cfg(feature = "derive")]
 #[proc_macro_derive(Typecheck, attributes(jlrs))]
 pub fn typecheck_derive(input: TokenStream) -> TokenStream {
@@ -213,7 +261,11 @@ pub fn typecheck_derive(input: TokenStream) -> TokenStream {
 
 /// Derive `ValidLayout`.
 ///
-/// Should only be used in combination with layouts generated by JlrsReflect.jl
+/// Should only be used in combination with layouts generated by JlrsReflect.jl or
+/// `jlrs::data::layout::reflect`. For structs without type parameters `DescribedLayout` is
+/// derived too, this lets the layout be checked with a `LayoutVerifier`. Layouts annotated with
+/// `#[jlrs(verify)]` are also registered, `LayoutVerifier::registered_layouts` adds all
+/// registered layouts.
 #[cfg(feature = "derive")]
 #[proc_macro_derive(ValidLayout, attributes(jlrs))]
 pub fn valid_layout_derive(input: TokenStream) -> TokenStream {
@@ -223,7 +275,8 @@ pub fn valid_layout_derive(input: TokenStream) -> TokenStream {
 
 /// Derive `ValidField`.
 ///
-/// Should only be used in combination with layouts generated by JlrsReflect.jl
+/// Should only be used in combination with layouts generated by JlrsReflect.jl or
+/// `jlrs::data::layout::reflect`.
 #[cfg(feature = "derive")]
 #[proc_macro_derive(ValidField, attributes(jlrs))]
 pub fn valid_field_derive(input: TokenStream) -> TokenStream {
@@ -233,7 +286,8 @@ pub fn valid_field_derive(input: TokenStream) -> TokenStream {
 
 /// Derive `ConstructType`.
 ///
-/// Should only be used in combination with layouts generated by JlrsReflect.jl
+/// Should only be used in combination with layouts generated by JlrsReflect.jl or
+/// `jlrs::data::layout::reflect`.
 #[cfg(feature = "derive")]
 #[proc_macro_derive(ConstructType, attributes(jlrs))]
 pub fn construct_type_derive(input: TokenStream) -> TokenStream {
@@ -243,7 +297,8 @@ pub fn construct_type_derive(input: TokenStream) -> TokenStream {
 
 /// Derive `CCallArg`.
 ///
-/// Should only be used in combination with layouts generated by JlrsReflect.jl
+/// Should only be used in combination with layouts generated by JlrsReflect.jl or
+/// `jlrs::data::layout::reflect`.
 #[cfg(feature = "derive")]
 #[proc_macro_derive(CCallArg, attributes(jlrs))]
 pub fn ccall_arg_derive(input: TokenStream) -> TokenStream {
@@ -253,7 +308,8 @@ pub fn ccall_arg_derive(input: TokenStream) -> TokenStream {
 
 /// Derive `CCallReturn`.
 ///
-/// Should only be used in combination with layouts generated by JlrsReflect.jl
+/// Should only be used in combination with layouts generated by JlrsReflect.jl or
+/// `jlrs::data::layout::reflect`.
 #[cfg(feature = "derive")]
 #[proc_macro_derive(CCallReturn, attributes(jlrs))]
 pub fn ccall_return_derive(input: TokenStream) -> TokenStream {
@@ -263,10 +319,46 @@ pub fn ccall_return_derive(input: TokenStream) -> TokenStream {
 
 /// Derive `Enum`.
 ///
-/// Should only be used in combination with layouts generated by JlrsReflect.jl
+/// Should only be used in combination with layouts generated by JlrsReflect.jl or
+/// `jlrs::data::layout::reflect`.
 #[cfg(feature = "derive")]
 #[proc_macro_derive(Enum, attributes(jlrs))]
 pub fn enum_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
     impl_enum(&ast)
 }
+
+/// Derive `ForeignType`.
+///
+/// The generated `mark` method marks all fields, so the type of every field must implement
+/// `Mark`. Fields annotated with `#[jlrs(skip)]` are never marked. Like manual implementations
+/// of `ForeignType`, the type must implement `Send`.
+#[cfg(feature = "derive")]
+#[proc_macro_derive(ForeignType, attributes(jlrs))]
+pub fn foreign_type_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_foreign_type(&ast)
+}
+
+/// Derive `Mark`.
+///
+/// Fields are marked the same way as they are by the `mark` method generated by
+/// `#[derive(ForeignType)]`.
+#[cfg(feature = "derive")]
+#[proc_macro_derive(Mark, attributes(jlrs))]
+pub fn mark_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_mark(&ast)
+}
+
+/// Derive `ExposedFields`.
+///
+/// Only fields annotated with `#[jlrs(expose)]` are exposed. The type of an exposed field must
+/// implement `IntoJulia`, `Clone`, `Typecheck` and `Unbox` with itself as `Output`. Only structs
+/// with named fields are supported.
+#[cfg(feature = "derive")]
+#[proc_macro_derive(ExposedFields, attributes(jlrs))]
+pub fn exposed_fields_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_exposed_fields(&ast)
+}
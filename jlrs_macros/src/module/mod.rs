@@ -42,6 +42,8 @@ impl Parse for InitFn {
 struct ExportedType {
     _struct_token: Token![struct],
     name: Path,
+    _colon_token: Option<Token![:]>,
+    super_trait: Option<Path>,
     _as_token: Option<Token![as]>,
     name_override: Option<RenameFragments>,
 }
@@ -64,6 +66,15 @@ impl ExportedType {
             .unwrap_or(name_ident)
             .to_string();
 
+        if let Some(super_trait) = self.super_trait.as_ref() {
+            let err = Error::new_spanned(
+                super_trait,
+                "generic types can't have a trait as their supertype",
+            )
+            .to_compile_error();
+            return parse_quote! { { #err } };
+        }
+
         let env = ParameterEnvironment::new(generic, env);
         let mut list = ParameterList::new(&env);
         let mut resolver = list.resolver();
@@ -171,6 +182,12 @@ impl Parse for ExportedType {
         let struct_token = input.parse()?;
         let name = input.parse()?;
 
+        let (colon_token, super_trait) = if input.peek(Token![:]) {
+            (Some(input.parse()?), Some(input.parse()?))
+        } else {
+            (None, None)
+        };
+
         let lookahead = input.lookahead1();
         if lookahead.peek(Token![as]) {
             let as_token = input.parse()?;
@@ -179,6 +196,8 @@ impl Parse for ExportedType {
             Ok(ExportedType {
                 _struct_token: struct_token,
                 name,
+                _colon_token: colon_token,
+                super_trait,
                 _as_token: Some(as_token),
                 name_override: Some(name_override),
             })
@@ -186,6 +205,42 @@ impl Parse for ExportedType {
             Ok(ExportedType {
                 _struct_token: struct_token,
                 name,
+                _colon_token: colon_token,
+                super_trait,
+                _as_token: None,
+                name_override: None,
+            })
+        }
+    }
+}
+
+struct ExportedTrait {
+    _trait_token: Token![trait],
+    name: Path,
+    _as_token: Option<Token![as]>,
+    name_override: Option<RenameFragments>,
+}
+
+impl Parse for ExportedTrait {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let trait_token = input.parse()?;
+        let name = input.parse()?;
+
+        let lookahead = input.lookahead1();
+        if lookahead.peek(Token![as]) {
+            let as_token = input.parse()?;
+            let name_override = RenameFragments::parse_separated_nonempty(input)?;
+
+            Ok(ExportedTrait {
+                _trait_token: trait_token,
+                name,
+                _as_token: Some(as_token),
+                name_override: Some(name_override),
+            })
+        } else {
+            Ok(ExportedTrait {
+                _trait_token: trait_token,
+                name,
                 _as_token: None,
                 name_override: None,
             })
@@ -487,6 +542,13 @@ impl ExportedMethod {
         untracked_self: bool,
         gc_safe: bool,
     ) -> Result<Expr> {
+        if let Type::TraitObject(_) = self.parent {
+            Err(Error::new_spanned(
+                &self.parent,
+                "methods of traits must be exported outside a `for` block",
+            ))?;
+        }
+
         let n_args = self.func.inputs.len();
         let name_ident = &self.func.ident;
         let start = *offset;
@@ -844,6 +906,16 @@ impl<'a> GenericEnvironment<'a> {
             panic!("Globals and constants must be defined outside a `for` block.")
         }
 
+        let n_traits = generics
+            .items
+            .iter()
+            .filter(|f| f.is_exported_trait())
+            .count();
+
+        if n_traits != 0 {
+            panic!("Traits must be defined outside a `for` block.")
+        }
+
         let items: Vec<_> = generics
             .items
             .iter()
@@ -1028,6 +1100,7 @@ impl Parse for ExportedGenerics {
 enum ModuleItem {
     InitFn(InitFn),
     ExportedType(ExportedType),
+    ExportedTrait(ExportedTrait),
     ExportedFunction(ExportedFunction),
     ExportedMethod(ExportedMethod),
     ExportedConst(ExportedConst),
@@ -1112,6 +1185,50 @@ impl ModuleItem {
         }
     }
 
+    fn get_exported_type_with_attrs(&self) -> (&ExportedType, Option<&[Attribute]>) {
+        match self {
+            ModuleItem::ExportedType(ref exported_type) => (exported_type, None),
+            ModuleItem::ItemWithAttrs(ItemWithAttrs { item, ref attrs })
+                if item.is_exported_type() =>
+            {
+                (item.get_exported_type(), Some(attrs.as_ref()))
+            }
+            _ => panic!(),
+        }
+    }
+
+    fn is_exported_trait(&self) -> bool {
+        match self {
+            ModuleItem::ExportedTrait(_) => true,
+            ModuleItem::ItemWithAttrs(ItemWithAttrs { item, .. }) if item.is_exported_trait() => {
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn get_exported_trait(&self) -> &ExportedTrait {
+        match self {
+            ModuleItem::ExportedTrait(ref exported_trait) => exported_trait,
+            ModuleItem::ItemWithAttrs(ItemWithAttrs { item, .. }) if item.is_exported_trait() => {
+                item.get_exported_trait()
+            }
+            _ => panic!(),
+        }
+    }
+
+    fn get_exported_trait_with_attrs(&self) -> (&ExportedTrait, Option<&[Attribute]>) {
+        match self {
+            ModuleItem::ExportedTrait(ref exported_trait) => (exported_trait, None),
+            ModuleItem::ItemWithAttrs(ItemWithAttrs { item, ref attrs })
+                if item.is_exported_trait() =>
+            {
+                (item.get_exported_trait(), Some(attrs.as_ref()))
+            }
+            _ => panic!(),
+        }
+    }
+
     fn is_exported_const(&self) -> bool {
         match self {
             ModuleItem::ExportedConst(_) => true,
@@ -1239,6 +1356,8 @@ impl Parse for ModuleItem {
             input.parse().map(ModuleItem::InitFn)
         } else if lookahead.peek(Token![struct]) {
             input.parse().map(ModuleItem::ExportedType)
+        } else if lookahead.peek(Token![trait]) {
+            input.parse().map(ModuleItem::ExportedTrait)
         } else if lookahead.peek(Token![fn]) {
             input.parse().map(ModuleItem::ExportedFunction)
         } else if lookahead.peek(Token![in]) {
@@ -1256,7 +1375,7 @@ impl Parse for ModuleItem {
         } else {
             Err(Error::new(
                 input.span(),
-                "Expected `become`, `fn`, `in`, `struct`, `const`, or `static`.",
+                "Expected `become`, `fn`, `in`, `struct`, `trait`, `const`, or `static`.",
             ))
         }
     }
@@ -1285,6 +1404,7 @@ impl JuliaModule {
         let method_fragments = MethodFragments::generate(&self, init_fn);
         let generic_method_fragments = MethodFragments::generate_generic(&self, init_fn)?;
         let type_fragments = TypeFragments::generate(&self, init_fn);
+        let type_method_fragments = TypeMethodFragments::generate(&self, init_fn)?;
         let generic_type_fragments = TypeFragments::generate_generic(&self, init_fn);
         let const_fragments = ConstFragments::generate(&self, init_fn);
         let alias_fragments = AliasFragments::generate(&self, init_fn);
@@ -1303,6 +1423,8 @@ impl JuliaModule {
         let function_init_fn_ident = fn_fragments.init_functions_fn_ident;
         let generic_function_init_fn = generic_fn_fragments.init_functions_fn;
         let generic_function_init_fn_ident = generic_fn_fragments.init_functions_fn_ident;
+        let type_method_init_fn = type_method_fragments.init_type_methods_fn;
+        let type_method_init_fn_ident = type_method_fragments.init_type_methods_fn_ident;
         let method_init_fn = method_fragments.init_methods_fn;
         let method_init_fn_ident = method_fragments.init_methods_fn_ident;
         let generic_method_init_fn = generic_method_fragments.init_methods_fn;
@@ -1373,6 +1495,8 @@ impl JuliaModule {
 
                 #generic_method_init_fn
 
+                #type_method_init_fn
+
                 #const_init_fn
 
                 #alias_init_fn
@@ -1427,6 +1551,7 @@ impl JuliaModule {
                     #generic_function_init_fn_ident(&mut frame, &mut arr, module, function_info_ty);
                     #method_init_fn_ident(&mut frame, &mut arr, module, function_info_ty);
                     #generic_method_init_fn_ident(&mut frame, &mut arr, module, function_info_ty);
+                    #type_method_init_fn_ident(&mut frame, &mut arr, function_info_ty);
 
                     let mut doc_items = ::jlrs::data::managed::array::Vector::new_for_unchecked(&mut frame, doc_item_ty.as_value(), 0);
                     if precompiling == 1 {
@@ -1482,6 +1607,31 @@ impl JuliaModule {
             .map(|it| it.get_exported_type())
     }
 
+    fn get_exported_types_with_attrs(
+        &self,
+    ) -> impl Iterator<Item = (&ExportedType, Option<&[Attribute]>)> {
+        self.items
+            .iter()
+            .filter(|it| it.is_exported_type())
+            .map(|it| it.get_exported_type_with_attrs())
+    }
+
+    fn get_exported_traits(&self) -> impl Iterator<Item = &ExportedTrait> {
+        self.items
+            .iter()
+            .filter(|it| it.is_exported_trait())
+            .map(|it| it.get_exported_trait())
+    }
+
+    fn get_exported_traits_with_attrs(
+        &self,
+    ) -> impl Iterator<Item = (&ExportedTrait, Option<&[Attribute]>)> {
+        self.items
+            .iter()
+            .filter(|it| it.is_exported_trait())
+            .map(|it| it.get_exported_trait_with_attrs())
+    }
+
     fn get_exported_consts(&self) -> impl Iterator<Item = &ExportedConst> {
         self.items
             .iter()
@@ -1730,10 +1880,49 @@ struct TypeFragments {
     type_reinit_ident: Ident,
 }
 
+struct TypeMethodFragments {
+    init_type_methods_fn_ident: Ident,
+    init_type_methods_fn: ItemFn,
+}
+
+impl TypeMethodFragments {
+    fn generate(info: &JuliaModule, init_fn: &InitFn) -> Result<Self> {
+        let init_type_methods_fn_ident = format_ident!("{}_type_methods", init_fn.init_fn);
+        for (_, attrs) in info.get_exported_traits_with_attrs() {
+            check_trait_attrs(attrs)?;
+        }
+
+        let type_methods_fragments = info
+            .get_exported_types_with_attrs()
+            .map(|(ty, attrs)| type_methods_fragment(ty, attrs))
+            .collect::<Result<Vec<_>>>()?;
+
+        let init_type_methods_fn = parse_quote! {
+            unsafe fn #init_type_methods_fn_ident(
+                frame: &mut ::jlrs::memory::target::frame::GcFrame,
+                array: &mut ::jlrs::data::managed::array::Vector<'_, 'static>,
+                function_info_ty: ::jlrs::data::managed::datatype::DataType,
+            ) {
+                #(
+                    #type_methods_fragments;
+                )*
+            }
+        };
+
+        Ok(TypeMethodFragments {
+            init_type_methods_fn_ident,
+            init_type_methods_fn,
+        })
+    }
+}
+
 impl TypeFragments {
     fn generate(info: &JuliaModule, init_fn: &InitFn) -> Self {
         let init_types_fn_ident = format_ident!("{}_types", init_fn.init_fn);
-        let init_types_fragments = info.get_exported_types().map(init_type_fragment);
+        let init_types_fragments = info
+            .get_exported_traits()
+            .map(init_trait_fragment)
+            .chain(info.get_exported_types().map(init_type_fragment));
 
         let type_init_fn = parse_quote! {
             unsafe fn #init_types_fn_ident(
@@ -1751,7 +1940,10 @@ impl TypeFragments {
         };
 
         let reinit_types_fn_ident = format_ident!("{}_reinittypes", init_fn.init_fn);
-        let reinit_types_fragments = info.get_exported_types().map(reinit_type_fragment);
+        let reinit_types_fragments = info
+            .get_exported_traits()
+            .map(reinit_trait_fragment)
+            .chain(info.get_exported_types().map(reinit_type_fragment));
 
         let type_reinit_fn = parse_quote! {
             unsafe fn #reinit_types_fn_ident(
@@ -1959,6 +2151,37 @@ fn doc_info_fragment((index, info): (usize, &ItemWithAttrs)) -> Result<Expr> {
 
             Ok(q)
         }
+        ModuleItem::ExportedTrait(ty) => {
+            let override_module_fragment = override_module_fragment(&ty.name_override);
+            let name_ident = &ty.name.segments.last().unwrap().ident;
+
+            let rename = ty
+                .name_override
+                .as_ref()
+                .and_then(|parts| parts.last())
+                .unwrap_or(name_ident)
+                .to_string();
+
+            let doc = info.get_docstr()?;
+
+            let q = parse_quote! {
+                {
+                    frame.scope(|mut frame| {
+                        unsafe {
+                            let module = #override_module_fragment;
+                            let item = ::jlrs::data::managed::symbol::Symbol::new(&frame, #rename);
+                            let signature = ::jlrs::data::managed::value::Value::bottom_type(&frame);
+                            let doc = ::jlrs::data::managed::string::JuliaString::new(&mut frame, #doc);
+
+                            let doc_it = doc_item_ty.instantiate_unchecked(&mut frame, [module.as_value(), item.as_value(), signature, doc.as_value()]);
+                            accessor.set_value(&mut frame, #index, doc_it).unwrap().into_jlrs_result().unwrap();
+                        }
+                    });
+                }
+            };
+
+            Ok(q)
+        }
         ModuleItem::ExportedFunction(func) => {
             let name_ident = &func.func.ident;
 
@@ -2356,16 +2579,192 @@ fn init_type_fragment(info: &ExportedType) -> Expr {
 
     let ty = format_ident!("{}", name_ident);
 
+    let create_type: Expr = match info.super_trait.as_ref() {
+        Some(super_trait) => {
+            let err = format!("{} has not been exported", super_trait.to_token_stream());
+            parse_quote! {
+                {
+                    let super_type = ::jlrs::data::types::foreign_type::trait_type::<dyn #super_trait, _>(&frame).expect(#err);
+                    <#ty as ::jlrs::data::types::foreign_type::OpaqueType>::create_subtype(&mut output, sym, module, super_type)
+                }
+            }
+        }
+        None => parse_quote! {
+            <#ty as ::jlrs::data::types::foreign_type::OpaqueType>::create_type(&mut output, sym, module)
+        },
+    };
+
+    let register_trait_impl = register_trait_impl_fragment(info);
+
     parse_quote! {
         {
             let sym = ::jlrs::data::managed::symbol::Symbol::new(&frame, #rename);
             let module = #override_module_fragment;
-            let ty = <#ty as ::jlrs::data::types::foreign_type::OpaqueType>::create_type(&mut output, sym, module);
+            let ty = #create_type;
+            #register_trait_impl;
             module.set_const_unchecked(sym, <::jlrs::data::managed::datatype::DataType as ::jlrs::data::managed::Managed>::as_value(ty));
         }
     }
 }
 
+fn register_trait_impl_fragment(info: &ExportedType) -> Expr {
+    let Some(super_trait) = info.super_trait.as_ref() else {
+        return parse_quote! { () };
+    };
+
+    let name_ident = &info.name.segments.last().unwrap().ident;
+    let ty = format_ident!("{}", name_ident);
+
+    parse_quote! {
+        {
+            unsafe fn cast(ptr: *mut ::std::ffi::c_void) -> *mut dyn #super_trait {
+                ptr.cast::<#ty>() as *mut dyn #super_trait
+            }
+
+            ::jlrs::data::types::foreign_type::register_trait_impl::<dyn #super_trait>(ty, cast)
+        }
+    }
+}
+
+fn init_trait_fragment(info: &ExportedTrait) -> Expr {
+    let override_module_fragment = override_module_fragment(&info.name_override);
+    let name = &info.name;
+    let name_ident = &name.segments.last().unwrap().ident;
+
+    let rename = info
+        .name_override
+        .as_ref()
+        .and_then(|parts| parts.last())
+        .unwrap_or(name_ident)
+        .to_string();
+
+    parse_quote! {
+        {
+            let sym = ::jlrs::data::managed::symbol::Symbol::new(&frame, #rename);
+            let module = #override_module_fragment;
+            let ty = ::jlrs::data::types::foreign_type::create_trait_type::<dyn #name, _>(&mut output, sym, module);
+            module.set_const_unchecked(sym, <::jlrs::data::managed::datatype::DataType as ::jlrs::data::managed::Managed>::as_value(ty));
+        }
+    }
+}
+
+fn reinit_trait_fragment(info: &ExportedTrait) -> Expr {
+    let override_module_fragment = override_module_fragment(&info.name_override);
+    let name = &info.name;
+    let name_ident = &name.segments.last().unwrap().ident;
+
+    let rename = info
+        .name_override
+        .as_ref()
+        .and_then(|parts| parts.last())
+        .unwrap_or(name_ident)
+        .to_string();
+
+    parse_quote! {
+        {
+            let module = #override_module_fragment;
+
+            let dt = module
+                .global(&frame, #rename)
+                .unwrap()
+                .as_value()
+                .cast::<::jlrs::data::managed::datatype::DataType>()
+                .unwrap();
+
+            ::jlrs::data::types::foreign_type::reinit_trait_type::<dyn #name>(dt);
+        }
+    }
+}
+
+fn type_methods_fragment(info: &ExportedType, attrs: Option<&[Attribute]>) -> Result<Expr> {
+    let name_ident = &info.name.segments.last().unwrap().ident;
+    let ty = format_ident!("{}", name_ident);
+
+    let mut add_methods: Vec<Path> = vec![];
+    for attr in attrs.unwrap_or_default() {
+        match attr.style {
+            AttrStyle::Outer => (),
+            _ => continue,
+        }
+
+        let path = attr.path();
+        if path.is_ident("doc") {
+            continue;
+        } else if path.is_ident("show") {
+            let add_show: Path = match attr.meta {
+                Meta::Path(_) => parse_quote!(::jlrs::data::types::base_methods::add_show_display),
+                Meta::List(_) => {
+                    let show_with: Ident = attr.parse_args()?;
+                    if show_with == "Display" {
+                        parse_quote!(::jlrs::data::types::base_methods::add_show_display)
+                    } else if show_with == "Debug" {
+                        parse_quote!(::jlrs::data::types::base_methods::add_show_debug)
+                    } else {
+                        Err(Error::new_spanned(
+                            show_with,
+                            "expected `Display` or `Debug`",
+                        ))?
+                    }
+                }
+                Meta::NameValue(_) => Err(Error::new_spanned(
+                    attr,
+                    "expected `#[show]`, `#[show(Display)]` or `#[show(Debug)]`",
+                ))?,
+            };
+            add_methods.push(add_show);
+        } else if has_outer_path_attr(std::slice::from_ref(attr), "eq") {
+            add_methods.push(parse_quote!(::jlrs::data::types::base_methods::add_eq));
+        } else if has_outer_path_attr(std::slice::from_ref(attr), "hash") {
+            add_methods.push(parse_quote!(::jlrs::data::types::base_methods::add_hash));
+        } else if has_outer_path_attr(std::slice::from_ref(attr), "copy") {
+            add_methods.push(parse_quote!(::jlrs::data::types::base_methods::add_copy));
+        } else if has_outer_path_attr(std::slice::from_ref(attr), "properties") {
+            add_methods.push(parse_quote!(
+                ::jlrs::data::types::exposed_fields::add_property_methods
+            ));
+        } else if has_outer_path_attr(std::slice::from_ref(attr), "close") {
+            add_methods.push(parse_quote!(::jlrs::data::types::base_methods::add_close));
+        } else if has_outer_path_attr(std::slice::from_ref(attr), "serialize") {
+            add_methods.push(parse_quote!(
+                ::jlrs::data::types::serializable_opaque::add_serialization_methods
+            ));
+        } else {
+            Err(Error::new_spanned(
+                attr,
+                "unknown attribute, expected `#[show]`, `#[eq]`, `#[hash]`, `#[copy]`, `#[properties]`, `#[close]` or `#[serialize]`",
+            ))?
+        }
+    }
+
+    Ok(parse_quote! {
+        {
+            <#ty as ::jlrs::data::types::foreign_type::OpaqueType>::add_methods(&*frame, array, function_info_ty);
+            #(
+                #add_methods::<#ty, _>(&*frame, array, function_info_ty);
+            )*
+        }
+    })
+}
+
+// Exported traits only support docstrings.
+fn check_trait_attrs(attrs: Option<&[Attribute]>) -> Result<()> {
+    for attr in attrs.unwrap_or_default() {
+        match attr.style {
+            AttrStyle::Outer => (),
+            _ => continue,
+        }
+
+        if !attr.path().is_ident("doc") {
+            Err(Error::new_spanned(
+                attr,
+                "unknown attribute, exported traits only support docstrings",
+            ))?
+        }
+    }
+
+    Ok(())
+}
+
 fn reinit_type_fragment(info: &ExportedType) -> Expr {
     {
         let override_module_fragment = override_module_fragment(&info.name_override);
@@ -2380,6 +2779,7 @@ fn reinit_type_fragment(info: &ExportedType) -> Expr {
             .to_string();
 
         let ty = format_ident!("{}", name_ident);
+        let register_trait_impl = register_trait_impl_fragment(info);
 
         parse_quote! {
             {
@@ -2393,6 +2793,9 @@ fn reinit_type_fragment(info: &ExportedType) -> Expr {
                     .unwrap();
 
                 <#ty as ::jlrs::data::types::foreign_type::OpaqueType>::reinit_type(dt);
+
+                let ty = dt;
+                #register_trait_impl;
             }
         }
     }
@@ -2578,7 +2981,10 @@ fn method_arg_type_fragments<'a>(
         _ => None,
     };
 
+    let is_dyn = matches!(info.parent, Type::TraitObject(_));
+
     let invoke_fn = match takes_self {
+        _ if is_dyn => invoke_fn_dyn_self_method_fragment(info, untracked_self, gc_safe),
         None => invoke_fn_no_self_method_fragment(info, gc_safe),
         Some((true, true)) => invoke_fn_mut_self_method_fragment(info, untracked_self, gc_safe),
         Some((false, true)) => invoke_fn_ref_self_method_fragment(info, untracked_self, gc_safe),
@@ -2601,6 +3007,11 @@ fn method_arg_type_fragments<'a>(
                         }
                     }
                 },
+                _ if is_dyn => {
+                    parse_quote! {
+                        ::jlrs::data::managed::datatype::DataType::any_type(&frame)
+                    }
+                },
                 _ => {
                     let span = parent.span();
                     parse_quote_spanned! {
@@ -2629,6 +3040,12 @@ fn method_arg_type_fragments<'a>(
                         }
                     }
                 },
+                _ if is_dyn => {
+                    let err = format!("{} has not been exported", parent.to_token_stream());
+                    parse_quote! {
+                        ::jlrs::data::types::foreign_type::trait_type::<#parent, _>(&frame).expect(#err)
+                    }
+                },
                 _ => {
                     let span = parent.span();
                     parse_quote_spanned! {
@@ -2818,6 +3235,84 @@ fn invoke_fn_no_self_method_fragment_in_env(
     }
 }
 
+fn invoke_fn_dyn_self_method_fragment(
+    info: &ExportedMethod,
+    untracked_self: bool,
+    gc_safe: bool,
+) -> ItemFn {
+    let name = &info.func.ident;
+    let span = info.func.ident.span();
+    let ty = &info.parent;
+    let ret_ty = &info.func.output;
+    let new_ret_ty = as_return_as(ret_ty);
+    let ret_ty = take_type(ret_ty.clone());
+
+    let args = &info.func.inputs;
+    match args.first() {
+        Some(FnArg::Receiver(r)) if r.reference.is_some() && r.mutability.is_none() => (),
+        _ => {
+            let err = Error::new(span, "methods of traits must take `&self`").to_compile_error();
+            return parse_quote! {
+                unsafe extern "C" fn invoke() {
+                    #err
+                }
+            };
+        }
+    }
+
+    let mut cloned_args = args.clone();
+    let first = cloned_args.first_mut().unwrap();
+
+    *first = parse_quote! {
+        this: ::jlrs::data::managed::value::Value<'static, 'static>
+    };
+
+    let args_self_renamed = cloned_args;
+
+    let names = args.iter().skip(1).map(|arg| match arg {
+        FnArg::Typed(ty) => &ty.pat,
+        _ => unreachable!(),
+    });
+
+    let names = Punctuated::<_, Comma>::from_iter(names);
+
+    let to_ref_expr: Expr = if untracked_self {
+        parse_quote! {
+            ::jlrs::data::types::foreign_type::trait_object::<#ty>(this)
+                .map(|this| this.as_ref())
+                .ok_or(())
+        }
+    } else {
+        parse_quote! { this.track_shared_dyn::<#ty>() }
+    };
+
+    let call_expr: Expr = if gc_safe {
+        parse_quote! {
+            ::jlrs::memory::gc::gc_safe(|| {
+                this.#name(#names)
+            })
+        }
+    } else {
+        parse_quote! { this.#name(#names) }
+    };
+
+    let fname = format!("{}\0", name);
+
+    parse_quote_spanned! {
+        span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
+            ::jlrs::data::types::foreign_type::throw_if_destroyed(this);
+            ::jlrs::data::types::foreign_type::throw_if_not_implemented::<#ty>(#fname, this);
+            match #to_ref_expr {
+                Ok(this) => {
+                    let res = #call_expr;
+                    <#ret_ty as ::jlrs::convert::ccall_types::CCallReturn>::return_or_throw(res)
+                },
+                Err(_) => ::jlrs::runtime::handle::ccall::CCall::throw_borrow_exception()
+            }
+        }
+    }
+}
+
 fn invoke_fn_ref_self_method_fragment(
     info: &ExportedMethod,
     untracked_self: bool,
@@ -2865,6 +3360,7 @@ fn invoke_fn_ref_self_method_fragment(
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
+            ::jlrs::data::types::foreign_type::throw_if_destroyed(this);
             match #to_ref_expr {
                 Ok(this) => {
                     let res = #call_expr;
@@ -2924,6 +3420,7 @@ fn invoke_fn_ref_self_method_fragment_in_env(
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
+            ::jlrs::data::types::foreign_type::throw_if_destroyed(this);
             match #to_ref_expr {
                 Ok(this) => {
                     let res = #call_expr;
@@ -2982,6 +3479,7 @@ fn invoke_fn_move_self_method_fragment(
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
+            ::jlrs::data::types::foreign_type::throw_if_destroyed(this);
             match #to_ref_expr {
                 Ok(this) => {
                     let res = #call_expr;
@@ -3041,6 +3539,7 @@ fn invoke_fn_move_self_method_fragment_in_env(
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
+            ::jlrs::data::types::foreign_type::throw_if_destroyed(this);
             match #to_ref_expr {
                 Ok(this) => {
                     let res = #call_expr;
@@ -3098,6 +3597,7 @@ fn invoke_fn_mut_self_method_fragment(
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
+            ::jlrs::data::types::foreign_type::throw_if_destroyed(this);
             match #to_ref_expr {
                 #[allow(unused_mut)]
                 Ok(mut this) => {
@@ -3157,6 +3657,7 @@ fn invoke_fn_mut_self_method_fragment_in_env(
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
+            ::jlrs::data::types::foreign_type::throw_if_destroyed(this);
             match #to_ref_expr {
                 #[allow(unused_mut)]
                 Ok(mut this) => {
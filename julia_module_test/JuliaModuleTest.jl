@@ -1,6 +1,7 @@
 include("JuliaModule.jl")
 
 using JlrsCore.Ledger
+using Serialization
 using Test
 
 @testset "Freestanding functions" begin
@@ -27,6 +28,12 @@ using Test
 
     @test JuliaModuleTest.returns_typed_value()
     @inferred JuliaModuleTest.returns_typed_value()
+
+    @test JuliaModuleTest.calls_julia_fn(+, 1.0, 2.0) == 3.0
+    @test JuliaModuleTest.calls_julia_fn((a, b) -> a * b, 2.0, 3.0) == 6.0
+    @test_throws JlrsCore.JlrsError JuliaModuleTest.calls_julia_fn((a, b) -> error("oops"), 2.0, 3.0)
+    @test_throws JlrsCore.JlrsError JuliaModuleTest.calls_julia_fn((a, b) -> 1, 2.0, 3.0)
+    @test_throws MethodError JuliaModuleTest.calls_julia_fn(1, 2.0, 3.0)
 end
 
 @testset "Arrays" begin
@@ -142,6 +149,98 @@ end
     @test JuliaModuleTest.extract_inner(foreign_thing) == UInt32(1)
 end
 
+@testset "Base methods of opaque types" begin
+    point = JuliaModuleTest.OpaquePoint(1, 2)
+    @test repr(point) == "(1, 2)"
+    @test sprint(show, point) == "(1, 2)"
+
+    same = JuliaModuleTest.OpaquePoint(1, 2)
+    other = JuliaModuleTest.OpaquePoint(2, 1)
+    @test point == same
+    @test point != other
+    @test hash(point) == hash(same)
+    @test length(Set([point, same, other])) == 2
+
+    copied = copy(point)
+    @test copied == point
+    @test copied !== point
+    @test deepcopy([point])[1] == point
+end
+
+@testset "Closing opaque types" begin
+    point = JuliaModuleTest.OpaquePoint(3, 4)
+    @test isopen(point)
+    @test JuliaModuleTest.norm_squared(point) == 25
+
+    close(point)
+    @test !isopen(point)
+    @test_throws JlrsCore.JlrsError JuliaModuleTest.norm_squared(point)
+    @test_throws JlrsCore.JlrsError repr(point)
+    @test_throws JlrsCore.JlrsError copy(point)
+
+    close(point)
+    @test !isopen(point)
+end
+
+@testset "Exposed fields of opaque types" begin
+    particle = JuliaModuleTest.Particle(1.0, -1)
+    @test propertynames(particle) == (:mass, :charge)
+    @test particle.mass == 1.0
+    @test particle.charge == Int32(-1)
+
+    particle.charge = Int32(2)
+    @test particle.charge == Int32(2)
+    @test_throws JlrsCore.JlrsError particle.charge = 2.0
+    @test_throws JlrsCore.JlrsError particle.n_updates
+
+    JuliaModuleTest.update!(particle, 2.0)
+    @test particle.mass == 2.0
+    @test JuliaModuleTest.n_updates(particle) == 1
+end
+
+@testset "Serializable opaque type" begin
+    point = JuliaModuleTest.OpaquePoint(3, -4)
+    io = IOBuffer()
+    serialize(io, point)
+    seekstart(io)
+    deserialized = deserialize(io)
+    @test deserialized isa JuliaModuleTest.OpaquePoint
+    @test deserialized == point
+    @test deserialized !== point
+
+    io = IOBuffer()
+    serialize(io, [point, JuliaModuleTest.OpaquePoint(0, 0)])
+    seekstart(io)
+    @test deserialize(io) == [point, JuliaModuleTest.OpaquePoint(0, 0)]
+end
+
+@testset "RustIterator" begin
+    squares = JuliaModuleTest.squares(UInt64(4))
+    @test squares isa JuliaModuleTest.Squares
+    @test eltype(squares) == UInt64
+    @test Base.IteratorSize(squares) == Base.SizeUnknown()
+    @test collect(squares) == UInt64[0, 1, 4, 9]
+    @test isnothing(iterate(squares))
+
+    @test sum(JuliaModuleTest.squares(UInt64(3))) == 5
+    @test isempty(collect(JuliaModuleTest.squares(UInt64(0))))
+end
+
+@testset "Traits" begin
+    @test isabstracttype(JuliaModuleTest.Shape)
+    @test JuliaModuleTest.Circle <: JuliaModuleTest.Shape
+    @test JuliaModuleTest.Rectangle <: JuliaModuleTest.Shape
+
+    circle = JuliaModuleTest.Circle(1.0)
+    rect = JuliaModuleTest.Rectangle(2.0, 3.0)
+    @test JuliaModuleTest.area(circle) ≈ π
+    @test JuliaModuleTest.area(rect) == 6.0
+    @test JuliaModuleTest.n_corners(circle) == 0
+    @test JuliaModuleTest.n_corners(rect) == 4
+    @test length(methods(JuliaModuleTest.area)) == 1
+    @test_throws MethodError JuliaModuleTest.area(1.0)
+end
+
 @testset "Associated function" begin
     @test JuliaModuleTest.assoc_func() == 1
     @inferred JuliaModuleTest.assoc_func()
@@ -1,4 +1,4 @@
-use std::ops::AddAssign;
+use std::{fmt, ops::AddAssign};
 
 use jlrs::{
     data::{
@@ -9,11 +9,13 @@ use jlrs::{
         types::{
             construct_type::ConstructType,
             foreign_type::{ForeignType, OpaqueType, ParametricBase, ParametricVariant},
+            serializable_opaque::SerializableOpaque,
         },
     },
+    error::{JlrsError, JlrsResult},
     impl_type_parameters, impl_variant_parameters,
     memory::gc::{mark_queue_obj, write_barrier},
-    prelude::{Managed, Value, ValueRef},
+    prelude::{ExposedFields, Managed, Value, ValueRef},
     weak_handle_unchecked,
 };
 
@@ -43,6 +45,81 @@ impl OpaqueInt {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OpaquePoint {
+    x: i64,
+    y: i64,
+}
+
+unsafe impl OpaqueType for OpaquePoint {}
+
+impl OpaquePoint {
+    pub fn new(x: i64, y: i64) -> TypedValueRet<OpaquePoint> {
+        let weak_handle = unsafe { weak_handle_unchecked!() };
+        TypedValue::new(weak_handle, OpaquePoint { x, y }).leak()
+    }
+
+    pub fn norm_squared(&self) -> i64 {
+        self.x * self.x + self.y * self.y
+    }
+}
+
+impl SerializableOpaque for OpaquePoint {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.x.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&self.y.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> JlrsResult<Self> {
+        if bytes.len() != 16 {
+            Err(JlrsError::exception("invalid OpaquePoint"))?
+        }
+
+        let x = i64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let y = i64::from_le_bytes(bytes[8..].try_into().unwrap());
+        Ok(OpaquePoint { x, y })
+    }
+}
+
+impl fmt::Display for OpaquePoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(ExposedFields)]
+pub struct Particle {
+    #[jlrs(expose)]
+    mass: f64,
+    #[jlrs(expose)]
+    charge: i32,
+    n_updates: usize,
+}
+
+unsafe impl OpaqueType for Particle {}
+
+impl Particle {
+    pub fn new(mass: f64, charge: i32) -> TypedValueRet<Particle> {
+        let weak_handle = unsafe { weak_handle_unchecked!() };
+        let particle = Particle {
+            mass,
+            charge,
+            n_updates: 0,
+        };
+        TypedValue::new(weak_handle, particle).leak()
+    }
+
+    pub fn update(&mut self, mass: f64) {
+        self.mass = mass;
+        self.n_updates += 1;
+    }
+
+    pub fn n_updates(&self) -> usize {
+        self.n_updates
+    }
+}
+
 #[derive(Clone)]
 pub struct POpaque<T> {
     value: T,
@@ -0,0 +1,7 @@
+use jlrs::data::types::rust_iterator::RustIterator;
+
+pub type Squares = RustIterator<u64>;
+
+pub fn squares(n: u64) -> Squares {
+    RustIterator::new((0..n).map(|i| i * i))
+}
@@ -0,0 +1,6 @@
+use jlrs::{data::managed::julia_fn::JuliaFn, error::JlrsResult, weak_handle_unchecked};
+
+pub fn calls_julia_fn(f: JuliaFn<(f64, f64), f64>, a: f64, b: f64) -> JlrsResult<f64> {
+    let weak_handle = unsafe { weak_handle_unchecked!() };
+    unsafe { f.call(&weak_handle, (a, b)) }
+}
@@ -3,6 +3,7 @@ use jlrs::{
         managed::{
             array::{ArrayRet, RankedArrayRet, TypedArrayRet, TypedRankedArrayRet},
             ccall_ref::{CCallRef, CCallRefRet},
+            julia_fn::JuliaFn,
             value::{
                 typed::{TypedValue, TypedValueRet},
                 ValueRet,
@@ -23,7 +24,10 @@ pub mod exceptions;
 pub mod foreign;
 pub mod generics;
 pub mod isbits;
+pub mod iterators;
+pub mod julia_fn;
 pub mod ref_types;
+pub mod traits;
 pub mod typed_value;
 
 use array::*;
@@ -32,7 +36,10 @@ use exceptions::*;
 use foreign::*;
 use generics::*;
 use isbits::*;
+use iterators::*;
+use julia_fn::*;
 use ref_types::*;
+use traits::*;
 use typed_value::*;
 
 julia_module! {
@@ -61,6 +68,7 @@ julia_module! {
     fn returns_jlrs_result(throw_err: Bool) -> JlrsResult<i32>;
     fn returns_ref_bool() -> CCallRefRet<bool>;
     fn returns_typed_value() -> TypedValueRet<bool>;
+    fn calls_julia_fn(f: JuliaFn<(f64, f64), f64>, a: f64, b: f64) -> JlrsResult<f64>;
     fn takes_generics_from_env(array: TypedValue<tvar!('A')>, data: TypedValue<tvar!('T')>) use GenericEnv;
     fn takes_generic_typed_ranked_arrays_ctor(
         a: TypedValue<ArrayTypeConstructor<tvar!('T'), ConstantIsize<1>>>,
@@ -105,6 +113,34 @@ julia_module! {
     in OpaqueInt fn get(&self) -> i32 as unbox_opaque_untracked;
     in OpaqueInt fn get_cloned(self) -> i32;
 
+    #[show]
+    #[eq]
+    #[hash]
+    #[copy]
+    #[serialize]
+    #[close]
+    struct OpaquePoint;
+    in OpaquePoint fn new(x: i64, y: i64) -> TypedValueRet<OpaquePoint> as OpaquePoint;
+    in OpaquePoint fn norm_squared(&self) -> i64;
+
+    #[properties]
+    struct Particle;
+    in Particle fn new(mass: f64, charge: i32) -> TypedValueRet<Particle> as Particle;
+    in Particle fn update(&mut self, mass: f64) as update!;
+    in Particle fn n_updates(&self) -> usize;
+
+    struct Squares;
+    fn squares(n: u64) -> Squares;
+
+    trait Shape;
+    struct Circle: Shape;
+    in Circle fn new(radius: f64) -> TypedValueRet<Circle> as Circle;
+    struct Rectangle: Shape;
+    in Rectangle fn new(width: f64, height: f64) -> TypedValueRet<Rectangle> as Rectangle;
+    in dyn Shape fn area(&self) -> f64;
+    #[untracked_self]
+    in dyn Shape fn n_corners(&self) -> usize;
+
     struct ForeignThing;
     in ForeignThing fn new(value: Value<'_, 'static>) -> TypedValueRet<ForeignThing> as ForeignThing;
 
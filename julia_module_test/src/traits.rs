@@ -0,0 +1,59 @@
+use jlrs::{
+    data::{
+        managed::value::typed::{TypedValue, TypedValueRet},
+        types::foreign_type::OpaqueType,
+    },
+    weak_handle_unchecked,
+};
+
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn n_corners(&self) -> usize;
+}
+
+pub struct Circle {
+    radius: f64,
+}
+
+impl Circle {
+    pub fn new(radius: f64) -> TypedValueRet<Circle> {
+        let weak_handle = unsafe { weak_handle_unchecked!() };
+        TypedValue::new(weak_handle, Circle { radius }).leak()
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+
+    fn n_corners(&self) -> usize {
+        0
+    }
+}
+
+unsafe impl OpaqueType for Circle {}
+
+pub struct Rectangle {
+    width: f64,
+    height: f64,
+}
+
+impl Rectangle {
+    pub fn new(width: f64, height: f64) -> TypedValueRet<Rectangle> {
+        let weak_handle = unsafe { weak_handle_unchecked!() };
+        TypedValue::new(weak_handle, Rectangle { width, height }).leak()
+    }
+}
+
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    fn n_corners(&self) -> usize {
+        4
+    }
+}
+
+unsafe impl OpaqueType for Rectangle {}